@@ -79,7 +79,8 @@ async fn main() {
         julia
             .register_task::<MyTask, _>(sender)
             .dispatch_any()
-            .await;
+            .await
+            .unwrap();
         receiver.await.unwrap().unwrap();
     }
 
@@ -96,7 +97,8 @@ async fn main() {
             sender1,
         )
         .dispatch_any()
-        .await;
+        .await
+        .unwrap();
 
     julia
         .task(
@@ -107,7 +109,8 @@ async fn main() {
             sender2,
         )
         .dispatch_any()
-        .await;
+        .await
+        .unwrap();
 
     // Receive the results of the tasks.
     let res1 = receiver1.await.unwrap().unwrap();
@@ -88,7 +88,8 @@ async fn main() {
         julia
             .register_task::<MyTask, _>(sender)
             .dispatch_any()
-            .await;
+            .await
+            .unwrap();
         receiver.await.unwrap().unwrap();
     }
 
@@ -105,7 +106,8 @@ async fn main() {
             sender1,
         )
         .dispatch_any()
-        .await;
+        .await
+        .unwrap();
 
     julia
         .task(
@@ -116,7 +118,8 @@ async fn main() {
             sender2,
         )
         .dispatch_any()
-        .await;
+        .await
+        .unwrap();
 
     // Receive the results of the tasks.
     let res1 = receiver1.await.unwrap().unwrap();
@@ -102,7 +102,8 @@ async fn main() {
         julia
             .register_persistent::<MyTask, _>(s)
             .dispatch_main()
-            .await;
+            .await
+            .unwrap();
         r.await.unwrap().unwrap();
     }
 
@@ -118,7 +119,8 @@ async fn main() {
                 handle_sender,
             )
             .dispatch_main()
-            .await;
+            .await
+            .unwrap();
 
         handle_receiver
             .await
@@ -284,6 +284,9 @@ fn compile_jlrs_cc(julia_dir: &str, target: Option<Target>) {
     #[cfg(feature = "julia-1-10")]
     c.define("JULIA_1_10", None);
 
+    #[cfg(feature = "julia-1-11")]
+    c.define("JULIA_1_11", None);
+
     #[cfg(feature = "fast-tls")]
     c.define("JLRS_FAST_TLS", None);
 
@@ -359,14 +362,9 @@ fn generate_bindings(julia_dir: &str) {
         .allowlist_function("jl_apply_type")
         .allowlist_function("jl_array_ptr_1d_push")
         .allowlist_function("jl_array_ptr_1d_append")
-        .allowlist_function("jl_array_del_beg")
         .allowlist_function("jl_array_del_end")
         .allowlist_function("jl_array_eltype")
-        .allowlist_function("jl_array_grow_beg")
         .allowlist_function("jl_array_grow_end")
-        .allowlist_function("jl_array_typetagdata")
-        .allowlist_function("jl_arrayset")
-        .allowlist_function("jl_arrayref")
         .allowlist_function("jl_atexit_hook")
         .allowlist_function("jl_atomic_cmpswap_bits")
         .allowlist_function("jl_atomic_bool_cmpswap_bits")
@@ -433,6 +431,7 @@ fn generate_bindings(julia_dir: &str) {
         .allowlist_function("jl_init__threading")
         .allowlist_function("jl_init_with_image")
         .allowlist_function("jl_init_with_image__threading")
+        .allowlist_function("jl_invoke")
         .allowlist_function("jl_is_debugbuild")
         .allowlist_function("jl_is_imported")
         .allowlist_function("jl_is_initialized")
@@ -488,10 +487,12 @@ fn generate_bindings(julia_dir: &str) {
         .allowlist_function("jl_ver_string")
         .allowlist_function("jl_yield")
         .allowlist_function("jlrs_catch_wrapper")
+        .allowlist_function("jlrs_catch_wrapper_handled")
+        .allowlist_function("jlrs_catch_backtrace")
+        .allowlist_function("jlrs_lookup_code_address")
         .allowlist_function("jlrs_lock")
         .allowlist_function("jlrs_typeof")
         .allowlist_function("jlrs_unlock")
-        .allowlist_function("jlrs_array_data_owner_offset")
         .allowlist_function("jlrs_gc_queue_multiroot")
         .allowlist_function("jlrs_pgcstack")
         .allowlist_function("jl_excstack_state")
@@ -687,6 +688,42 @@ fn generate_bindings(julia_dir: &str) {
         builder = builder.allowlist_function("jl_binding_type");
     }
 
+    #[cfg(not(any(
+        feature = "julia-1-6",
+        feature = "julia-1-7",
+        feature = "julia-1-8",
+        feature = "julia-1-9",
+        feature = "julia-1-10"
+    )))]
+    {
+        builder = builder
+            .allowlist_function("jl_alloc_genericmemory")
+            .allowlist_function("jl_genericmemory_owner")
+            .allowlist_function("jl_memoryrefget")
+            .allowlist_function("jl_memoryrefindex")
+            .allowlist_function("jl_memoryrefset")
+            .allowlist_function("jl_ptr_to_genericmemory")
+            .allowlist_type("jl_genericmemory_t")
+            .allowlist_type("jl_genericmemoryref_t");
+    }
+
+    #[cfg(any(
+        feature = "julia-1-6",
+        feature = "julia-1-7",
+        feature = "julia-1-8",
+        feature = "julia-1-9",
+        feature = "julia-1-10"
+    ))]
+    {
+        builder = builder
+            .allowlist_function("jl_array_del_beg")
+            .allowlist_function("jl_array_grow_beg")
+            .allowlist_function("jl_array_typetagdata")
+            .allowlist_function("jl_arrayref")
+            .allowlist_function("jl_arrayset")
+            .allowlist_function("jlrs_array_data_owner_offset");
+    }
+
     let bindings = builder.generate().expect("Unable to generate bindings");
 
     let mut bindings_bytes = Vec::new();
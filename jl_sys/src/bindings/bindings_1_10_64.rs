@@ -3732,6 +3732,21 @@ extern "C" {
     ),
     link(name = "libjulia", kind = "raw-dylib")
 )]
+extern "C" {
+    pub fn jl_invoke(
+        F: *mut jl_value_t,
+        args: *mut *mut jl_value_t,
+        nargs: u32,
+        meth: *mut jl_method_instance_t,
+    ) -> *mut jl_value_t;
+}
+#[cfg_attr(
+    all(
+        any(windows, target_os = "windows", feature = "windows"),
+        any(target_env = "msvc", feature = "yggdrasil")
+    ),
+    link(name = "libjulia", kind = "raw-dylib")
+)]
 extern "C" {
     pub fn jl_call(
         f: *mut jl_function_t,
@@ -4168,6 +4183,7 @@ pub struct _jl_task_t {
 pub const jlrs_catch_tag_t_JLRS_CATCH_OK: jlrs_catch_tag_t = 0;
 pub const jlrs_catch_tag_t_JLRS_CATCH_EXCEPTION: jlrs_catch_tag_t = 1;
 pub const jlrs_catch_tag_t_JLRS_CATCH_PANIC: jlrs_catch_tag_t = 2;
+pub const jlrs_catch_tag_t_JLRS_CATCH_RETHROW: jlrs_catch_tag_t = 3;
 pub type jlrs_catch_tag_t = ::std::os::raw::c_uint;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -4188,6 +4204,29 @@ extern "C" {
         result: *mut ::std::os::raw::c_void,
     ) -> jlrs_catch_t;
 }
+pub type jlrs_exception_handler_caller_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        arg1: *mut ::std::os::raw::c_void,
+        arg2: *mut jl_value_t,
+        arg3: *mut ::std::os::raw::c_void,
+    ) -> jlrs_catch_t,
+>;
+extern "C" {
+    pub fn jlrs_catch_wrapper_handled(
+        callback: *mut ::std::os::raw::c_void,
+        caller: jlrs_callback_caller_t,
+        result: *mut ::std::os::raw::c_void,
+        handler: *mut ::std::os::raw::c_void,
+        handler_caller: jlrs_exception_handler_caller_t,
+        handler_result: *mut ::std::os::raw::c_void,
+    ) -> jlrs_catch_t;
+}
+extern "C" {
+    pub fn jlrs_catch_backtrace(ips: *mut usize, capacity: usize) -> usize;
+}
+extern "C" {
+    pub fn jlrs_lookup_code_address(ip: *mut ::std::os::raw::c_void) -> *mut jl_value_t;
+}
 extern "C" {
     pub fn jlrs_array_data_owner_offset(n_dims: u16) -> uint_t;
 }
@@ -208,148 +208,22 @@ pub struct jl_svec_t {
     pub length: usize,
 }
 #[repr(C)]
-#[repr(align(2))]
 #[derive(Debug, Copy, Clone)]
-pub struct jl_array_flags_t {
-    pub _bitfield_align_1: [u16; 0],
-    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 2usize]>,
-}
-impl jl_array_flags_t {
-    #[inline]
-    pub fn how(&self) -> u16 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 2u8) as u16) }
-    }
-    #[inline]
-    pub fn set_how(&mut self, val: u16) {
-        unsafe {
-            let val: u16 = ::std::mem::transmute(val);
-            self._bitfield_1.set(0usize, 2u8, val as u64)
-        }
-    }
-    #[inline]
-    pub fn ndims(&self) -> u16 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(2usize, 9u8) as u16) }
-    }
-    #[inline]
-    pub fn set_ndims(&mut self, val: u16) {
-        unsafe {
-            let val: u16 = ::std::mem::transmute(val);
-            self._bitfield_1.set(2usize, 9u8, val as u64)
-        }
-    }
-    #[inline]
-    pub fn pooled(&self) -> u16 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(11usize, 1u8) as u16) }
-    }
-    #[inline]
-    pub fn set_pooled(&mut self, val: u16) {
-        unsafe {
-            let val: u16 = ::std::mem::transmute(val);
-            self._bitfield_1.set(11usize, 1u8, val as u64)
-        }
-    }
-    #[inline]
-    pub fn ptrarray(&self) -> u16 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(12usize, 1u8) as u16) }
-    }
-    #[inline]
-    pub fn set_ptrarray(&mut self, val: u16) {
-        unsafe {
-            let val: u16 = ::std::mem::transmute(val);
-            self._bitfield_1.set(12usize, 1u8, val as u64)
-        }
-    }
-    #[inline]
-    pub fn hasptr(&self) -> u16 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(13usize, 1u8) as u16) }
-    }
-    #[inline]
-    pub fn set_hasptr(&mut self, val: u16) {
-        unsafe {
-            let val: u16 = ::std::mem::transmute(val);
-            self._bitfield_1.set(13usize, 1u8, val as u64)
-        }
-    }
-    #[inline]
-    pub fn isshared(&self) -> u16 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(14usize, 1u8) as u16) }
-    }
-    #[inline]
-    pub fn set_isshared(&mut self, val: u16) {
-        unsafe {
-            let val: u16 = ::std::mem::transmute(val);
-            self._bitfield_1.set(14usize, 1u8, val as u64)
-        }
-    }
-    #[inline]
-    pub fn isaligned(&self) -> u16 {
-        unsafe { ::std::mem::transmute(self._bitfield_1.get(15usize, 1u8) as u16) }
-    }
-    #[inline]
-    pub fn set_isaligned(&mut self, val: u16) {
-        unsafe {
-            let val: u16 = ::std::mem::transmute(val);
-            self._bitfield_1.set(15usize, 1u8, val as u64)
-        }
-    }
-    #[inline]
-    pub fn new_bitfield_1(
-        how: u16,
-        ndims: u16,
-        pooled: u16,
-        ptrarray: u16,
-        hasptr: u16,
-        isshared: u16,
-        isaligned: u16,
-    ) -> __BindgenBitfieldUnit<[u8; 2usize]> {
-        let mut __bindgen_bitfield_unit: __BindgenBitfieldUnit<[u8; 2usize]> = Default::default();
-        __bindgen_bitfield_unit.set(0usize, 2u8, {
-            let how: u16 = unsafe { ::std::mem::transmute(how) };
-            how as u64
-        });
-        __bindgen_bitfield_unit.set(2usize, 9u8, {
-            let ndims: u16 = unsafe { ::std::mem::transmute(ndims) };
-            ndims as u64
-        });
-        __bindgen_bitfield_unit.set(11usize, 1u8, {
-            let pooled: u16 = unsafe { ::std::mem::transmute(pooled) };
-            pooled as u64
-        });
-        __bindgen_bitfield_unit.set(12usize, 1u8, {
-            let ptrarray: u16 = unsafe { ::std::mem::transmute(ptrarray) };
-            ptrarray as u64
-        });
-        __bindgen_bitfield_unit.set(13usize, 1u8, {
-            let hasptr: u16 = unsafe { ::std::mem::transmute(hasptr) };
-            hasptr as u64
-        });
-        __bindgen_bitfield_unit.set(14usize, 1u8, {
-            let isshared: u16 = unsafe { ::std::mem::transmute(isshared) };
-            isshared as u64
-        });
-        __bindgen_bitfield_unit.set(15usize, 1u8, {
-            let isaligned: u16 = unsafe { ::std::mem::transmute(isaligned) };
-            isaligned as u64
-        });
-        __bindgen_bitfield_unit
-    }
+pub struct jl_genericmemory_t {
+    pub length: usize,
+    pub ptr: *mut ::std::os::raw::c_void,
 }
 #[repr(C)]
-#[derive(Copy, Clone)]
-pub struct jl_array_t {
-    pub data: *mut ::std::os::raw::c_void,
-    pub length: usize,
-    pub flags: jl_array_flags_t,
-    pub elsize: u16,
-    pub offset: u32,
-    pub nrows: usize,
-    pub __bindgen_anon_1: jl_array_t__bindgen_ty_1,
+#[derive(Debug, Copy, Clone)]
+pub struct jl_genericmemoryref_t {
+    pub ptr_or_offset: *mut ::std::os::raw::c_void,
+    pub mem: *mut jl_genericmemory_t,
 }
 #[repr(C)]
-#[derive(Copy, Clone)]
-pub union jl_array_t__bindgen_ty_1 {
-    pub maxsize: usize,
-    pub ncols: usize,
+#[derive(Debug, Copy, Clone)]
+pub struct jl_array_t {
+    pub ref_: jl_genericmemoryref_t,
+    pub dimsize: [usize; 0usize],
 }
 pub type jl_tupletype_t = _jl_datatype_t;
 pub type jl_method_instance_t = _jl_method_instance_t;
@@ -877,6 +751,14 @@ pub struct jl_datatype_layout_t {
     pub _bitfield_1: __BindgenBitfieldUnit<[u8; 2usize]>,
 }
 impl jl_datatype_layout_t {
+    #[inline]
+    pub fn arrayelem_isboxed(&self) -> u16 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(3usize, 1u8) as u16) }
+    }
+    #[inline]
+    pub fn arrayelem_isunion(&self) -> u16 {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get(4usize, 1u8) as u16) }
+    }
     #[inline]
     pub fn haspadding(&self) -> u16 {
         unsafe { ::std::mem::transmute(self._bitfield_1.get(0usize, 1u8) as u16) }
@@ -2624,16 +2506,6 @@ extern "C" {
     ),
     link(name = "libjulia", kind = "raw-dylib")
 )]
-extern "C" {
-    pub fn jl_array_typetagdata(a: *mut jl_array_t) -> *mut ::std::os::raw::c_char;
-}
-#[cfg_attr(
-    all(
-        any(windows, target_os = "windows", feature = "windows"),
-        any(target_env = "msvc", feature = "yggdrasil")
-    ),
-    link(name = "libjulia", kind = "raw-dylib")
-)]
 extern "C" {
     pub fn jl_compute_fieldtypes(
         st: *mut jl_datatype_t,
@@ -3388,7 +3260,7 @@ extern "C" {
     link(name = "libjulia", kind = "raw-dylib")
 )]
 extern "C" {
-    pub fn jl_arrayref(a: *mut jl_array_t, i: usize) -> *mut jl_value_t;
+    pub fn jl_array_grow_end(a: *mut jl_array_t, inc: usize);
 }
 #[cfg_attr(
     all(
@@ -3398,7 +3270,7 @@ extern "C" {
     link(name = "libjulia", kind = "raw-dylib")
 )]
 extern "C" {
-    pub fn jl_arrayset(a: *mut jl_array_t, v: *mut jl_value_t, i: usize);
+    pub fn jl_array_del_end(a: *mut jl_array_t, dec: usize);
 }
 #[cfg_attr(
     all(
@@ -3408,7 +3280,7 @@ extern "C" {
     link(name = "libjulia", kind = "raw-dylib")
 )]
 extern "C" {
-    pub fn jl_array_grow_end(a: *mut jl_array_t, inc: usize);
+    pub fn jl_alloc_genericmemory(mtype: *mut jl_value_t, nel: usize) -> *mut jl_genericmemory_t;
 }
 #[cfg_attr(
     all(
@@ -3418,7 +3290,32 @@ extern "C" {
     link(name = "libjulia", kind = "raw-dylib")
 )]
 extern "C" {
-    pub fn jl_array_del_end(a: *mut jl_array_t, dec: usize);
+    pub fn jl_ptr_to_genericmemory(
+        mtype: *mut jl_value_t,
+        data: *mut ::std::os::raw::c_void,
+        nel: usize,
+        own_buffer: ::std::os::raw::c_int,
+    ) -> *mut jl_genericmemory_t;
+}
+#[cfg_attr(
+    all(
+        any(windows, target_os = "windows", feature = "windows"),
+        any(target_env = "msvc", feature = "yggdrasil")
+    ),
+    link(name = "libjulia", kind = "raw-dylib")
+)]
+extern "C" {
+    pub fn jl_genericmemory_owner(m: *mut jl_genericmemory_t) -> *mut jl_value_t;
+}
+#[cfg_attr(
+    all(
+        any(windows, target_os = "windows", feature = "windows"),
+        any(target_env = "msvc", feature = "yggdrasil")
+    ),
+    link(name = "libjulia", kind = "raw-dylib")
+)]
+extern "C" {
+    pub fn jl_memoryrefindex(m: jl_genericmemoryref_t, idx: usize) -> jl_genericmemoryref_t;
 }
 #[cfg_attr(
     all(
@@ -3428,7 +3325,10 @@ extern "C" {
     link(name = "libjulia", kind = "raw-dylib")
 )]
 extern "C" {
-    pub fn jl_array_grow_beg(a: *mut jl_array_t, inc: usize);
+    pub fn jl_memoryrefget(
+        m: jl_genericmemoryref_t,
+        isatomic: ::std::os::raw::c_int,
+    ) -> *mut jl_value_t;
 }
 #[cfg_attr(
     all(
@@ -3438,7 +3338,11 @@ extern "C" {
     link(name = "libjulia", kind = "raw-dylib")
 )]
 extern "C" {
-    pub fn jl_array_del_beg(a: *mut jl_array_t, dec: usize);
+    pub fn jl_memoryrefset(
+        m: jl_genericmemoryref_t,
+        v: *mut jl_value_t,
+        isatomic: ::std::os::raw::c_int,
+    );
 }
 #[cfg_attr(
     all(
@@ -3764,6 +3668,21 @@ extern "C" {
     ),
     link(name = "libjulia", kind = "raw-dylib")
 )]
+extern "C" {
+    pub fn jl_invoke(
+        F: *mut jl_value_t,
+        args: *mut *mut jl_value_t,
+        nargs: u32,
+        meth: *mut jl_method_instance_t,
+    ) -> *mut jl_value_t;
+}
+#[cfg_attr(
+    all(
+        any(windows, target_os = "windows", feature = "windows"),
+        any(target_env = "msvc", feature = "yggdrasil")
+    ),
+    link(name = "libjulia", kind = "raw-dylib")
+)]
 extern "C" {
     pub fn jl_call(
         f: *mut jl_function_t,
@@ -4200,6 +4119,7 @@ pub struct _jl_task_t {
 pub const jlrs_catch_tag_t_JLRS_CATCH_OK: jlrs_catch_tag_t = 0;
 pub const jlrs_catch_tag_t_JLRS_CATCH_EXCEPTION: jlrs_catch_tag_t = 1;
 pub const jlrs_catch_tag_t_JLRS_CATCH_PANIC: jlrs_catch_tag_t = 2;
+pub const jlrs_catch_tag_t_JLRS_CATCH_RETHROW: jlrs_catch_tag_t = 3;
 pub type jlrs_catch_tag_t = ::std::os::raw::c_uint;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -4220,8 +4140,28 @@ extern "C" {
         result: *mut ::std::os::raw::c_void,
     ) -> jlrs_catch_t;
 }
+pub type jlrs_exception_handler_caller_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        arg1: *mut ::std::os::raw::c_void,
+        arg2: *mut jl_value_t,
+        arg3: *mut ::std::os::raw::c_void,
+    ) -> jlrs_catch_t,
+>;
+extern "C" {
+    pub fn jlrs_catch_wrapper_handled(
+        callback: *mut ::std::os::raw::c_void,
+        caller: jlrs_callback_caller_t,
+        result: *mut ::std::os::raw::c_void,
+        handler: *mut ::std::os::raw::c_void,
+        handler_caller: jlrs_exception_handler_caller_t,
+        handler_result: *mut ::std::os::raw::c_void,
+    ) -> jlrs_catch_t;
+}
+extern "C" {
+    pub fn jlrs_catch_backtrace(ips: *mut usize, capacity: usize) -> usize;
+}
 extern "C" {
-    pub fn jlrs_array_data_owner_offset(n_dims: u16) -> uint_t;
+    pub fn jlrs_lookup_code_address(ip: *mut ::std::os::raw::c_void) -> *mut jl_value_t;
 }
 extern "C" {
     pub fn jlrs_gc_queue_multiroot(
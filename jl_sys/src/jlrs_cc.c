@@ -12,6 +12,10 @@ JULIA_DEFINE_FAST_TLS
 extern "C"
 {
 #endif
+    // Exported by libjulia and used by `Base.catch_backtrace` and `Base.StackTraces.lookup`.
+    JL_DLLEXPORT jl_value_t *jl_get_backtrace(void);
+    JL_DLLEXPORT jl_value_t *jl_lookup_code_address(void *ip, int skipC);
+
     jlrs_catch_t jlrs_catch_wrapper(void *callback, jlrs_callback_caller_t caller, void *result)
     {
         jlrs_catch_t res = {.tag = JLRS_CATCH_OK, .error = NULL};
@@ -32,10 +36,76 @@ extern "C"
         return res;
     }
 
+    jlrs_catch_t jlrs_catch_wrapper_handled(void *callback, jlrs_callback_caller_t caller, void *result, void *handler, jlrs_exception_handler_caller_t handler_caller, void *handler_result)
+    {
+        jlrs_catch_t res = {.tag = JLRS_CATCH_OK, .error = NULL};
+
+#ifndef JLRS_WINDOWS_LTS
+        JL_TRY
+        {
+            res = caller(callback, result);
+        }
+        JL_CATCH
+        {
+            // The handler is called from the catch block so the exception can be rethrown with
+            // its original backtrace
+            res = handler_caller(handler, jl_current_exception(), handler_result);
+            if (res.tag == JLRS_CATCH_RETHROW)
+            {
+                jl_rethrow();
+            }
+        }
+#else
+    res = caller(callback, result);
+#endif
+        return res;
+    }
+
+    size_t jlrs_catch_backtrace(uintptr_t *ips, size_t capacity)
+    {
+        jl_value_t *bt = jl_svecref(jl_get_backtrace(), 0);
+#ifdef JULIA_1_11
+        uintptr_t *entries = jl_array_data((jl_array_t *)bt, uintptr_t);
+#else
+        uintptr_t *entries = (uintptr_t *)jl_array_data((jl_array_t *)bt);
+#endif
+        size_t len = jl_array_len((jl_array_t *)bt);
+        size_t n = 0;
+
+        for (size_t i = 0; i < len;)
+        {
+            // Extended entries, e.g. interpreter frames, start with -1. The second word encodes
+            // the number of GC-managed values and integers that follow it.
+            if (entries[i] == (uintptr_t)-1)
+            {
+                uintptr_t header = entries[i + 1];
+                i += 2 + (header & 7) + ((header >> 3) & 7);
+                continue;
+            }
+
+            if (n < capacity)
+            {
+                ips[n] = entries[i];
+            }
+
+            n += 1;
+            i += 1;
+        }
+
+        return n;
+    }
+
+    jl_value_t *jlrs_lookup_code_address(void *ip)
+    {
+        return jl_lookup_code_address(ip, 0);
+    }
+
+#ifndef JULIA_1_11
     uint_t jlrs_array_data_owner_offset(uint16_t n_dims)
     {
         return jl_array_data_owner_offset(n_dims);
     }
+#endif
 
     void jlrs_gc_queue_multiroot(jl_value_t *parent, jl_datatype_t *dt, const void *ptr) JL_NOTSAFEPOINT
     {
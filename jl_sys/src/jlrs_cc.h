@@ -298,6 +298,7 @@ struct jlrs_mutex_t
         JLRS_CATCH_OK = 0,
         JLRS_CATCH_EXCEPTION = 1,
         JLRS_CATCH_PANIC = 2,
+        JLRS_CATCH_RETHROW = 3,
     } jlrs_catch_tag_t;
 
     typedef struct
@@ -308,8 +309,14 @@ struct jlrs_mutex_t
 
     typedef jlrs_catch_t (*jlrs_callback_caller_t)(void *, void *);
     jlrs_catch_t jlrs_catch_wrapper(void *callback, jlrs_callback_caller_t caller, void *result);
+    typedef jlrs_catch_t (*jlrs_exception_handler_caller_t)(void *, jl_value_t *, void *);
+    jlrs_catch_t jlrs_catch_wrapper_handled(void *callback, jlrs_callback_caller_t caller, void *result, void *handler, jlrs_exception_handler_caller_t handler_caller, void *handler_result);
+    size_t jlrs_catch_backtrace(uintptr_t *ips, size_t capacity);
+    jl_value_t *jlrs_lookup_code_address(void *ip);
 
+#ifndef JULIA_1_11
     uint_t jlrs_array_data_owner_offset(uint16_t n_dims);
+#endif
     void jlrs_gc_queue_multiroot(jl_value_t *parent, jl_datatype_t *dt, const void *ptr) JL_NOTSAFEPOINT;
 
     int8_t jlrs_gc_safe_enter(jl_ptls_t ptls);
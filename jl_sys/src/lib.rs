@@ -120,6 +120,7 @@ pub unsafe fn jl_svec_data(t: *mut jl_svec_t) -> *mut *mut jl_value_t {
     t.cast::<u8>().add(size_of::<jl_svec_t>()).cast()
 }
 
+#[cfg(not(feature = "julia-1-11"))]
 #[inline]
 pub unsafe fn jl_array_data(array: *mut jl_value_t) -> *mut c_void {
     NonNull::new_unchecked(array)
@@ -129,11 +130,13 @@ pub unsafe fn jl_array_data(array: *mut jl_value_t) -> *mut c_void {
         .cast()
 }
 
+#[cfg(not(feature = "julia-1-11"))]
 #[inline]
 pub unsafe fn jl_array_ndims(array: *mut jl_array_t) -> u16 {
     NonNull::new_unchecked(array).as_ref().flags.ndims()
 }
 
+#[cfg(not(feature = "julia-1-11"))]
 #[inline]
 pub unsafe fn jl_array_data_owner(a: *mut jl_array_t) -> *mut jl_value_t {
     a.cast::<u8>()
@@ -252,11 +255,13 @@ pub unsafe fn jl_field_offset(st: *mut jl_datatype_t, i: isize) -> u32 {
     }
 }
 
+#[cfg(not(feature = "julia-1-11"))]
 #[inline]
 pub unsafe fn jl_array_dims_ptr<'a>(array: *mut jl_array_t) -> *mut usize {
     &mut NonNull::new_unchecked(array).as_mut().nrows
 }
 
+#[cfg(not(feature = "julia-1-11"))]
 #[inline]
 pub unsafe fn jl_array_ptr_set(a: *mut jl_array_t, i: usize, x: *mut c_void) -> *mut jl_value_t {
     assert!(NonNull::new_unchecked(a).as_ref().flags.ptrarray() != 0);
@@ -277,6 +282,154 @@ pub unsafe fn jl_array_ptr_set(a: *mut jl_array_t, i: usize, x: *mut c_void) ->
     x.cast()
 }
 
+// Since Julia 1.11 arrays are backed by a `GenericMemory`, the following functions replace the
+// macros and functions that accessed the fields of the old array layout directly, or that have
+// been removed from the C API.
+
+#[cfg(feature = "julia-1-11")]
+#[inline]
+unsafe fn jl_genericmemory_layout<'a>(m: *mut jl_genericmemory_t) -> &'a jl_datatype_layout_t {
+    let ty = jl_typeof(m.cast()).cast::<jl_datatype_t>();
+    &*NonNull::new_unchecked(ty).as_ref().layout
+}
+
+#[cfg(feature = "julia-1-11")]
+#[inline]
+pub unsafe fn jl_genericmemory_elsize(m: *mut jl_genericmemory_t) -> usize {
+    jl_genericmemory_layout(m).size as usize
+}
+
+#[cfg(feature = "julia-1-11")]
+#[inline]
+pub unsafe fn jl_genericmemory_isboxed(m: *mut jl_genericmemory_t) -> bool {
+    jl_genericmemory_layout(m).arrayelem_isboxed() != 0
+}
+
+#[cfg(feature = "julia-1-11")]
+#[inline]
+pub unsafe fn jl_genericmemory_isbitsunion(m: *mut jl_genericmemory_t) -> bool {
+    jl_genericmemory_layout(m).arrayelem_isunion() != 0
+}
+
+#[cfg(feature = "julia-1-11")]
+#[inline]
+pub unsafe fn jl_genericmemory_typetagdata(m: *mut jl_genericmemory_t) -> *mut u8 {
+    let mem = NonNull::new_unchecked(m).as_ref();
+    mem.ptr
+        .cast::<u8>()
+        .add(mem.length * jl_genericmemory_elsize(m))
+}
+
+#[cfg(feature = "julia-1-11")]
+#[inline]
+pub unsafe fn jl_array_data(array: *mut jl_value_t) -> *mut c_void {
+    let r = NonNull::new_unchecked(array)
+        .cast::<jl_array_t>()
+        .as_ref()
+        .ref_;
+
+    // The data of a bits-union or zero-sized array starts at an offset in the backing memory.
+    if jl_genericmemory_isbitsunion(r.mem) || jl_genericmemory_elsize(r.mem) == 0 {
+        let offset = r.ptr_or_offset as usize * jl_genericmemory_elsize(r.mem);
+        NonNull::new_unchecked(r.mem)
+            .as_ref()
+            .ptr
+            .cast::<u8>()
+            .add(offset)
+            .cast()
+    } else {
+        r.ptr_or_offset
+    }
+}
+
+#[cfg(feature = "julia-1-11")]
+#[inline]
+pub unsafe fn jl_array_ndims(array: *mut jl_array_t) -> u16 {
+    // The rank is the second type parameter of `Array{T, N}`.
+    let ty = jl_typeof(array.cast()).cast::<jl_datatype_t>();
+    let params = NonNull::new_unchecked(ty).as_ref().parameters;
+    let n = *jl_svec_data(params).add(1);
+    *n.cast::<isize>() as u16
+}
+
+#[cfg(feature = "julia-1-11")]
+#[inline]
+pub unsafe fn jl_array_data_owner(a: *mut jl_array_t) -> *mut jl_value_t {
+    jl_genericmemory_owner(NonNull::new_unchecked(a).as_ref().ref_.mem)
+}
+
+#[cfg(feature = "julia-1-11")]
+#[inline]
+pub unsafe fn jl_array_dims_ptr<'a>(array: *mut jl_array_t) -> *mut usize {
+    std::ptr::addr_of_mut!((*array).dimsize).cast()
+}
+
+#[cfg(feature = "julia-1-11")]
+#[inline]
+pub unsafe fn jl_array_typetagdata(a: *mut jl_array_t) -> *mut ::std::os::raw::c_char {
+    let r = NonNull::new_unchecked(a).as_ref().ref_;
+    jl_genericmemory_typetagdata(r.mem)
+        .add(r.ptr_or_offset as usize)
+        .cast()
+}
+
+#[cfg(feature = "julia-1-11")]
+#[inline]
+pub unsafe fn jl_array_ptr_set(a: *mut jl_array_t, i: usize, x: *mut c_void) -> *mut jl_value_t {
+    let mem = NonNull::new_unchecked(a).as_ref().ref_.mem;
+    assert!(jl_genericmemory_isboxed(mem));
+    let a_data: *mut AtomicPtr<jl_value_t> = jl_array_data(a.cast()).cast();
+
+    NonNull::new_unchecked(a_data.add(i))
+        .as_ref()
+        .store(x.cast(), Ordering::Release);
+
+    if !x.is_null() {
+        jl_gc_wb(jl_genericmemory_owner(mem), x.cast());
+    }
+
+    x.cast()
+}
+
+#[cfg(feature = "julia-1-11")]
+#[inline]
+pub unsafe fn jl_arrayref(a: *mut jl_array_t, i: usize) -> *mut jl_value_t {
+    let r = jl_memoryrefindex(NonNull::new_unchecked(a).as_ref().ref_, i);
+    jl_memoryrefget(r, 0)
+}
+
+#[cfg(feature = "julia-1-11")]
+#[inline]
+pub unsafe fn jl_arrayset(a: *mut jl_array_t, v: *mut jl_value_t, i: usize) {
+    let r = jl_memoryrefindex(NonNull::new_unchecked(a).as_ref().ref_, i);
+    jl_memoryrefset(r, v, 0)
+}
+
+// Call `Base.$name(a, n)`, exceptions are rethrown.
+#[cfg(feature = "julia-1-11")]
+unsafe fn jl_array_resize_with(name: &[u8], a: *mut jl_array_t, n: usize) {
+    let sym = jl_symbol(name.as_ptr().cast());
+    let func = jl_get_global(jl_base_module, sym);
+    let n = jl_box_uint64(n as u64);
+    let res = jl_call2(func, a.cast(), n);
+
+    if res.is_null() {
+        jl_throw(jl_exception_occurred())
+    }
+}
+
+#[cfg(feature = "julia-1-11")]
+#[inline]
+pub unsafe fn jl_array_grow_beg(a: *mut jl_array_t, inc: usize) {
+    jl_array_resize_with(b"_growbeg!\0", a, inc)
+}
+
+#[cfg(feature = "julia-1-11")]
+#[inline]
+pub unsafe fn jl_array_del_beg(a: *mut jl_array_t, dec: usize) {
+    jl_array_resize_with(b"_deletebeg!\0", a, dec)
+}
+
 #[cfg(test)]
 mod tests {
     use std::ffi::CString;
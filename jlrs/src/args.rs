@@ -4,9 +4,36 @@
 //! arguments. Functions that require this take the arguments as an implementation of [`Values`],
 //! which can add these extra arguments without requiring any heap allocations as long as the
 //! number of arguments is known at compile-time.
-
-use self::private::ValuesPriv;
-use crate::data::managed::value::Value;
+//!
+//! Functions can also be called with a tuple of arguments that implements [`IntoArgs`]. Each
+//! element of the tuple must implement [`IntoArg`], which is implemented for managed data like
+//! `Value` and `Array`, and for all data that implements [`IntoJuliaOwned`]. The arguments are
+//! converted and rooted in a local frame before the function is called.
+//!
+//! [`IntoJuliaOwned`]: crate::convert::into_julia_owned::IntoJuliaOwned
+
+use self::private::{IntoArgsPriv, ValuesPriv};
+use crate::{
+    convert::into_julia_owned::IntoJuliaOwned,
+    data::{
+        layout::tuple::{Tuple0, Tuple1, Tuple2, Tuple3, Tuple4, Tuple5, Tuple6, Tuple7, Tuple8},
+        layout::valid_layout::ValidField,
+        managed::{
+            array::{Array, TypedArray},
+            datatype::DataType,
+            dict::Dict,
+            function::Function,
+            module::Module,
+            string::JuliaString,
+            symbol::Symbol,
+            value::{typed::TypedValue, Value},
+            Managed,
+        },
+        types::construct_type::{ArrayTypeConstructor, ConstructType, Name, TypeVarConstructor},
+    },
+    error::JlrsResult,
+    memory::target::{output::LocalOutput, Target},
+};
 
 const MAX_SIZE: usize = 8;
 const UMAX: usize = usize::MAX;
@@ -46,6 +73,137 @@ pub fn with_small_vec_size<'scope, 'data, 'borrow, const SIZE: usize>(
     WithSmallVecSize(values)
 }
 
+/// A single argument of a Julia function.
+///
+/// This trait is implemented for all types that implement [`IntoJuliaOwned`], and for several
+/// managed types.
+///
+/// [`IntoJuliaOwned`]: crate::convert::into_julia_owned::IntoJuliaOwned
+pub trait IntoArg<'data> {
+    /// Type constructor for the type of the argument.
+    type ArgType: ConstructType;
+
+    /// Convert `self` to a `Value` rooted in `output`.
+    fn into_arg<'scope>(self, output: LocalOutput<'scope>) -> JlrsResult<Value<'scope, 'data>>;
+}
+
+impl<'data, T: IntoJuliaOwned> IntoArg<'data> for T {
+    type ArgType = T::JuliaType;
+
+    #[inline]
+    fn into_arg<'scope>(self, output: LocalOutput<'scope>) -> JlrsResult<Value<'scope, 'data>> {
+        self.into_julia_owned(output)
+    }
+}
+
+macro_rules! impl_into_arg_managed {
+    ($ty:ty, $arg_ty:ty, $($generics:tt)*) => {
+        impl<$($generics)*> IntoArg<'data> for $ty {
+            type ArgType = $arg_ty;
+
+            #[inline]
+            fn into_arg<'scope>(
+                self,
+                output: LocalOutput<'scope>,
+            ) -> JlrsResult<Value<'scope, 'data>> {
+                Ok(self.as_value().root(output))
+            }
+        }
+    };
+}
+
+impl_into_arg_managed!(Value<'_, 'data>, Value<'static, 'static>, 'data);
+impl_into_arg_managed!(Array<'_, 'data>, Array<'static, 'static>, 'data);
+impl_into_arg_managed!(
+    TypedArray<'_, 'data, T>,
+    ArrayTypeConstructor<T, TypeVarConstructor<Name<'N'>>>,
+    'data,
+    T: ValidField + ConstructType
+);
+impl_into_arg_managed!(TypedValue<'_, 'data, T>, T, 'data, T: ConstructType);
+impl_into_arg_managed!(Function<'_, 'data>, Function<'static, 'static>, 'data);
+impl_into_arg_managed!(
+    Dict<'_, 'data, K, V>,
+    Dict<'static, 'static, K, V>,
+    'data,
+    K: ConstructType,
+    V: ConstructType
+);
+impl_into_arg_managed!(DataType<'_>, DataType<'static>, 'data);
+impl_into_arg_managed!(JuliaString<'_>, JuliaString<'static>, 'data);
+impl_into_arg_managed!(Module<'_>, Module<'static>, 'data);
+impl_into_arg_managed!(Symbol<'_>, Symbol<'static>, 'data);
+
+/// A tuple of arguments of a Julia function.
+///
+/// This trait is implemented for tuples with up to eight elements that implement [`IntoArg`],
+/// these arguments can be used with [`Call::call_with`] and [`Call::call_typed`].
+///
+/// [`Call::call_with`]: crate::call::Call::call_with
+/// [`Call::call_typed`]: crate::call::Call::call_typed
+pub trait IntoArgs<'data>: IntoArgsPriv<'data> {}
+
+macro_rules! impl_into_args {
+    ($n:expr $(, $name:ident: $idx:tt)*) => {
+        impl<'data, $($name: IntoArg<'data>),*> IntoArgs<'data> for ($($name,)*) {}
+
+        impl<'data, $($name: IntoArg<'data>),*> IntoArgsPriv<'data> for ($($name,)*) {
+            #[allow(unused_variables, unused_mut)]
+            fn with_args<'target, Tgt, T, F>(self, target: &Tgt, func: F) -> JlrsResult<T>
+            where
+                Tgt: Target<'target>,
+                F: for<'scope> FnOnce(
+                    LocalOutput<'scope>,
+                    &[Value<'scope, 'data>],
+                ) -> JlrsResult<T>,
+            {
+                target.local_scope::<_, _, { $n + 1 }>(|mut frame| {
+                    let args: [Value<'_, 'data>; $n] =
+                        [$(self.$idx.into_arg(frame.local_output())?),*];
+                    func(frame.local_output(), &args)
+                })
+            }
+        }
+    };
+}
+
+/// A tuple of arguments of a Julia function with a known signature.
+///
+/// This trait is implemented for tuples with up to eight elements that implement [`IntoArg`].
+/// The signature is the `Tuple` type of the argument types.
+pub trait TypedArgs<'data>: IntoArgs<'data> {
+    /// Type constructor for the `Tuple` type of the argument types.
+    type Signature: ConstructType;
+}
+
+macro_rules! impl_typed_args {
+    ($tuple:ident $(, $name:ident)*) => {
+        impl<'data, $($name: IntoArg<'data>),*> TypedArgs<'data> for ($($name,)*) {
+            type Signature = $tuple<$($name::ArgType),*>;
+        }
+    };
+}
+
+impl_typed_args!(Tuple0);
+impl_typed_args!(Tuple1, A0);
+impl_typed_args!(Tuple2, A0, A1);
+impl_typed_args!(Tuple3, A0, A1, A2);
+impl_typed_args!(Tuple4, A0, A1, A2, A3);
+impl_typed_args!(Tuple5, A0, A1, A2, A3, A4);
+impl_typed_args!(Tuple6, A0, A1, A2, A3, A4, A5);
+impl_typed_args!(Tuple7, A0, A1, A2, A3, A4, A5, A6);
+impl_typed_args!(Tuple8, A0, A1, A2, A3, A4, A5, A6, A7);
+
+impl_into_args!(0);
+impl_into_args!(1, A0: 0);
+impl_into_args!(2, A0: 0, A1: 1);
+impl_into_args!(3, A0: 0, A1: 1, A2: 2);
+impl_into_args!(4, A0: 0, A1: 1, A2: 2, A3: 3);
+impl_into_args!(5, A0: 0, A1: 1, A2: 2, A3: 3, A4: 4);
+impl_into_args!(6, A0: 0, A1: 1, A2: 2, A3: 3, A4: 4, A5: 5);
+impl_into_args!(7, A0: 0, A1: 1, A2: 2, A3: 3, A4: 4, A5: 5, A6: 6);
+impl_into_args!(8, A0: 0, A1: 1, A2: 2, A3: 3, A4: 4, A5: 5, A6: 6, A7: 7);
+
 pub(crate) mod private {
     use std::slice;
 
@@ -55,9 +213,19 @@ pub(crate) mod private {
     use super::{WithSmallVecSize, MAX_SIZE, UMAX};
     use crate::{
         data::managed::{private::ManagedPriv, value::Value},
+        error::JlrsResult,
+        memory::target::{output::LocalOutput, Target},
         private::Private,
     };
 
+    pub trait IntoArgsPriv<'data> {
+        // Convert the arguments in a new local scope and call `func` with them.
+        fn with_args<'target, Tgt, T, F>(self, target: &Tgt, func: F) -> JlrsResult<T>
+        where
+            Tgt: Target<'target>,
+            F: for<'scope> FnOnce(LocalOutput<'scope>, &[Value<'scope, 'data>]) -> JlrsResult<T>;
+    }
+
     #[repr(C)]
     #[derive(Copy, Clone)]
     pub struct ExtendedArray<T: Copy, const N: usize, const M: usize> {
@@ -0,0 +1,105 @@
+//! Cancel async tasks.
+//!
+//! An async task that has been sent to the runtime with [`AsyncJulia::cancellable_task`] can be
+//! cancelled with the [`CancelHandle`] that is returned alongside the dispatcher. If the task is
+//! cancelled before it has started, it's never run. Otherwise, the task is dropped the next time
+//! it's waiting at an await point. If it's waiting for a Julia task that has been scheduled with
+//! a method of [`CallAsync`] and that Julia task is waiting itself, e.g. in `sleep` or on a
+//! `Channel`, an `InterruptException` is thrown in that Julia task. A Julia task that is running
+//! or has been scheduled to run can't be interrupted, it keeps running in the background and its
+//! result is discarded.
+//!
+//! In both cases the result sender of the task receives `RuntimeError::Cancelled`.
+//!
+//! [`AsyncJulia::cancellable_task`]: crate::runtime::async_rt::AsyncJulia::cancellable_task
+//! [`CallAsync`]: crate::call::CallAsync
+
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use futures::{
+    task::{AtomicWaker, Context, Poll},
+    Future,
+};
+
+struct CancelState {
+    cancelled: AtomicBool,
+    waker: AtomicWaker,
+}
+
+/// A handle that can be used to cancel an async task.
+///
+/// The handle can be cloned and shared across threads. Cancelling a task that has already
+/// completed has no effect.
+#[derive(Clone)]
+pub struct CancelHandle {
+    state: Arc<CancelState>,
+}
+
+impl CancelHandle {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        CancelHandle {
+            state: Arc::new(CancelState {
+                cancelled: AtomicBool::new(false),
+                waker: AtomicWaker::new(),
+            }),
+        }
+    }
+
+    /// Cancel the task.
+    #[inline]
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::Release);
+        self.state.waker.wake();
+    }
+
+    /// Returns `true` if the task has been cancelled.
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Acquire)
+    }
+
+    // Returns a future that resolves when the task is cancelled.
+    #[inline]
+    pub(crate) fn cancelled(&self) -> Cancelled {
+        Cancelled {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl std::fmt::Debug for CancelHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancelHandle")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+pub(crate) struct Cancelled {
+    state: Arc<CancelState>,
+}
+
+impl Future for Cancelled {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.state.cancelled.load(Ordering::Acquire) {
+            return Poll::Ready(());
+        }
+
+        // The waker must be registered before checking again to avoid missing a cancellation.
+        self.state.waker.register(cx.waker());
+        if self.state.cancelled.load(Ordering::Acquire) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
@@ -1,23 +1,25 @@
 use std::{ffi::c_void, marker::PhantomData, num::NonZeroUsize, path::PathBuf, sync::Arc};
 
 use async_trait::async_trait;
+use futures::future::{select, Either};
 
 use super::{channel::Channel, task::PersistentTask};
 use crate::{
     async_util::{
+        cancel::CancelHandle,
         channel::{ChannelReceiver, OneshotSender},
-        future::JuliaFuture,
+        future::{drop_interrupting, JuliaFuture},
         task::AsyncTask,
     },
     call::Call,
     data::managed::{module::Module, string::JuliaString, value::Value, Managed},
-    error::{JlrsError, JlrsResult},
+    error::{JlrsError, JlrsResult, RuntimeError},
     memory::{
         context::stack::Stack,
         stack_frame::StackFrame,
         target::frame::{AsyncGcFrame, GcFrame},
     },
-    runtime::async_rt::{PersistentHandle, PersistentMessage},
+    runtime::async_rt::{PersistentHandle, PersistentMessage, ShutdownReport},
 };
 
 pub(crate) type InnerPersistentMessage<P> = Box<
@@ -30,6 +32,7 @@ pub(crate) type InnerPersistentMessage<P> = Box<
 // What follows is a significant amount of indirection to allow different tasks to have a
 // different Output types and be unaware of the used channels.
 pub(crate) enum Task {}
+pub(crate) enum CancellableTask {}
 pub(crate) enum RegisterTask {}
 pub(crate) enum Persistent {}
 pub(crate) enum RegisterPersistent {}
@@ -217,6 +220,27 @@ where
     }
 }
 
+impl<O, A> PendingTask<(O, CancelHandle), A, CancellableTask>
+where
+    O: OneshotSender<JlrsResult<A::Output>>,
+    A: AsyncTask,
+{
+    #[inline]
+    pub(crate) fn new(task: A, sender: O, handle: CancelHandle) -> Self {
+        PendingTask {
+            task: Some(task),
+            sender: (sender, handle),
+            _kind: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn split(self) -> (A, O, CancelHandle) {
+        let (sender, handle) = self.sender;
+        (self.task.unwrap(), sender, handle)
+    }
+}
+
 impl<O, A> PendingTask<O, A, RegisterTask>
 where
     O: OneshotSender<JlrsResult<()>>,
@@ -286,6 +310,42 @@ where
     }
 }
 
+#[async_trait(?Send)]
+impl<O, A> PendingTaskEnvelope for PendingTask<(O, CancelHandle), A, CancellableTask>
+where
+    O: OneshotSender<JlrsResult<A::Output>>,
+    A: AsyncTask,
+{
+    async fn call(mut self: Box<Self>, stack: &'static Stack) {
+        let (mut task, result_sender, handle) = self.split();
+
+        if handle.is_cancelled() {
+            result_sender.send(Err(RuntimeError::Cancelled.into()));
+            return;
+        }
+
+        // Safety: the stack slots can be reallocated because it doesn't contain any frames
+        // yet. The frame is dropped at the end of the scope, the nested hierarchy of scopes is
+        // maintained. If the task is cancelled, it's dropped before the frame is.
+        let res = unsafe {
+            let (owner, frame) = AsyncGcFrame::base(&stack);
+
+            let res = match select(task.call_run(frame), handle.cancelled()).await {
+                Either::Left((res, _)) => res,
+                Either::Right((_, run)) => {
+                    drop_interrupting(run);
+                    Err(RuntimeError::Cancelled.into())
+                }
+            };
+
+            std::mem::drop(owner);
+            res
+        };
+
+        result_sender.send(res);
+    }
+}
+
 #[async_trait(?Send)]
 impl<O, A> PendingTaskEnvelope for PendingTask<O, A, RegisterTask>
 where
@@ -572,6 +632,41 @@ where
     }
 }
 
+pub(crate) struct ShutdownTask<O> {
+    exit_code: i32,
+    sender: O,
+}
+
+impl<O> ShutdownTask<O>
+where
+    O: OneshotSender<JlrsResult<ShutdownReport>>,
+{
+    #[inline]
+    pub(crate) fn new(exit_code: i32, sender: O) -> Self {
+        Self { exit_code, sender }
+    }
+}
+
+pub(crate) trait ShutdownTaskEnvelope: Send {
+    fn exit_code(&self) -> i32;
+
+    fn respond(self: Box<Self>, report: ShutdownReport);
+}
+
+impl<O> ShutdownTaskEnvelope for ShutdownTask<O>
+where
+    O: OneshotSender<JlrsResult<ShutdownReport>>,
+{
+    #[inline]
+    fn exit_code(&self) -> i32 {
+        self.exit_code
+    }
+
+    fn respond(self: Box<Self>, report: ShutdownReport) {
+        OneshotSender::send(self.sender, Ok(report));
+    }
+}
+
 pub(crate) trait SetErrorColorTaskEnvelope: Send {
     fn call(self: Box<Self>, stack: &'static Stack);
 }
@@ -1,4 +1,6 @@
-use std::{ffi::c_void, fmt::Display, marker::PhantomData, pin::Pin, ptr::NonNull, sync::Arc};
+use std::{
+    cell::Cell, ffi::c_void, fmt::Display, marker::PhantomData, pin::Pin, ptr::NonNull, sync::Arc,
+};
 
 use futures::{
     task::{Context, Poll, Waker},
@@ -9,21 +11,109 @@ use jlrs_macros::julia_version;
 
 use crate::{
     args::Values,
-    call::{Call, WithKeywords},
-    data::managed::{
-        erase_scope_lifetime,
-        module::{JlrsCore, Module},
-        private::ManagedPriv,
-        task::Task,
-        value::Value,
-        Managed,
+    call::{Call, ProvideKeywords, WithKeywords},
+    data::{
+        layout::nothing::Nothing,
+        managed::{
+            erase_scope_lifetime,
+            function::Function,
+            module::{JlrsCore, Module},
+            private::ManagedPriv,
+            symbol::Symbol,
+            task::Task,
+            value::Value,
+            Managed,
+        },
     },
     error::{JuliaResult, CANNOT_DISPLAY_VALUE},
     gc_safe::GcSafeMutex,
-    memory::target::{frame::AsyncGcFrame, private::TargetPriv, unrooted::Unrooted},
+    inline_static_ref,
+    memory::target::{frame::AsyncGcFrame, private::TargetPriv, unrooted::Unrooted, Target},
     private::Private,
 };
 
+#[cfg(feature = "async-rt")]
+use std::time::Duration;
+
+#[cfg(feature = "async-rt")]
+use futures::future::{select, Either};
+
+#[cfg(feature = "async-rt")]
+use crate::{
+    error::{JlrsError, JlrsResult, RuntimeError},
+    runtime::async_rt::AsyncRuntime,
+};
+
+thread_local! {
+    // If this flag is set, a `JuliaFuture` that is dropped before the Julia task has completed
+    // interrupts that task.
+    static INTERRUPT_ON_DROP: Cell<bool> = const { Cell::new(false) };
+}
+
+#[cfg(feature = "async-rt")]
+type Timer = fn(Duration) -> Pin<Box<dyn Future<Output = ()>>>;
+
+#[cfg(feature = "async-rt")]
+thread_local! {
+    // The timer of the async runtime that runs on this thread, set when the runtime starts.
+    static RUNTIME_TIMER: Cell<Option<Timer>> = const { Cell::new(None) };
+}
+
+#[cfg(feature = "async-rt")]
+fn runtime_timer<R: AsyncRuntime>(duration: Duration) -> Pin<Box<dyn Future<Output = ()>>> {
+    Box::pin(async move {
+        R::timeout(duration, futures::future::pending::<()>()).await;
+    })
+}
+
+// Use the timer of `R` for timeouts of tasks that run on the current thread.
+#[cfg(feature = "async-rt")]
+pub(crate) fn set_runtime_timer<R: AsyncRuntime>() {
+    RUNTIME_TIMER.with(|timer| timer.set(Some(runtime_timer::<R>)));
+}
+
+// Drop `value`, every `JuliaFuture` it owns interrupts its Julia task if it hasn't completed.
+#[cfg(feature = "async-rt")]
+pub(crate) fn drop_interrupting<T>(value: T) {
+    INTERRUPT_ON_DROP.with(|flag| flag.set(true));
+    std::mem::drop(value);
+    INTERRUPT_ON_DROP.with(|flag| flag.set(false));
+}
+
+// Throw an `InterruptException` in `task` if it's waiting, e.g. in `sleep` or on a `Channel`.
+// A waiting task is stored in the wait queue of the object it waits on, `schedule` removes it
+// from that queue before it's scheduled again. A task that is running or has been scheduled to
+// run isn't in a wait queue, scheduling it would enqueue it a second time so it's left alone and
+// keeps running in the background. Errors are ignored.
+unsafe fn interrupt_task(task: Task) {
+    let queue = task.unwrap_non_null(Private).as_ref().queue;
+    let waiting = match NonNull::new(queue) {
+        Some(queue) => !Value::wrap_non_null(queue, Private).is::<Nothing>(),
+        None => false,
+    };
+
+    if !waiting {
+        return;
+    }
+
+    let unrooted = Unrooted::new();
+    let _ = unrooted.local_scope::<_, _, 2>(|mut frame| {
+        let error = Symbol::new(&frame, "error");
+        let t = Value::true_v(&frame);
+        let kws = Value::new_named_tuple(&mut frame, &[(error, t)]);
+        let exc = inline_static_ref!(INTERRUPT, Value, "Core.InterruptException", &frame)
+            .call0(&mut frame);
+
+        if let Ok(exc) = exc {
+            let _ = inline_static_ref!(SCHEDULE, Function, "Base.schedule", &frame)
+                .provide_keywords(kws)?
+                .call2(&frame, task.as_value(), exc);
+        }
+
+        Ok(())
+    });
+}
+
 pub(crate) struct TaskState<'frame, 'data> {
     completed: bool,
     waker: Option<Waker>,
@@ -280,9 +370,13 @@ impl<'frame, 'data> JuliaFuture<'frame, 'data> {
                 AsyncMethod::ScheduleAsyncLocal => JlrsCore::schedule_async_local(&frame),
             };
 
-            #[cfg(not(any(feature = "julia-1-10", feature = "julia-1-9")))]
+            #[cfg(not(any(
+                feature = "julia-1-11",
+                feature = "julia-1-10",
+                feature = "julia-1-9"
+            )))]
             let kw_call = jl_sys::jl_get_kwsorter(f.datatype().unwrap(Private).cast());
-            #[cfg(any(feature = "julia-1-10", feature = "julia-1-9"))]
+            #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
             let kw_call = jl_sys::jl_kwcall_func;
 
             // WithKeywords::call has to extend the provided arguments, it has been inlined so
@@ -368,6 +462,57 @@ impl<'frame, 'data> Future for JuliaFuture<'frame, 'data> {
     }
 }
 
+impl Drop for JuliaFuture<'_, '_> {
+    fn drop(&mut self) {
+        if !INTERRUPT_ON_DROP.with(|flag| flag.get()) {
+            return;
+        }
+
+        let task = {
+            let shared_state = self.shared_state.lock();
+            if shared_state.completed {
+                return;
+            }
+            shared_state.task
+        };
+
+        if let Some(task) = task {
+            // Safety: the task is rooted, it's only interrupted if it's waiting.
+            unsafe { interrupt_task(task) }
+        }
+    }
+}
+
+// Wait for `future` until it resolves or `timeout` has elapsed. If it times out, the future is
+// dropped and the Julia task is interrupted if `interrupt` is `true` and it's waiting.
+#[cfg(feature = "async-rt")]
+pub(crate) async fn with_timeout<F>(
+    future: F,
+    timeout: Duration,
+    interrupt: bool,
+) -> JlrsResult<F::Output>
+where
+    F: Future + Unpin,
+{
+    let timer = match RUNTIME_TIMER.with(|timer| timer.get()) {
+        Some(timer) => timer(timeout),
+        None => Err(RuntimeError::NoAsyncRuntime)?,
+    };
+
+    match select(future, timer).await {
+        Either::Left((res, _)) => Ok(res),
+        Either::Right((_, future)) => {
+            if interrupt {
+                drop_interrupting(future);
+            } else {
+                std::mem::drop(future);
+            }
+
+            Err(JlrsError::Timeout(timeout))?
+        }
+    }
+}
+
 // This function is called using `ccall` to indicate a task has completed.
 #[cfg(feature = "async-rt")]
 pub(crate) unsafe extern "C" fn wake_task(state: *const GcSafeMutex<TaskState>) {
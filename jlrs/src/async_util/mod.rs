@@ -1,6 +1,8 @@
 //! Async tasks and channels that can be used with an async runtime.
 
 pub mod affinity;
+#[cfg(feature = "async-rt")]
+pub mod cancel;
 pub mod channel;
 #[cfg(feature = "async-rt")]
 pub(crate) mod envelope;
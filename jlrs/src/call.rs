@@ -4,22 +4,28 @@
 //! can be used to call Julia functions, including inner and outer constructors; schedule a
 //! function call as a new Julia task; and provide keyword arguments respectively.
 
-use std::{mem::ManuallyDrop, ptr::NonNull};
+use std::{hint::unreachable_unchecked, mem::ManuallyDrop, ptr::NonNull};
 
 #[julia_version(until = "1.8")]
 use jl_sys::jl_get_kwsorter;
 #[julia_version(since = "1.9")]
 use jl_sys::jl_kwcall_func;
-use jl_sys::{jl_apply_generic, jl_call, jl_exception_occurred, jl_get_world_counter};
+use jl_sys::{jl_apply_generic, jl_call, jl_exception_occurred, jl_get_world_counter, jl_value_t};
 use jlrs_macros::julia_version;
 
 use crate::{
-    args::Values,
-    data::managed::{
-        private::ManagedPriv,
-        value::{Value, ValueResult},
+    args::{IntoArgs, Values},
+    catch::catch_exceptions_or_rethrow,
+    convert::unbox::Unbox,
+    data::{
+        managed::{
+            private::ManagedPriv,
+            value::{Value, ValueResult},
+        },
+        types::typecheck::Typecheck,
     },
-    error::{AccessError, JlrsResult},
+    error::{AccessError, CaughtException, JlrsResult, JuliaException, TracedResult},
+    inline_static_ref,
     memory::{context::ledger::Ledger, target::Target},
     prelude::ValueData,
     private::Private,
@@ -189,6 +195,212 @@ pub trait Call<'data>: private::CallPriv {
 
         Ok(res)
     }
+
+    /// Call a function with an arbitrary number arguments and capture the backtrace if an
+    /// exception is thrown.
+    ///
+    /// If an exception is thrown it's caught, its backtrace is captured and it's rooted in
+    /// `target`. The exception is returned as a [`CaughtException`], which can be converted to a
+    /// `JlrsError::JuliaException` that keeps the backtrace with the `?` operator. Exceptions
+    /// returned by the other methods of this trait have no backtrace.
+    ///
+    /// Safety: this method lets you call arbitrary Julia functions which can't be checked for
+    /// correctness. More information can be found in the [`safety`] module. This method doesn't
+    /// check if any of the arguments is currently borrowed from Rust.
+    ///
+    /// [`safety`]: crate::safety
+    /// [`CaughtException`]: crate::error::CaughtException
+    unsafe fn call_traced<'target, 'value, V, T, const N: usize>(
+        self,
+        target: T,
+        args: V,
+    ) -> TracedResult<'target, 'data, T>
+    where
+        V: Values<'value, 'data, N>,
+        T: Target<'target>,
+    {
+        let unrooted = target.unrooted();
+        let args = args.as_slice(Private);
+
+        // The function is called at most once.
+        let mut func = Some(self);
+        let res = catch_exceptions_or_rethrow(
+            || match func.take() {
+                Some(func) => func.call_unchecked(unrooted, args).ptr(),
+                None => unreachable_unchecked(),
+            },
+            |e| Ok((e.unwrap_non_null(Private), JuliaException::capture(e))),
+        );
+
+        match res {
+            Ok(res) => Ok(target.data_from_ptr(res, Private)),
+            Err((exc, info)) => Err(CaughtException::new(
+                target.data_from_ptr(exc, Private),
+                info,
+            )),
+        }
+    }
+
+    /// Call a function with an arbitrary number arguments in the latest world age.
+    ///
+    /// The function is called with `Base.invokelatest`, so methods that have been defined after
+    /// the function was obtained, e.g. by evaluating code with [`Value::eval_string`], are
+    /// visible to the called function even if the call is made from a context whose world age
+    /// is fixed. The current world age can be queried with [`Info::world_counter`].
+    ///
+    /// Safety: this method lets you call arbitrary Julia functions which can't be checked for
+    /// correctness. More information can be found in the [`safety`] module. This method doesn't
+    /// check if any of the arguments is currently borrowed from Rust.
+    ///
+    /// [`safety`]: crate::safety
+    /// [`Info::world_counter`]: crate::info::Info::world_counter
+    unsafe fn call_latest<'target, 'value, V, T, const N: usize>(
+        self,
+        target: T,
+        args: V,
+    ) -> ValueResult<'target, 'data, T>
+    where
+        V: Values<'value, 'data, N>,
+        T: Target<'target>;
+
+    /// Call a function with a tuple of arguments.
+    ///
+    /// Each argument is either managed data like a `Value`, or data that implements
+    /// [`IntoJuliaOwned`] like an `f64` or a `Vec<i32>`. The arguments are converted and rooted
+    /// in a new local scope before the function is called. If an argument can't be converted an
+    /// error is returned.
+    ///
+    /// Safety: this method lets you call arbitrary Julia functions which can't be checked for
+    /// correctness. More information can be found in the [`safety`] module. This method doesn't
+    /// check if any of the arguments is currently borrowed from Rust.
+    ///
+    /// [`safety`]: crate::safety
+    /// [`IntoJuliaOwned`]: crate::convert::into_julia_owned::IntoJuliaOwned
+    unsafe fn call_with<'target, A, Tgt>(
+        self,
+        target: Tgt,
+        args: A,
+    ) -> JlrsResult<ValueResult<'target, 'data, Tgt>>
+    where
+        A: IntoArgs<'data>,
+        Tgt: Target<'target>,
+    {
+        let unrooted = target.unrooted();
+        args.with_args(&unrooted, |_, args| Ok(self.call(target, args)))
+    }
+
+    /// Call a function with a tuple of arguments and unbox the result as `T`.
+    ///
+    /// The arguments are converted in the same way as they are by [`Call::call_with`]. The
+    /// function is called with [`Call::call_traced`], if an exception is thrown it's converted to
+    /// an error that contains its backtrace. An error is also returned if the result can't be
+    /// unboxed as `T`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// # use jlrs::prelude::*;
+    /// # use jlrs::util::test::JULIA;
+    /// # fn main() {
+    /// # JULIA.with(|j| {
+    /// # let mut julia = j.borrow_mut();
+    /// # let mut frame = StackFrame::new();
+    /// # let mut julia = julia.instance(&mut frame);
+    /// julia.scope(|mut frame| unsafe {
+    ///     let func = Module::base(&frame).function(&frame, "+")?.as_managed();
+    ///
+    ///     // Adding numbers is safe.
+    ///     let res = func.call_typed::<f64, _>(&mut frame, (1.0f64, 2i32))?;
+    ///     assert_eq!(res, 3.0);
+    ///
+    ///     Ok(())
+    /// })
+    /// # .unwrap();
+    /// # });
+    /// # }
+    /// ```
+    ///
+    /// Safety: this method lets you call arbitrary Julia functions which can't be checked for
+    /// correctness. More information can be found in the [`safety`] module. This method doesn't
+    /// check if any of the arguments is currently borrowed from Rust.
+    ///
+    /// [`safety`]: crate::safety
+    unsafe fn call_typed<'target, T, A>(
+        self,
+        target: impl Target<'target>,
+        args: A,
+    ) -> JlrsResult<T::Output>
+    where
+        T: Unbox + Typecheck,
+        A: IntoArgs<'data>,
+    {
+        args.with_args(&target, |output, args| {
+            self.call_traced(output, args)?.unbox::<T>()
+        })
+    }
+}
+
+/// Provide keyword arguments to a Julia function.
+///
+/// The first argument is the target the keyword arguments are rooted in, the second the function
+/// they're provided to. The keyword arguments are provided as `name = value` pairs, every value
+/// must implement [`IntoArg`]. The values are converted and rooted in a local frame, only the
+/// `NamedTuple` that contains the keyword arguments is rooted in the target. This macro returns a
+/// `JlrsResult<WithKeywords>`.
+///
+/// Example:
+///
+/// ```
+/// # use jlrs::prelude::*;
+/// # use jlrs::util::test::JULIA;
+/// # fn main() {
+/// # JULIA.with(|j| {
+/// # let mut julia = j.borrow_mut();
+/// # let mut frame = StackFrame::new();
+/// # let mut julia = julia.instance(&mut frame);
+/// julia.scope(|mut frame| unsafe {
+///     // The code we evaluate is a simple function definition, which is safe.
+///     let func = unsafe {
+///         Value::eval_string(&mut frame, "func(; a=3, b=4, c=5) = a + b + c")
+///         .into_jlrs_result()?
+///     };
+///
+///     // Call the previously defined function. This function simply sums its three
+///     // keyword arguments and has no side effects, so it's safe to call.
+///     let res = unsafe {
+///         kwargs!(&mut frame, func, a = 1isize, b = 2isize)?
+///             .call0(&mut frame)
+///             .into_jlrs_result()?
+///             .unbox::<isize>()?
+///     };
+///
+///     assert_eq!(res, 8);
+///
+///     Ok(())
+/// })
+/// # .unwrap();
+/// # });
+/// # }
+/// ```
+///
+/// [`IntoArg`]: crate::args::IntoArg
+#[macro_export]
+macro_rules! kwargs {
+    ($target:expr, $func:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        const N: usize = [$(stringify!($name)),+].len();
+        $crate::memory::target::Target::with_local_scope::<_, _, N>(
+            $target,
+            |target, mut frame| {
+                let pairs = [$((
+                    $crate::data::managed::symbol::Symbol::new(&frame, stringify!($name)),
+                    $crate::args::IntoArg::into_arg($value, frame.local_output())?,
+                )),+];
+
+                Ok($crate::data::managed::value::Value::new_named_tuple(target, &pairs))
+            },
+        )
+        .and_then(|keywords| $crate::call::ProvideKeywords::provide_keywords($func, keywords))
+    }};
 }
 
 /// Provide keyword arguments to a Julia function.
@@ -196,6 +408,9 @@ pub trait Call<'data>: private::CallPriv {
 pub trait ProvideKeywords<'value, 'data>: Call<'data> {
     /// Provide keyword arguments to the function. The keyword arguments must be a `NamedTuple`.
     ///
+    /// The [`kwargs`] macro can be used to convert Rust data to keyword arguments and provide
+    /// them to a function.
+    ///
     /// Example:
     ///
     /// ```
@@ -233,6 +448,9 @@ pub trait ProvideKeywords<'value, 'data>: Call<'data> {
     /// # .unwrap();
     /// # });
     /// # }
+    /// ```
+    ///
+    /// [`kwargs`]: crate::kwargs!
     fn provide_keywords(
         self,
         keywords: Value<'value, 'data>,
@@ -297,9 +515,9 @@ impl<'data> Call<'data> for WithKeywords<'_, 'data> {
         V: Values<'value, 'data, N>,
         T: Target<'target>,
     {
-        #[cfg(not(any(feature = "julia-1-10", feature = "julia-1-9")))]
+        #[cfg(not(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9")))]
         let func = jl_get_kwsorter(self.func.datatype().unwrap(Private).cast());
-        #[cfg(any(feature = "julia-1-10", feature = "julia-1-9"))]
+        #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
         let func = jl_kwcall_func;
 
         let values = args.into_extended_pointers_with_start(
@@ -333,9 +551,9 @@ impl<'data> Call<'data> for WithKeywords<'_, 'data> {
         V: Values<'value, 'data, N>,
         T: Target<'target>,
     {
-        #[cfg(not(any(feature = "julia-1-10", feature = "julia-1-9")))]
+        #[cfg(not(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9")))]
         let func = jl_get_kwsorter(self.func.datatype().unwrap(Private).cast());
-        #[cfg(any(feature = "julia-1-10", feature = "julia-1-9"))]
+        #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
         let func = jl_kwcall_func;
 
         let values = args.into_extended_pointers_with_start(
@@ -369,6 +587,32 @@ impl<'data> Call<'data> for WithKeywords<'_, 'data> {
         ManuallyDrop::drop(&mut values);
         target.data_from_ptr(NonNull::new_unchecked(v), Private)
     }
+
+    #[inline]
+    unsafe fn call_latest<'target, 'value, V, T, const N: usize>(
+        self,
+        target: T,
+        args: V,
+    ) -> ValueResult<'target, 'data, T>
+    where
+        V: Values<'value, 'data, N>,
+        T: Target<'target>,
+    {
+        #[cfg(not(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9")))]
+        let func = jl_get_kwsorter(self.func.datatype().unwrap(Private).cast());
+        #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
+        let func = jl_kwcall_func;
+
+        invoke_latest(
+            target,
+            [
+                func,
+                self.keywords.unwrap(Private),
+                self.func.unwrap(Private),
+            ],
+            args,
+        )
+    }
 }
 
 cfg_if::cfg_if! {
@@ -385,6 +629,10 @@ cfg_if::cfg_if! {
                 future::JuliaFuture,
             }
         };
+        #[cfg(feature = "async-rt")]
+        use std::time::Duration;
+        #[cfg(feature = "async-rt")]
+        use crate::async_util::future::with_timeout;
 
         /// This trait provides async methods to create and schedule `Task`s that resolve when the
         /// `Task` has completed. Sync methods are also provided which only schedule the `Task`,
@@ -451,6 +699,29 @@ cfg_if::cfg_if! {
                 Ok(res)
             }
 
+            /// Creates and schedules a new task with `Base.Threads.@spawn` that calls the
+            /// function with `Base.invokelatest`, and returns a future that resolves when this
+            /// task is finished.
+            ///
+            /// This guarantees the function is called in the latest world age when the task is
+            /// run, so methods that have been defined after the function was obtained are
+            /// visible to it.
+            ///
+            /// Since Julia 1.9 this task is spawned on the `:default` thread pool.
+            ///
+            /// Safety: this method lets you call arbitrary Julia functions which can't be checked for
+            /// correctness. More information can be found in the [`safety`] module. This method doesn't
+            /// check if any of the arguments is currently borrowed from Rust.
+            ///
+            /// [`safety`]: crate::safety
+            async unsafe fn call_async_latest<'target, 'value, V, const N: usize>(
+                self,
+                frame: &mut AsyncGcFrame<'target>,
+                args: V,
+            ) -> JuliaResult<'target, 'data>
+            where
+                V: Values<'value, 'data, N>;
+
             /// Does the same thing as [`CallAsync::call_async`], but the task is returned rather than an
             /// awaitable `Future`. This method should only be called in [`PersistentTask::init`],
             /// otherwise it's not guaranteed this task can make progress.
@@ -886,6 +1157,126 @@ cfg_if::cfg_if! {
 
                 Ok(res)
             }
+
+            #[cfg(feature = "async-rt")]
+            /// Does the same thing as [`CallAsync::call_async`], but stops waiting for the task after a timeout.
+            ///
+            /// If the task hasn't completed after `timeout` has elapsed, this method stops waiting
+            /// and returns `JlrsError::Timeout`. If `interrupt` is `true` and the task is waiting,
+            /// e.g. in `sleep` or on a `Channel`, an `InterruptException` is thrown in the task.
+            /// Otherwise the task continues in the background and its result is discarded. The
+            /// timeout is tracked by the runtime the task runs on, if it isn't called from a task
+            /// that runs on an async runtime `RuntimeError::NoAsyncRuntime` is returned.
+            ///
+            /// Safety: this method lets you call arbitrary Julia functions which can't be checked for
+            /// correctness. More information can be found in the [`safety`] module. This method doesn't
+            /// check if any of the arguments is currently borrowed from Rust.
+            ///
+            /// [`safety`]: crate::safety
+            async unsafe fn call_async_timeout<'target, 'value, V, const N: usize>(
+                self,
+                frame: &mut AsyncGcFrame<'target>,
+                args: V,
+                timeout: Duration,
+                interrupt: bool,
+            ) -> JlrsResult<JuliaResult<'target, 'data>>
+            where
+                V: Values<'value, 'data, N>,
+            {
+                let call = self.call_async(frame, args);
+                with_timeout(call, timeout, interrupt).await
+            }
+
+            #[julia_version(since = "1.9")]
+            #[cfg(feature = "async-rt")]
+            /// Does the same thing as [`CallAsync::call_async_interactive`], but stops waiting for the task
+            /// after a timeout.
+            ///
+            /// If the task hasn't completed after `timeout` has elapsed, this method stops waiting
+            /// and returns `JlrsError::Timeout`. If `interrupt` is `true` and the task is waiting,
+            /// e.g. in `sleep` or on a `Channel`, an `InterruptException` is thrown in the task.
+            /// Otherwise the task continues in the background and its result is discarded. The
+            /// timeout is tracked by the runtime the task runs on, if it isn't called from a task
+            /// that runs on an async runtime `RuntimeError::NoAsyncRuntime` is returned.
+            ///
+            /// Safety: this method lets you call arbitrary Julia functions which can't be checked for
+            /// correctness. More information can be found in the [`safety`] module. This method doesn't
+            /// check if any of the arguments is currently borrowed from Rust.
+            ///
+            /// [`safety`]: crate::safety
+            async unsafe fn call_async_interactive_timeout<'target, 'value, V, const N: usize>(
+                self,
+                frame: &mut AsyncGcFrame<'target>,
+                args: V,
+                timeout: Duration,
+                interrupt: bool,
+            ) -> JlrsResult<JuliaResult<'target, 'data>>
+            where
+                V: Values<'value, 'data, N>,
+            {
+                let call = self.call_async_interactive(frame, args);
+                with_timeout(call, timeout, interrupt).await
+            }
+
+            #[cfg(feature = "async-rt")]
+            /// Does the same thing as [`CallAsync::call_async_local`], but stops waiting for the task after
+            /// a timeout.
+            ///
+            /// If the task hasn't completed after `timeout` has elapsed, this method stops waiting
+            /// and returns `JlrsError::Timeout`. If `interrupt` is `true` and the task is waiting,
+            /// e.g. in `sleep` or on a `Channel`, an `InterruptException` is thrown in the task.
+            /// Otherwise the task continues in the background and its result is discarded. The
+            /// timeout is tracked by the runtime the task runs on, if it isn't called from a task
+            /// that runs on an async runtime `RuntimeError::NoAsyncRuntime` is returned.
+            ///
+            /// Safety: this method lets you call arbitrary Julia functions which can't be checked for
+            /// correctness. More information can be found in the [`safety`] module. This method doesn't
+            /// check if any of the arguments is currently borrowed from Rust.
+            ///
+            /// [`safety`]: crate::safety
+            async unsafe fn call_async_local_timeout<'target, 'value, V, const N: usize>(
+                self,
+                frame: &mut AsyncGcFrame<'target>,
+                args: V,
+                timeout: Duration,
+                interrupt: bool,
+            ) -> JlrsResult<JuliaResult<'target, 'data>>
+            where
+                V: Values<'value, 'data, N>,
+            {
+                let call = self.call_async_local(frame, args);
+                with_timeout(call, timeout, interrupt).await
+            }
+
+            #[cfg(feature = "async-rt")]
+            /// Does the same thing as [`CallAsync::call_async_main`], but stops waiting for the task after
+            /// a timeout.
+            ///
+            /// If the task hasn't completed after `timeout` has elapsed, this method stops waiting
+            /// and returns `JlrsError::Timeout`. If `interrupt` is `true` and the task is waiting,
+            /// e.g. in `sleep` or on a `Channel`, an `InterruptException` is thrown in the task.
+            /// Otherwise the task continues in the background and its result is discarded. The
+            /// timeout is tracked by the runtime the task runs on, if it isn't called from a task
+            /// that runs on an async runtime `RuntimeError::NoAsyncRuntime` is returned.
+            ///
+            /// Safety: this method lets you call arbitrary Julia functions which can't be checked for
+            /// correctness. More information can be found in the [`safety`] module. This method doesn't
+            /// check if any of the arguments is currently borrowed from Rust.
+            ///
+            /// [`safety`]: crate::safety
+            async unsafe fn call_async_main_timeout<'target, 'value, V, const N: usize>(
+                self,
+                frame: &mut AsyncGcFrame<'target>,
+                args: V,
+                timeout: Duration,
+                interrupt: bool,
+            ) -> JlrsResult<JuliaResult<'target, 'data>>
+            where
+                V: Values<'value, 'data, N>,
+            {
+                let call = self.call_async_main(frame, args);
+                with_timeout(call, timeout, interrupt).await
+            }
         }
 
         #[async_trait(?Send)]
@@ -902,6 +1293,20 @@ cfg_if::cfg_if! {
                 JuliaFuture::new(frame, erase_scope_lifetime(self), args).await
             }
 
+            #[inline]
+            async unsafe fn call_async_latest<'target, 'value, V, const N: usize>(
+                self,
+                frame: &mut AsyncGcFrame<'target>,
+                args: V,
+            ) -> JuliaResult<'target, 'data>
+            where
+                V: Values<'value, 'data, N>,
+            {
+                let func = invokelatest(&*frame);
+                let args = args.into_extended_with_start([erase_scope_lifetime(self)], Private);
+                JuliaFuture::new(frame, func, args.as_ref()).await
+            }
+
             #[julia_version(since = "1.9")]
             #[inline]
             async unsafe fn call_async_interactive<'target, 'value, V, const N: usize>(
@@ -1035,6 +1440,18 @@ cfg_if::cfg_if! {
                 JuliaFuture::new(frame, erase_scope_lifetime(self.as_value()), args).await
             }
 
+            #[inline]
+            async unsafe fn call_async_latest<'target, 'value, V, const N: usize>(
+                self,
+                frame: &mut AsyncGcFrame<'target>,
+                args: V,
+            ) -> JuliaResult<'target, 'data>
+            where
+                V: Values<'value, 'data, N>,
+            {
+                self.as_value().call_async_latest(frame, args).await
+            }
+
             #[julia_version(since = "1.9")]
             #[inline]
             async unsafe fn call_async_interactive<'target, 'value, V, const N: usize>(
@@ -1136,6 +1553,33 @@ cfg_if::cfg_if! {
                 JuliaFuture::new_with_keywords(frame, self, args).await
             }
 
+            #[inline]
+            async unsafe fn call_async_latest<'target, 'value, V, const N: usize>(
+                self,
+                frame: &mut AsyncGcFrame<'target>,
+                args: V,
+            ) -> JuliaResult<'target, 'data>
+            where
+                V: Values<'value, 'data, N>,
+            {
+                #[cfg(not(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9")))]
+                let kw_func = jl_get_kwsorter(self.func.datatype().unwrap(Private).cast());
+                #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
+                let kw_func = jl_kwcall_func;
+
+                let func = invokelatest(&*frame);
+                let kw_func = Value::wrap_non_null(NonNull::new_unchecked(kw_func), Private);
+                let args = args.into_extended_with_start(
+                    [
+                        kw_func,
+                        erase_scope_lifetime(self.keywords()),
+                        erase_scope_lifetime(self.function()),
+                    ],
+                    Private,
+                );
+                JuliaFuture::new(frame, func, args.as_ref()).await
+            }
+
             #[julia_version(since = "1.9")]
             #[inline]
             async unsafe fn call_async_interactive<'target, 'value, V, const N: usize>(
@@ -1265,6 +1709,42 @@ cfg_if::cfg_if! {
     }
 }
 
+/// Returns `Base.invokelatest`.
+#[inline]
+pub(crate) fn invokelatest<'target, Tgt>(target: &Tgt) -> Value<'target, 'static>
+where
+    Tgt: Target<'target>,
+{
+    inline_static_ref!(INVOKELATEST, Value, "Base.invokelatest", target)
+}
+
+// Calls `Base.invokelatest` with `start` followed by `args`.
+#[inline]
+pub(crate) unsafe fn invoke_latest<'target, 'value, 'data, V, T, const A: usize, const N: usize>(
+    target: T,
+    start: [*mut jl_value_t; A],
+    args: V,
+) -> ValueResult<'target, 'data, T>
+where
+    V: Values<'value, 'data, N>,
+    T: Target<'target>,
+{
+    let func = invokelatest(&target).unwrap(Private);
+    let values = args.into_extended_pointers_with_start(start, Private);
+    let values = values.as_ref();
+
+    let res = jl_call(func, values.as_ptr() as *mut _, values.len() as _);
+    let exc = jl_exception_occurred();
+
+    let res = if exc.is_null() {
+        Ok(NonNull::new_unchecked(res))
+    } else {
+        Err(NonNull::new_unchecked(exc))
+    };
+
+    target.result_from_ptr(res, Private)
+}
+
 mod private {
     #[cfg(feature = "internal-types")]
     use jlrs_macros::julia_version;
@@ -9,18 +9,24 @@ use std::{
     ptr::null_mut,
 };
 
-#[julia_version(windows_lts = false)]
-use jl_sys::jlrs_catch_tag_t_JLRS_CATCH_EXCEPTION;
+#[julia_version(windows_lts = true)]
+use jl_sys::jl_throw;
 use jl_sys::{
     jlrs_catch_t, jlrs_catch_tag_t_JLRS_CATCH_OK, jlrs_catch_tag_t_JLRS_CATCH_PANIC,
     jlrs_catch_wrapper,
 };
+#[julia_version(windows_lts = false)]
+use jl_sys::{
+    jl_value_t, jlrs_catch_tag_t_JLRS_CATCH_EXCEPTION, jlrs_catch_tag_t_JLRS_CATCH_RETHROW,
+    jlrs_catch_wrapper_handled,
+};
 use jlrs_macros::julia_version;
 
+use super::Rethrow;
 #[julia_version(windows_lts = true)]
 use crate::{
     call::Call,
-    data::managed::module::JlrsCore,
+    data::managed::{module::JlrsCore, Managed},
     prelude::{Target, Value},
 };
 #[julia_version(windows_lts = false)]
@@ -132,6 +138,87 @@ where
     }
 }
 
+/// Call `func`, if an exception is thrown it is caught and `exception_handler` is called. The
+/// exception handler is called from the catch block and the exception is rooted while it's
+/// called. If the exception handler returns `Err(Rethrow)`, e.g. by returning [`rethrow`], the
+/// exception is rethrown with its original backtrace.
+///
+/// Safety:
+///
+/// If an exception is thrown, there must be no pending drops. This includes the pending drops of
+/// the caller if the exception is rethrown. Only local scopes may be created in `func` and
+/// `exception_handler`.
+///
+/// [`rethrow`]: crate::catch::rethrow
+#[julia_version(windows_lts = false)]
+pub unsafe fn catch_exceptions_or_rethrow<G, H, T, E>(
+    mut func: G,
+    exception_handler: H,
+) -> Result<T, E>
+where
+    G: FnMut() -> T,
+    H: for<'exc> FnOnce(Value<'exc, 'static>) -> Result<E, Rethrow>,
+{
+    let func = &mut func;
+    let trampoline = trampoline_for(func);
+    let mut result = MaybeUninit::<T>::uninit();
+
+    let mut handler = Some(exception_handler);
+    let mut error = MaybeUninit::<E>::uninit();
+    let handler_trampoline = handler_trampoline_for(&mut handler, &mut error);
+
+    let res = jlrs_catch_wrapper_handled(
+        func as *mut _ as *mut _,
+        trampoline,
+        (&mut result) as *mut _ as *mut _,
+        (&mut handler) as *mut _ as *mut _,
+        handler_trampoline,
+        (&mut error) as *mut _ as *mut _,
+    );
+
+    match res.tag {
+        x if x == jlrs_catch_tag_t_JLRS_CATCH_OK => Ok(result.assume_init()),
+        x if x == jlrs_catch_tag_t_JLRS_CATCH_EXCEPTION => Err(error.assume_init()),
+        x if x == jlrs_catch_tag_t_JLRS_CATCH_PANIC => {
+            let err: Box<Box<dyn Any + Send>> = Box::from_raw(res.error.cast());
+            std::panic::resume_unwind(err)
+        }
+        _ => unreachable_unchecked(),
+    }
+}
+
+/// Call `func`, if an exception is thrown it is caught and `exception_handler` is called. The
+/// exception is guaranteed to be rooted inside the exception handler. If the exception handler
+/// returns `Err(Rethrow)`, e.g. by returning [`rethrow`], the exception is rethrown.
+///
+/// The original backtrace of a rethrown exception is not preserved when Julia 1.6 is used on
+/// Windows.
+///
+/// Safety:
+///
+/// If an exception is thrown, there must be no pending drops. This includes the pending drops of
+/// the caller if the exception is rethrown. Only local scopes may be created in `func` and
+/// `exception_handler`.
+///
+/// [`rethrow`]: crate::catch::rethrow
+#[julia_version(windows_lts = true)]
+pub unsafe fn catch_exceptions_or_rethrow<G, H, T, E>(func: G, exception_handler: H) -> Result<T, E>
+where
+    G: FnMut() -> T,
+    H: for<'exc> FnOnce(Value<'exc, 'static>) -> Result<E, Rethrow>,
+{
+    let res = catch_exceptions(func, |exc| match exception_handler(exc) {
+        Ok(err) => Ok(err),
+        Err(Rethrow) => Err(exc.leak()),
+    });
+
+    match res {
+        Ok(res) => Ok(res),
+        Err(Ok(err)) => Err(err),
+        Err(Err(exc)) => jl_throw(exc.ptr().as_ptr()),
+    }
+}
+
 #[inline]
 unsafe extern "C-unwind" fn trampoline<'frame, F: FnMut() -> T, T>(
     func: &mut F,
@@ -168,3 +255,63 @@ fn trampoline_for<'frame, F: FnMut() -> T, T>(
         >(Some(trampoline::<F, T>))
     }
 }
+
+#[julia_version(windows_lts = false)]
+#[inline]
+unsafe extern "C-unwind" fn handler_trampoline<H, E>(
+    handler: &mut Option<H>,
+    exception: NonNull<jl_value_t>,
+    result: &mut MaybeUninit<E>,
+) -> jlrs_catch_t
+where
+    H: for<'exc> FnOnce(Value<'exc, 'static>) -> Result<E, Rethrow>,
+{
+    let handler = handler.take().unwrap_unchecked();
+    // The exception is rooted while the catch block is active.
+    let exception = Value::wrap_non_null(exception, Private);
+    let res = catch_unwind(AssertUnwindSafe(|| handler(exception)));
+
+    match res {
+        Ok(Ok(e)) => {
+            result.write(e);
+            jlrs_catch_t {
+                tag: jlrs_catch_tag_t_JLRS_CATCH_EXCEPTION,
+                error: null_mut(),
+            }
+        }
+        Ok(Err(Rethrow)) => jlrs_catch_t {
+            tag: jlrs_catch_tag_t_JLRS_CATCH_RETHROW,
+            error: null_mut(),
+        },
+        Err(e) => {
+            // extra box because it's a fat pointer
+            jlrs_catch_t {
+                tag: jlrs_catch_tag_t_JLRS_CATCH_PANIC,
+                error: Box::leak(Box::new(e)) as *mut _ as *mut _,
+            }
+        }
+    }
+}
+
+#[julia_version(windows_lts = false)]
+#[inline]
+fn handler_trampoline_for<H, E>(
+    _: &mut Option<H>,
+    _: &mut MaybeUninit<E>,
+) -> Option<unsafe extern "C-unwind" fn(*mut c_void, *mut jl_value_t, *mut c_void) -> jlrs_catch_t>
+where
+    H: for<'exc> FnOnce(Value<'exc, 'static>) -> Result<E, Rethrow>,
+{
+    unsafe {
+        std::mem::transmute::<
+            Option<
+                unsafe extern "C-unwind" fn(
+                    &mut Option<H>,
+                    NonNull<jl_value_t>,
+                    &mut MaybeUninit<E>,
+                ) -> jlrs_catch_t,
+            >,
+            Option<unsafe extern "C-unwind" fn(*mut c_void, *mut jl_value_t, *mut c_void) -> jlrs_catch_t>,
+        >(Some(handler_trampoline::<H, E>))
+    }
+}
@@ -9,7 +9,13 @@
 //!
 //! Instead of using the checked variants you can create a try-catch block from Rust with
 //! [`catch_exceptions`]. This function takes two closures, think of them as the content of the
-//! try and catch blocks respectively.
+//! try and catch blocks respectively. If you only want to catch exceptions of a specific type,
+//! e.g. `BoundsError`, use [`catch_exceptions_of`] instead. Exceptions of other types are
+//! rethrown with their original backtrace. The exception handler of
+//! [`catch_exceptions_or_rethrow`] can decide to rethrow the exception with its original
+//! backtrace by returning [`rethrow`], like `rethrow()` in a Julia catch block. Exceptions can
+//! also be thrown explicitly with [`throw`]. Types for the exceptions defined in `Core` and
+//! `Base` can be found in the [`exception_types`] module.
 //!
 //! Because exceptions work by jumping to the nearest enclosing catch block, you must guarantee
 //! that there are no pending drops when an exception is thrown. See this [blog post] for more
@@ -19,9 +25,20 @@
 //! any scope we jump out of is removed from the GC stack. Dynamic scopes (i.e. scopes that
 //! provide a `GcFrame`) depend on `Drop` so jumping out of them is not sound.
 //!
+//! [`exception_types`]: crate::data::types::exception_types
 //! [blog post]: https://blog.rust-lang.org/inside-rust/2021/01/26/ffi-unwind-longjmp.html#pofs-and-stack-deallocating-functions
 
 use cfg_if::cfg_if;
+use jl_sys::{jl_throw, jl_value_t};
+
+use crate::{
+    data::{
+        managed::{private::ManagedPriv, value::Value},
+        types::construct_type::ConstructType,
+    },
+    memory::target::{unrooted::Unrooted, Target},
+    private::Private,
+};
 
 cfg_if! {
     if #[cfg(feature = "c-unwind")] {
@@ -33,4 +50,61 @@ cfg_if! {
     }
 }
 
-pub use imp::catch_exceptions;
+pub use imp::{catch_exceptions, catch_exceptions_or_rethrow};
+
+/// Returned by the exception handler of [`catch_exceptions_or_rethrow`] to rethrow the exception.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rethrow;
+
+/// Rethrow the exception that is being handled with its original backtrace.
+///
+/// This function must be returned from the exception handler of [`catch_exceptions_or_rethrow`].
+#[inline]
+pub fn rethrow<E>() -> Result<E, Rethrow> {
+    Err(Rethrow)
+}
+
+/// Call `func`, if an exception of type `X` is thrown it is caught and `exception_handler` is
+/// called. The exception is guaranteed to be rooted inside the exception handler. Exceptions of
+/// other types are rethrown, their original backtrace is preserved unless Julia 1.6 is used on
+/// Windows.
+///
+/// Safety:
+///
+/// If an exception is thrown, there must be no pending drops. This includes the pending drops of
+/// the caller if the exception is not an instance of `X`. Only local scopes may be created in
+/// `func`.
+pub unsafe fn catch_exceptions_of<X, G, H, T, E>(func: G, exception_handler: H) -> Result<T, E>
+where
+    X: ConstructType,
+    G: FnMut() -> T,
+    H: for<'exc> FnOnce(Value<'exc, 'static>) -> E,
+{
+    Unrooted::new()
+        .local_scope::<_, _, 1>(|mut frame| {
+            let filter = X::construct_type(&mut frame);
+            Ok(catch_exceptions_or_rethrow(func, |exc| {
+                if exc.isa(filter) {
+                    Ok(exception_handler(exc))
+                } else {
+                    rethrow()
+                }
+            }))
+        })
+        .unwrap_unchecked()
+}
+
+/// Throw `exception`.
+///
+/// The backtrace of the exception starts at the call to this function. Use [`rethrow`] to
+/// rethrow an exception with its original backtrace.
+///
+/// Safety:
+///
+/// There must be no pending drops. This function must be called from a context where exceptions
+/// are caught, e.g. inside the exception handler of `catch_exceptions` or a function called
+/// from Julia.
+#[inline]
+pub unsafe fn throw(exception: Value) -> ! {
+    jl_throw(exception.unwrap(Private))
+}
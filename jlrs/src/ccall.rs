@@ -5,27 +5,35 @@
 // TODO
 
 use std::{
-    cell::UnsafeCell,
+    any::Any,
+    backtrace::Backtrace,
+    cell::{RefCell, UnsafeCell},
     ffi::c_void,
     fmt::Debug,
     hint::spin_loop,
-    ptr::NonNull,
-    sync::{atomic::AtomicBool, Arc},
+    ptr::{null_mut, NonNull},
+    sync::{
+        atomic::{AtomicBool, AtomicPtr},
+        Arc, Once,
+    },
 };
 
 use atomic::Ordering;
 #[cfg(feature = "uv")]
 use jl_sys::uv_async_send;
-use jl_sys::{jl_tagged_gensym, jl_throw};
+use jl_sys::{jl_datatype_t, jl_emptysvec, jl_new_datatype, jl_tagged_gensym, jl_throw};
 use threadpool::{Builder, ThreadPool};
 
 use crate::{
     call::Call,
-    convert::{ccall_types::CCallReturn, into_julia::IntoJulia},
+    convert::{ccall_types::CCallReturn, into_jlrs_result::IntoJlrsResult, into_julia::IntoJulia},
     data::{
         managed::{
+            datatype::DataType,
             module::{JlrsCore, Module},
             private::ManagedPriv,
+            simple_vector::SimpleVector,
+            string::JuliaString,
             symbol::Symbol,
             value::{Value, ValueRet},
             Managed,
@@ -34,7 +42,7 @@ use crate::{
     },
     error::{JlrsError, JlrsResult},
     gc_safe::{GcSafeMutex, GcSafeOnceLock},
-    init_jlrs,
+    init_jlrs, inline_static_ref,
     memory::{
         stack_frame::{PinnedFrame, StackFrame},
         target::{
@@ -59,6 +67,186 @@ thread_local! {
     }
 }
 
+// The backtrace of the most recent panic on this thread, it's captured by the panic hook that is
+// installed when a module generated with `julia_module!` is initialized.
+thread_local! {
+    static PANIC_BACKTRACE: RefCell<Option<Backtrace>> = const { RefCell::new(None) };
+}
+
+static PANIC_HOOK: Once = Once::new();
+
+// The previously installed hook is still called, so panics are reported as before.
+fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let prev = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let backtrace = Backtrace::capture();
+            PANIC_BACKTRACE.with(|bt| *bt.borrow_mut() = Some(backtrace));
+            prev(info)
+        }));
+    });
+}
+
+/// The `JlrsCore.RustPanic` exception type.
+///
+/// Functions exported with `julia_module!` catch panics and rethrow them as a
+/// `JlrsCore.RustPanic` exception, which has two fields: `msg`, the panic message, and
+/// `backtrace`, the Rust backtrace. The type is created when the first module generated with
+/// `julia_module!` is initialized if JlrsCore doesn't define it. It's created with the C API
+/// rather than by evaluating its definition because that module can be initialized while it's
+/// precompiled, and Julia doesn't allow evaluating code in another module at that point.
+#[doc(hidden)]
+pub struct RustPanic(AtomicPtr<jl_datatype_t>);
+
+impl RustPanic {
+    pub const fn new() -> Self {
+        RustPanic(AtomicPtr::new(null_mut()))
+    }
+
+    /// Define `JlrsCore.RustPanic` if it hasn't been defined yet.
+    ///
+    /// Safety: must only be called from the init function of a module generated with
+    /// `julia_module!`, after jlrs has been initialized.
+    pub unsafe fn init(&self) -> JlrsResult<()> {
+        install_panic_hook();
+        let ty = Self::find_or_define()?;
+        self.0.store(ty.as_ptr(), Ordering::Relaxed);
+        Ok(())
+    }
+
+    // Returns `JlrsCore.RustPanic`, the type is created if it doesn't exist yet.
+    unsafe fn find_or_define() -> JlrsResult<NonNull<jl_datatype_t>> {
+        let unrooted = Unrooted::new();
+        unrooted.local_scope::<_, _, 4>(|mut frame| {
+            let jlrs_core = JlrsCore::module(&frame);
+
+            if jlrs_core.global(&frame, "RustPanic").is_err() {
+                let string = DataType::string_type(&frame).as_value();
+                let mut fnames = SimpleVector::with_capacity(&mut frame, 2);
+                let mut ftypes = SimpleVector::with_capacity(&mut frame, 2);
+                {
+                    let mut fnames = fnames.data_mut();
+                    fnames.set(0, Some(Symbol::new(&frame, "msg").as_value()))?;
+                    fnames.set(1, Some(Symbol::new(&frame, "backtrace").as_value()))?;
+                    let mut ftypes = ftypes.data_mut();
+                    ftypes.set(0, Some(string))?;
+                    ftypes.set(1, Some(string))?;
+                }
+
+                let lock_fn = jlrs_core.global(&frame, "lock_init_lock")?.as_value();
+                let unlock_fn = jlrs_core.global(&frame, "unlock_init_lock")?.as_value();
+                lock_fn.call0(&mut frame).into_jlrs_result()?;
+
+                // Another module might have defined it while we were waiting for the lock.
+                if jlrs_core.global(&frame, "RustPanic").is_err() {
+                    let name = Symbol::new(&frame, "RustPanic");
+                    let super_type =
+                        inline_static_ref!(EXCEPTION, DataType, "Core.Exception", &frame);
+
+                    #[cfg(feature = "julia-1-6")]
+                    let ty = jl_new_datatype(
+                        name.unwrap(Private),
+                        jlrs_core.unwrap(Private),
+                        super_type.unwrap(Private),
+                        jl_emptysvec,
+                        fnames.unwrap(Private),
+                        ftypes.unwrap(Private),
+                        0,
+                        0,
+                        2,
+                    );
+
+                    #[cfg(not(feature = "julia-1-6"))]
+                    let ty = jl_new_datatype(
+                        name.unwrap(Private),
+                        jlrs_core.unwrap(Private),
+                        super_type.unwrap(Private),
+                        jl_emptysvec,
+                        fnames.unwrap(Private),
+                        ftypes.unwrap(Private),
+                        jl_emptysvec,
+                        0,
+                        0,
+                        2,
+                    );
+
+                    // Safety: the new type is rooted by the constant as soon as it's created,
+                    // nothing is allocated in between.
+                    let ty = DataType::wrap_non_null(NonNull::new_unchecked(ty), Private);
+                    jlrs_core.set_const_unchecked(name, ty.as_value());
+                }
+
+                unlock_fn.call0(&mut frame).into_jlrs_result()?;
+            }
+
+            // The type is a constant global of JlrsCore, so it's never freed.
+            let ty = jlrs_core
+                .global(&frame, "RustPanic")?
+                .as_value()
+                .cast::<DataType>()?;
+            Ok(ty.unwrap_non_null(Private))
+        })
+    }
+
+    /// Throw a `RustPanic` exception that contains the message and backtrace of a caught panic.
+    ///
+    /// The backtrace is only captured if backtraces have been enabled with the `RUST_BACKTRACE`
+    /// environment variable. If `JlrsCore.RustPanic` can't be defined, a `JlrsCore.JlrsError` is
+    /// thrown instead.
+    ///
+    /// Safety: this method must only be called from `ccall`ed functions. There must be no pending
+    /// drops.
+    pub unsafe fn throw(&self, payload: Box<dyn Any + Send>) -> ! {
+        let exc = CCall::local_scope::<_, _, 3>(|mut frame| {
+            let msg = panic_message(payload.as_ref());
+            std::mem::drop(payload);
+            let backtrace = PANIC_BACKTRACE
+                .with(|bt| bt.borrow_mut().take())
+                .map(|bt| bt.to_string())
+                .unwrap_or_default();
+
+            // The type hasn't been stored yet if `init` hasn't been called in this process.
+            let ty = match NonNull::new(self.0.load(Ordering::Relaxed)) {
+                Some(ty) => Some(ty),
+                None => Self::find_or_define().ok(),
+            };
+
+            let msg_v = JuliaString::new(&mut frame, &msg).as_value();
+            if let Some(ty) = ty {
+                let rust_panic = DataType::wrap_non_null(ty, Private);
+                let bt_v = JuliaString::new(&mut frame, backtrace).as_value();
+                if let Ok(Ok(exc)) = rust_panic.instantiate(&frame, [msg_v, bt_v]) {
+                    return Ok(exc.leak());
+                }
+            }
+
+            let msg_v = JuliaString::new(&mut frame, format!("Rust panic: {}", msg)).as_value();
+            Ok(JlrsCore::jlrs_error(&frame)
+                .instantiate_unchecked(&frame, [msg_v])
+                .leak())
+        })
+        .unwrap();
+
+        CCall::throw_exception(exc)
+    }
+}
+
+impl Default for RustPanic {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        String::from("Box<dyn Any>")
+    }
+}
+
 unsafe fn init_pool() -> &'static GcSafeMutex<ThreadPool> {
     POOL.get_or_init(|| {
         let name = POOL_NAME.get_or_init(|| {
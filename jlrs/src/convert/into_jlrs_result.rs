@@ -1,21 +1,17 @@
 //! Convert a `JuliaResult` to a `JlrsResult`.
 //!
-//! A `JuliaResult` contains an exception in its `Err` variant, if you're only interested in
-//! the error message you can convert it to a `JlrsException` with the [`IntoJlrsResult`] trait
-//! defined in this module.
+//! A `JuliaResult` contains an exception in its `Err` variant, you can convert it to a
+//! [`JuliaException`] with the [`IntoJlrsResult`] trait defined in this module.
 
-use crate::{
-    data::managed::Managed,
-    error::{JlrsError, JlrsResult, JuliaResult, CANNOT_DISPLAY_VALUE},
-};
+use crate::error::{JlrsResult, JuliaException, JuliaResult};
 
 /// Extension trait that lets you convert a `JuliaResult` to a `JlrsResult`.
 ///
-/// If an exception is thrown, this trait's only method converts the exception to an error message
-/// by calling `Base.showerror`.
+/// If an exception is thrown, this trait's only method converts the exception to a
+/// [`JuliaException`], which contains the error message returned by `Base.showerror`.
 pub trait IntoJlrsResult<T>: private::IntoJlrsResultPriv {
-    /// Convert `self` to `JlrsResult` by calling `Base.showerror` if an exception has been
-    /// thrown.
+    /// Convert `self` to `JlrsResult`, if an exception has been thrown it's converted to
+    /// `JlrsError::JuliaException`.
     fn into_jlrs_result(self) -> JlrsResult<T>;
 }
 
@@ -24,7 +20,7 @@ impl<T> IntoJlrsResult<T> for JuliaResult<'_, '_, T> {
     fn into_jlrs_result(self) -> JlrsResult<T> {
         match self {
             Ok(v) => Ok(v),
-            Err(e) => JlrsError::exception_error(e.error_string_or(CANNOT_DISPLAY_VALUE))?,
+            Err(e) => Err(JuliaException::new(e))?,
         }
     }
 }
@@ -0,0 +1,246 @@
+//! Convert owned Rust data to Julia.
+//!
+//! [`IntoJulia`] only supports isbits-types, which means owned data like a `Vec<f64>` or a
+//! `String` must be converted by calling a constructor like `Array::from_vec` or
+//! `JuliaString::new`. The [`IntoJuliaOwned`] trait defined in this module provides a single
+//! conversion for this data, and for any type that implements both [`IntoJulia`] and
+//! [`ConstructType`]. It's implemented for the following types:
+//!
+//! | Rust                           | Julia                   |
+//! |--------------------------------|-------------------------|
+//! | `T: IntoJulia + ConstructType` | `T`                     |
+//! | `String`, `&str`               | `String`                |
+//! | `Vec<T>`, `Box<[T]>`           | `Vector{T}`             |
+//! | `Option<T>`                    | `Union{Nothing, T}`     |
+//!
+//! These implementations can be nested, e.g. a `Vec<Option<String>>` is converted to a
+//! `Vector{Union{Nothing, String}}`. The implementations for `String`, `Vec<T>`, `Box<[T]>`, and
+//! `Option<T>` can also be returned from functions exported with the [`julia_module`] macro.
+//!
+//! [`julia_module`]: ::jlrs_macros::julia_module
+
+use std::ptr::copy_nonoverlapping;
+
+use jl_sys::jl_arrayset;
+
+use crate::{
+    convert::{ccall_types::CCallReturn, into_jlrs_result::IntoJlrsResult, into_julia::IntoJulia},
+    data::{
+        layout::nothing::Nothing,
+        managed::{
+            array::Array,
+            private::ManagedPriv,
+            string::JuliaString,
+            value::{typed::TypedValueRet, Value, ValueData},
+            Managed,
+        },
+        types::construct_type::{ConstructType, RankedArrayType, UnionTypeConstructor},
+    },
+    error::JlrsResult,
+    memory::target::Target,
+    private::Private,
+};
+
+/// Trait implemented by owned data that can be converted to Julia data.
+///
+/// Safety: The converted data must be an instance of the type constructed by `JuliaType`.
+pub unsafe trait IntoJuliaOwned: Sized {
+    /// Type constructor for the type of the converted data.
+    type JuliaType: ConstructType;
+
+    /// Convert `self` to Julia data. If an exception is thrown it's converted to an error.
+    fn into_julia_owned<'target, Tgt>(
+        self,
+        target: Tgt,
+    ) -> JlrsResult<ValueData<'target, 'static, Tgt>>
+    where
+        Tgt: Target<'target>;
+
+    #[doc(hidden)]
+    fn vec_into_julia<'target, Tgt>(
+        data: Vec<Self>,
+        target: Tgt,
+    ) -> JlrsResult<ValueData<'target, 'static, Tgt>>
+    where
+        Tgt: Target<'target>,
+    {
+        target.with_local_scope::<_, _, 2>(|target, mut frame| {
+            let ty = Self::JuliaType::construct_type(&mut frame);
+            let array = Array::new_for(&mut frame, data.len(), ty).into_jlrs_result()?;
+
+            for (idx, elem) in data.into_iter().enumerate() {
+                frame.local_scope::<_, _, 1>(|mut frame| {
+                    let elem = elem.into_julia_owned(&mut frame)?;
+                    // Safety: the element is an instance of the element type, and the index is
+                    // in bounds.
+                    unsafe { jl_arrayset(array.unwrap(Private), elem.unwrap(Private), idx) };
+                    Ok(())
+                })?;
+            }
+
+            Ok(array.as_value().root(target))
+        })
+    }
+}
+
+// Safety: `IntoJulia::into_julia` returns an instance of `T`.
+unsafe impl<T: IntoJulia + ConstructType> IntoJuliaOwned for T {
+    type JuliaType = T;
+
+    #[inline]
+    fn into_julia_owned<'target, Tgt>(
+        self,
+        target: Tgt,
+    ) -> JlrsResult<ValueData<'target, 'static, Tgt>>
+    where
+        Tgt: Target<'target>,
+    {
+        Ok(Value::new(target, self))
+    }
+
+    fn vec_into_julia<'target, Tgt>(
+        data: Vec<Self>,
+        target: Tgt,
+    ) -> JlrsResult<ValueData<'target, 'static, Tgt>>
+    where
+        Tgt: Target<'target>,
+    {
+        target.with_local_scope::<_, _, 1>(|target, mut frame| {
+            let array = Array::new::<T, _, _>(&mut frame, data.len()).into_jlrs_result()?;
+
+            // Safety: the layout of `T` matches the layout of the elements.
+            unsafe {
+                let ptr = array.data_ptr().cast::<T>();
+                copy_nonoverlapping(data.as_ptr(), ptr, data.len());
+            }
+
+            Ok(array.as_value().root(target))
+        })
+    }
+}
+
+// Safety: `JuliaString::new` returns a `String`.
+unsafe impl IntoJuliaOwned for String {
+    type JuliaType = JuliaString<'static>;
+
+    #[inline]
+    fn into_julia_owned<'target, Tgt>(
+        self,
+        target: Tgt,
+    ) -> JlrsResult<ValueData<'target, 'static, Tgt>>
+    where
+        Tgt: Target<'target>,
+    {
+        self.as_str().into_julia_owned(target)
+    }
+}
+
+// Safety: `JuliaString::new` returns a `String`.
+unsafe impl IntoJuliaOwned for &str {
+    type JuliaType = JuliaString<'static>;
+
+    #[inline]
+    fn into_julia_owned<'target, Tgt>(
+        self,
+        target: Tgt,
+    ) -> JlrsResult<ValueData<'target, 'static, Tgt>>
+    where
+        Tgt: Target<'target>,
+    {
+        unsafe {
+            let s = JuliaString::new(&target, self).ptr();
+            Ok(target.data_from_ptr(s.cast(), Private))
+        }
+    }
+}
+
+// Safety: `vec_into_julia` returns a `Vector{T}`.
+unsafe impl<T: IntoJuliaOwned> IntoJuliaOwned for Vec<T> {
+    type JuliaType = RankedArrayType<T::JuliaType, 1>;
+
+    #[inline]
+    fn into_julia_owned<'target, Tgt>(
+        self,
+        target: Tgt,
+    ) -> JlrsResult<ValueData<'target, 'static, Tgt>>
+    where
+        Tgt: Target<'target>,
+    {
+        T::vec_into_julia(self, target)
+    }
+}
+
+// Safety: `vec_into_julia` returns a `Vector{T}`.
+unsafe impl<T: IntoJuliaOwned> IntoJuliaOwned for Box<[T]> {
+    type JuliaType = RankedArrayType<T::JuliaType, 1>;
+
+    #[inline]
+    fn into_julia_owned<'target, Tgt>(
+        self,
+        target: Tgt,
+    ) -> JlrsResult<ValueData<'target, 'static, Tgt>>
+    where
+        Tgt: Target<'target>,
+    {
+        T::vec_into_julia(self.into_vec(), target)
+    }
+}
+
+// Safety: `None` is converted to `nothing`, `Some(T)` to an instance of `T`.
+unsafe impl<T: IntoJuliaOwned> IntoJuliaOwned for Option<T> {
+    type JuliaType = UnionTypeConstructor<Nothing, T::JuliaType>;
+
+    #[inline]
+    fn into_julia_owned<'target, Tgt>(
+        self,
+        target: Tgt,
+    ) -> JlrsResult<ValueData<'target, 'static, Tgt>>
+    where
+        Tgt: Target<'target>,
+    {
+        match self {
+            Some(data) => data.into_julia_owned(target),
+            None => Ok(Value::nothing(&target).root(target)),
+        }
+    }
+}
+
+macro_rules! impl_ccall_return_owned {
+    ($ty:ty, $($bounds:tt)*) => {
+        unsafe impl<$($bounds)*> CCallReturn for $ty {
+            type FunctionReturnType = <Self as IntoJuliaOwned>::JuliaType;
+            type CCallReturnType = Value<'static, 'static>;
+            type ReturnAs = TypedValueRet<<Self as IntoJuliaOwned>::JuliaType>;
+
+            #[inline]
+            unsafe fn return_or_throw(self) -> Self::ReturnAs {
+                #[cfg(feature = "ccall")]
+                return return_owned(self);
+
+                #[cfg(not(feature = "ccall"))]
+                unimplemented!(
+                    "CCallReturn::return_or_throw can only be called if the `ccall` feature is enabled"
+                )
+            }
+        }
+    };
+}
+
+impl_ccall_return_owned!(String,);
+impl_ccall_return_owned!(Vec<T>, T: IntoJuliaOwned);
+impl_ccall_return_owned!(Box<[T]>, T: IntoJuliaOwned);
+impl_ccall_return_owned!(Option<T>, T: IntoJuliaOwned);
+
+// Convert the data to Julia data and return it, or throw an exception if an error occurs.
+#[cfg(feature = "ccall")]
+#[inline]
+unsafe fn return_owned<T: IntoJuliaOwned>(data: T) -> TypedValueRet<T::JuliaType> {
+    use crate::data::managed::value::typed::TypedValue;
+
+    let res = crate::ccall::CCall::local_scope::<_, _, 1>(|mut frame| {
+        let value = data.into_julia_owned(&mut frame)?;
+        Ok(TypedValue::<T::JuliaType>::from_value_unchecked(value).leak())
+    });
+
+    res.return_or_throw()
+}
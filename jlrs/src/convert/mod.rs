@@ -4,9 +4,12 @@ pub mod ccall_types;
 pub mod compatible;
 pub mod into_jlrs_result;
 pub mod into_julia;
+pub mod into_julia_owned;
 #[cfg(feature = "async-rt")]
 pub mod into_result;
 #[cfg(feature = "jlrs-ndarray")]
 pub mod ndarray;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod to_symbol;
 pub mod unbox;
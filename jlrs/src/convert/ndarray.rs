@@ -1,11 +1,32 @@
 //! Borrow data from Julia arrays as `ndarray`'s `ArrayView` and `ArrayViewMut`.
+//!
+//! Strided views like `SubArray`s can be borrowed the same way through their
+//! [`StridedArrayAccessor`].
+//!
+//! Owned ndarrays can be moved to Julia without copying their data with [`Array::from_ndarray`]
+//! and [`TypedArray::from_ndarray`].
+//!
+//! [`Array::from_ndarray`]: crate::data::managed::array::Array::from_ndarray
+//! [`TypedArray::from_ndarray`]: crate::data::managed::array::TypedArray::from_ndarray
 
-use ndarray::{ArrayView, ArrayViewMut, Dim, IntoDimension, IxDynImpl, ShapeBuilder};
+use ndarray::{
+    Array, ArrayBase, ArrayView, ArrayViewMut, Axis, Dim, Dimension, IntoDimension, IxDynImpl,
+    RawData, ShapeBuilder, StrideShape,
+};
 
 use super::compatible::{Compatible, CompatibleCast};
-use crate::data::managed::array::data::{
-    accessor::{BitsArrayAccessor, InlinePtrArrayAccessor, Mutability, Mutable},
-    copied::CopiedArray,
+use crate::{
+    data::{
+        layout::valid_layout::ValidField,
+        managed::array::{
+            data::{
+                accessor::{BitsArrayAccessor, InlinePtrArrayAccessor, Mutability, Mutable},
+                copied::CopiedArray,
+            },
+            strided::StridedArrayAccessor,
+        },
+    },
+    error::{InstantiationError, JlrsResult},
 };
 
 /// Trait to borrow Julia arrays with inline data as `ndarray`'s `ArrayView`.
@@ -119,10 +140,151 @@ impl<'view, T> NdArrayViewMut<'view, T> for CopiedArray<T> {
     }
 }
 
+impl<'borrow: 'view, 'view, 'data, T, M> NdArrayView<'view, T>
+    for StridedArrayAccessor<'borrow, 'data, T, M>
+where
+    T: ValidField,
+    M: Mutability,
+{
+    fn array_view(&'view self) -> ArrayView<'view, T, Dim<IxDynImpl>> {
+        let layout = StridedLayout::new(self.dimensions().as_slice(), self.strides());
+        // Safety: the shape and strides are those of the view, which is borrowed. The pointer
+        // points to the first element in memory, which is part of the view.
+        unsafe {
+            let ptr = self.as_ptr().offset(layout.offset);
+            let view = ArrayView::from_shape_ptr(layout.shape(), ptr);
+            layout.invert_axes(view)
+        }
+    }
+
+    fn compatible_array_view<U>(&'view self) -> ArrayView<'view, U, Dim<IxDynImpl>>
+    where
+        T: Compatible<U>,
+    {
+        let layout = StridedLayout::new(self.dimensions().as_slice(), self.strides());
+        // Safety: the shape and strides are those of the view, which is borrowed. The pointer
+        // points to the first element in memory, which is part of the view. `T` and `U` have the
+        // same layout.
+        unsafe {
+            let ptr = self.as_ptr().offset(layout.offset).cast();
+            let view = ArrayView::from_shape_ptr(layout.shape(), ptr);
+            layout.invert_axes(view)
+        }
+    }
+}
+
+impl<'borrow: 'view, 'view, 'data, T> NdArrayViewMut<'view, T>
+    for StridedArrayAccessor<'borrow, 'data, T, Mutable<'borrow, T>>
+where
+    T: ValidField,
+{
+    fn array_view_mut(&'view mut self) -> ArrayViewMut<'view, T, Dim<IxDynImpl>> {
+        let layout = StridedLayout::new(self.dimensions().as_slice(), self.strides());
+        // Safety: the shape and strides are those of the view, which is mutably borrowed. The
+        // pointer points to the first element in memory, which is part of the view.
+        unsafe {
+            let ptr = self.as_mut_ptr().offset(layout.offset);
+            let view = ArrayViewMut::from_shape_ptr(layout.shape(), ptr);
+            layout.invert_axes(view)
+        }
+    }
+
+    fn compatible_array_view_mut<U>(&'view mut self) -> ArrayViewMut<'view, U, Dim<IxDynImpl>>
+    where
+        T: Compatible<U>,
+    {
+        let layout = StridedLayout::new(self.dimensions().as_slice(), self.strides());
+        // Safety: the shape and strides are those of the view, which is mutably borrowed. The
+        // pointer points to the first element in memory, which is part of the view. `T` and `U`
+        // have the same layout.
+        unsafe {
+            let ptr = self.as_mut_ptr().offset(layout.offset).cast();
+            let view = ArrayViewMut::from_shape_ptr(layout.shape(), ptr);
+            layout.invert_axes(view)
+        }
+    }
+}
+
+// ndarray doesn't accept negative strides. A view with negative strides is created from the
+// element with the lowest address and the absolute strides, the axes with a negative stride are
+// inverted afterwards.
+struct StridedLayout {
+    dims: Vec<usize>,
+    strides: Vec<usize>,
+    inverted: Vec<usize>,
+    offset: isize,
+}
+
+impl StridedLayout {
+    fn new(dims: &[usize], strides: &[isize]) -> Self {
+        let mut inverted = Vec::new();
+        let mut offset = 0;
+
+        for (axis, (&dim, &stride)) in dims.iter().zip(strides).enumerate() {
+            if stride < 0 && dim > 0 {
+                offset += stride * (dim as isize - 1);
+                inverted.push(axis);
+            }
+        }
+
+        StridedLayout {
+            dims: dims.to_vec(),
+            strides: strides.iter().map(|s| s.unsigned_abs()).collect(),
+            inverted,
+            offset,
+        }
+    }
+
+    fn shape(&self) -> StrideShape<Dim<IxDynImpl>> {
+        let strides = self.strides.as_slice().into_dimension();
+        self.dims.as_slice().into_dimension().strides(strides)
+    }
+
+    fn invert_axes<S: RawData>(
+        &self,
+        mut view: ArrayBase<S, Dim<IxDynImpl>>,
+    ) -> ArrayBase<S, Dim<IxDynImpl>> {
+        for &axis in self.inverted.iter() {
+            view.invert_axis(Axis(axis));
+        }
+
+        view
+    }
+}
+
+// Take the data of an owned ndarray and return it with the dimensions of the Julia array that
+// shares its layout. Julia arrays are column-major, so the shape of an array in standard order is
+// reversed; Julia sees the transpose of that array.
+pub(crate) fn ndarray_into_vec<T, D>(data: Array<T, D>) -> JlrsResult<(Vec<T>, Vec<usize>)>
+where
+    D: Dimension,
+{
+    let dims = if data.t().is_standard_layout() {
+        data.shape().to_vec()
+    } else if data.is_standard_layout() {
+        data.shape().iter().rev().copied().collect()
+    } else {
+        Err(InstantiationError::NonContiguousNdArray)?
+    };
+
+    // An owned array can be a slice of its data, in which case it can't be moved.
+    let ptr = data.as_ptr();
+    let len = data.len();
+    let vec = data.into_raw_vec();
+    if vec.as_ptr() != ptr || vec.len() != len {
+        Err(InstantiationError::NonContiguousNdArray)?
+    }
+
+    Ok((vec, dims))
+}
+
 mod private {
-    use crate::data::managed::array::data::{
-        accessor::{BitsArrayAccessor, InlinePtrArrayAccessor, Mutability},
-        copied::CopiedArray,
+    use crate::data::managed::array::{
+        data::{
+            accessor::{BitsArrayAccessor, InlinePtrArrayAccessor, Mutability},
+            copied::CopiedArray,
+        },
+        strided::StridedArrayAccessor,
     };
 
     pub trait NdArrayPriv {}
@@ -139,4 +301,9 @@ mod private {
     }
 
     impl<T> NdArrayPriv for CopiedArray<T> {}
+
+    impl<'borrow, 'data, T, M> NdArrayPriv for StridedArrayAccessor<'borrow, 'data, T, M> where
+        M: Mutability
+    {
+    }
 }
@@ -0,0 +1,573 @@
+// Deserializer that converts Julia data to Rust data.
+
+use std::ptr::NonNull;
+
+use jl_sys::{
+    jl_arrayref, jl_bool_type, jl_char_type, jl_datatype_t, jl_field_isptr, jl_field_offset,
+    jl_float32_type, jl_float64_type, jl_get_nth_field, jl_get_nth_field_noalloc, jl_int16_type,
+    jl_int32_type, jl_int64_type, jl_int8_type, jl_uint16_type, jl_uint32_type, jl_uint64_type,
+    jl_uint8_type, jl_value_t,
+};
+use serde::{
+    de::{
+        value::StrDeserializer, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess,
+        SeqAccess, VariantAccess, Visitor,
+    },
+    forward_to_deserialize_any,
+};
+
+use super::Roots;
+use crate::{
+    call::Call,
+    data::{
+        layout::{char::Char, nothing::Nothing, tuple::Tuple},
+        managed::{
+            array::{dimensions::Dims, Array},
+            datatype::DataType,
+            function::Function,
+            private::ManagedPriv,
+            string::JuliaString,
+            symbol::Symbol,
+            value::{Value, ValueRef},
+            Managed,
+        },
+        types::typecheck::NamedTuple,
+    },
+    error::{AccessError, JlrsError, JlrsResult, SerdeError, CANNOT_DISPLAY_TYPE},
+    inline_static_ref,
+    private::Private,
+};
+
+// Data that is deserialized, either a Julia value or primitive data that has been read from a
+// field or array element without boxing it.
+#[derive(Clone, Copy)]
+enum Item<'scope, 'data> {
+    Value(Value<'scope, 'data>),
+    Primitive(Primitive),
+}
+
+#[derive(Clone, Copy)]
+enum Primitive {
+    Bool(bool),
+    Char(Char),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    F32(f32),
+    F64(f64),
+}
+
+impl Primitive {
+    // Safety: `ptr` must point to data of type `ty`.
+    unsafe fn read(ty: DataType, ptr: *const u8) -> Option<Self> {
+        let ty = ty.unwrap(Private);
+        let p = if ty == jl_float64_type {
+            Primitive::F64(ptr.cast::<f64>().read_unaligned())
+        } else if ty == jl_int64_type {
+            Primitive::I64(ptr.cast::<i64>().read_unaligned())
+        } else if ty == jl_bool_type {
+            Primitive::Bool(ptr.read() != 0)
+        } else if ty == jl_float32_type {
+            Primitive::F32(ptr.cast::<f32>().read_unaligned())
+        } else if ty == jl_int32_type {
+            Primitive::I32(ptr.cast::<i32>().read_unaligned())
+        } else if ty == jl_uint8_type {
+            Primitive::U8(ptr.read())
+        } else if ty == jl_uint64_type {
+            Primitive::U64(ptr.cast::<u64>().read_unaligned())
+        } else if ty == jl_char_type {
+            Primitive::Char(ptr.cast::<Char>().read_unaligned())
+        } else if ty == jl_int8_type {
+            Primitive::I8(ptr.cast::<i8>().read())
+        } else if ty == jl_int16_type {
+            Primitive::I16(ptr.cast::<i16>().read_unaligned())
+        } else if ty == jl_uint16_type {
+            Primitive::U16(ptr.cast::<u16>().read_unaligned())
+        } else if ty == jl_uint32_type {
+            Primitive::U32(ptr.cast::<u32>().read_unaligned())
+        } else {
+            return None;
+        };
+
+        Some(p)
+    }
+
+    fn visit<'de, V: Visitor<'de>>(self, visitor: V) -> JlrsResult<V::Value> {
+        match self {
+            Primitive::Bool(v) => visitor.visit_bool(v),
+            Primitive::Char(v) => match v.try_as_char() {
+                Some(ch) => visitor.visit_char(ch),
+                None => visitor.visit_u32(v.as_u32()),
+            },
+            Primitive::I8(v) => visitor.visit_i8(v),
+            Primitive::I16(v) => visitor.visit_i16(v),
+            Primitive::I32(v) => visitor.visit_i32(v),
+            Primitive::I64(v) => visitor.visit_i64(v),
+            Primitive::U8(v) => visitor.visit_u8(v),
+            Primitive::U16(v) => visitor.visit_u16(v),
+            Primitive::U32(v) => visitor.visit_u32(v),
+            Primitive::U64(v) => visitor.visit_u64(v),
+            Primitive::F32(v) => visitor.visit_f32(v),
+            Primitive::F64(v) => visitor.visit_f64(v),
+        }
+    }
+}
+
+pub(super) struct Deserializer<'a, 'scope, 'data> {
+    roots: &'a mut Roots<'scope>,
+    item: Item<'scope, 'data>,
+}
+
+impl<'a, 'scope, 'data> Deserializer<'a, 'scope, 'data> {
+    pub(super) fn new(roots: &'a mut Roots<'scope>, value: Value<'scope, 'data>) -> Self {
+        Deserializer {
+            roots,
+            item: Item::Value(value),
+        }
+    }
+
+    fn is_nothing(&self) -> bool {
+        match self.item {
+            Item::Value(v) => v.is::<Nothing>(),
+            _ => false,
+        }
+    }
+}
+
+// Returns the `idx`th field of `value`, fields with primitive types are read without boxing them.
+fn field<'scope, 'data>(
+    roots: &mut Roots<'scope>,
+    value: Value<'scope, 'data>,
+    idx: usize,
+) -> JlrsResult<Item<'scope, 'data>> {
+    let ty = value.datatype();
+    let ty_ptr: *mut jl_datatype_t = ty.unwrap(Private);
+
+    // Safety: `idx` is in bounds, the pointers point to valid data. If the field is boxed it's
+    // referenced by `value`, otherwise it's rooted immediately.
+    unsafe {
+        if jl_field_isptr(ty_ptr, idx as _) {
+            let ptr = jl_get_nth_field_noalloc(value.unwrap(Private), idx);
+            let ptr = NonNull::new(ptr).ok_or(AccessError::UndefRef)?;
+            return Ok(Item::Value(Value::wrap_non_null(ptr, Private)));
+        }
+
+        let unrooted = roots.unrooted();
+        if let Some(field_ty) = ty.field_type_unchecked(unrooted, idx) {
+            if let Ok(field_ty) = field_ty.as_value().cast::<DataType>() {
+                let offset = jl_field_offset(ty_ptr, idx as _) as usize;
+                let ptr = value.data_ptr().cast::<u8>().as_ptr().add(offset);
+                if let Some(p) = Primitive::read(field_ty, ptr) {
+                    return Ok(Item::Primitive(p));
+                }
+            }
+        }
+
+        let ptr = jl_get_nth_field(value.unwrap(Private), idx);
+        let ptr = NonNull::new(ptr).ok_or(AccessError::UndefRef)?;
+        Ok(Item::Value(roots.root(ValueRef::wrap(ptr))))
+    }
+}
+
+// Returns the `idx`th element of `array`, elements with primitive types are read without boxing
+// them.
+fn element<'scope, 'data>(
+    roots: &mut Roots<'scope>,
+    array: Array<'scope, 'data>,
+    idx: usize,
+) -> JlrsResult<Item<'scope, 'data>> {
+    // Safety: `idx` is in bounds, the pointers point to valid data. If the element is boxed
+    // it's referenced by `array`, otherwise it's rooted immediately.
+    unsafe {
+        if array.is_value_array() {
+            let ptr = array.data_ptr().cast::<*mut jl_value_t>().add(idx).read();
+            let ptr = NonNull::new(ptr).ok_or(AccessError::UndefRef)?;
+            return Ok(Item::Value(Value::wrap_non_null(ptr, Private)));
+        }
+
+        if let Ok(elty) = array.element_type().cast::<DataType>() {
+            let ptr = array
+                .data_ptr()
+                .cast::<u8>()
+                .add(idx * array.element_size());
+            if let Some(p) = Primitive::read(elty, ptr) {
+                return Ok(Item::Primitive(p));
+            }
+        }
+
+        let ptr = jl_arrayref(array.unwrap(Private), idx);
+        let ptr = NonNull::new(ptr).ok_or(AccessError::UndefRef)?;
+        Ok(Item::Value(roots.root(ValueRef::wrap(ptr))))
+    }
+}
+
+#[inline]
+fn str_de(s: &str) -> StrDeserializer<'_, Box<JlrsError>> {
+    s.into_deserializer()
+}
+
+fn unsupported(value: Value) -> Box<JlrsError> {
+    SerdeError::Unsupported {
+        value_type: value.datatype().display_string_or(CANNOT_DISPLAY_TYPE),
+    }
+    .into()
+}
+
+impl<'de, 'a, 'scope, 'data> serde::Deserializer<'de> for Deserializer<'a, 'scope, 'data> {
+    type Error = Box<JlrsError>;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> JlrsResult<V::Value> {
+        let value = match self.item {
+            Item::Value(value) => value,
+            Item::Primitive(p) => return p.visit(visitor),
+        };
+
+        let ty = value.datatype();
+        // Safety: the data has type `ty`.
+        if let Some(p) = unsafe { Primitive::read(ty, value.data_ptr().cast().as_ptr()) } {
+            return p.visit(visitor);
+        }
+
+        if value.is::<Nothing>() {
+            visitor.visit_unit()
+        } else if let Ok(s) = value.cast::<JuliaString>() {
+            visitor.visit_str(s.as_str()?)
+        } else if let Ok(s) = value.cast::<Symbol>() {
+            visitor.visit_str(s.as_str()?)
+        } else if let Ok(array) = value.cast::<Array>() {
+            // Safety: the dimensions aren't mutated while they're in use.
+            let len = unsafe { array.dimensions().size() };
+            visitor.visit_seq(ArraySeq {
+                roots: self.roots,
+                array,
+                idx: 0,
+                len,
+            })
+        } else if value.is::<NamedTuple>() {
+            visitor.visit_map(FieldMap::new(self.roots, value))
+        } else if value.is::<Tuple>() {
+            visitor.visit_seq(FieldSeq {
+                roots: self.roots,
+                value,
+                idx: 0,
+                len: value.n_fields(),
+            })
+        } else if is_dict(value) {
+            visitor.visit_map(DictMap::new(self.roots, value)?)
+        } else if value.is_type() {
+            Err(unsupported(value))
+        } else if value.n_fields() > 0 {
+            visitor.visit_map(FieldMap::new(self.roots, value))
+        } else if ty.instance().is_some() {
+            visitor.visit_unit()
+        } else {
+            Err(unsupported(value))
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> JlrsResult<V::Value> {
+        if self.is_nothing() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> JlrsResult<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> JlrsResult<V::Value> {
+        let value = match self.item {
+            Item::Value(value) => value,
+            Item::Primitive(_) => Err(SerdeError::NotAnEnum {
+                value_type: "a primitive type".into(),
+            })?,
+        };
+
+        if let Ok(s) = value.cast::<Symbol>() {
+            let variant: &str = s.as_str()?;
+            return visitor.visit_enum(str_de(variant));
+        } else if let Ok(s) = value.cast::<JuliaString>() {
+            let variant: &str = s.as_str()?;
+            return visitor.visit_enum(str_de(variant));
+        } else if value.is::<NamedTuple>() && value.n_fields() == 1 {
+            let variant = value.datatype().field_name_str(0).unwrap_or_default();
+            let item = field(self.roots, value, 0)?;
+            return visitor.visit_enum(Enum {
+                roots: self.roots,
+                variant,
+                item,
+            });
+        }
+
+        Err(SerdeError::NotAnEnum {
+            value_type: value.datatype().display_string_or(CANNOT_DISPLAY_TYPE),
+        })?
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+fn is_dict(value: Value) -> bool {
+    let unrooted = value.unrooted_target();
+    let abstract_dict = inline_static_ref!(ABSTRACT_DICT, Value, "Base.AbstractDict", unrooted);
+    value.isa(abstract_dict)
+}
+
+struct ArraySeq<'a, 'scope, 'data> {
+    roots: &'a mut Roots<'scope>,
+    array: Array<'scope, 'data>,
+    idx: usize,
+    len: usize,
+}
+
+impl<'de, 'a, 'scope, 'data> SeqAccess<'de> for ArraySeq<'a, 'scope, 'data> {
+    type Error = Box<JlrsError>;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> JlrsResult<Option<T::Value>> {
+        if self.idx >= self.len {
+            return Ok(None);
+        }
+
+        let item = element(self.roots, self.array, self.idx)?;
+        self.idx += 1;
+
+        let de = Deserializer {
+            roots: self.roots,
+            item,
+        };
+        seed.deserialize(de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.idx)
+    }
+}
+
+struct FieldSeq<'a, 'scope, 'data> {
+    roots: &'a mut Roots<'scope>,
+    value: Value<'scope, 'data>,
+    idx: usize,
+    len: usize,
+}
+
+impl<'de, 'a, 'scope, 'data> SeqAccess<'de> for FieldSeq<'a, 'scope, 'data> {
+    type Error = Box<JlrsError>;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> JlrsResult<Option<T::Value>> {
+        if self.idx >= self.len {
+            return Ok(None);
+        }
+
+        let item = field(self.roots, self.value, self.idx)?;
+        self.idx += 1;
+
+        let de = Deserializer {
+            roots: self.roots,
+            item,
+        };
+        seed.deserialize(de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.idx)
+    }
+}
+
+struct FieldMap<'a, 'scope, 'data> {
+    roots: &'a mut Roots<'scope>,
+    value: Value<'scope, 'data>,
+    idx: usize,
+    len: usize,
+}
+
+impl<'a, 'scope, 'data> FieldMap<'a, 'scope, 'data> {
+    fn new(roots: &'a mut Roots<'scope>, value: Value<'scope, 'data>) -> Self {
+        FieldMap {
+            roots,
+            value,
+            idx: 0,
+            len: value.n_fields(),
+        }
+    }
+}
+
+impl<'de, 'a, 'scope, 'data> MapAccess<'de> for FieldMap<'a, 'scope, 'data> {
+    type Error = Box<JlrsError>;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> JlrsResult<Option<K::Value>> {
+        if self.idx >= self.len {
+            return Ok(None);
+        }
+
+        let name = self
+            .value
+            .datatype()
+            .field_name_str(self.idx)
+            .unwrap_or_default();
+        seed.deserialize(str_de(name)).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> JlrsResult<V::Value> {
+        let item = field(self.roots, self.value, self.idx)?;
+        self.idx += 1;
+
+        let de = Deserializer {
+            roots: self.roots,
+            item,
+        };
+        seed.deserialize(de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.idx)
+    }
+}
+
+struct DictMap<'a, 'scope, 'data> {
+    roots: &'a mut Roots<'scope>,
+    keys: Array<'scope, 'data>,
+    values: Array<'scope, 'data>,
+    idx: usize,
+    len: usize,
+}
+
+impl<'a, 'scope, 'data> DictMap<'a, 'scope, 'data> {
+    fn new(roots: &'a mut Roots<'scope>, dict: Value<'scope, 'data>) -> JlrsResult<Self> {
+        let unrooted = roots.unrooted();
+
+        // Safety: `collect`, `keys` and `values` are safe to call with a dict.
+        let (keys, values) = unsafe {
+            let collect = inline_static_ref!(COLLECT, Function, "Base.collect", unrooted);
+            let keys_fn = inline_static_ref!(KEYS, Function, "Base.keys", unrooted);
+            let values_fn = inline_static_ref!(VALUES, Function, "Base.values", unrooted);
+
+            let keys = keys_fn.call1(unrooted, dict);
+            let keys = roots.root_result(keys)?;
+            let keys = collect.call1(unrooted, keys);
+            let keys = roots.root_result(keys)?.cast::<Array>()?;
+
+            let values = values_fn.call1(unrooted, dict);
+            let values = roots.root_result(values)?;
+            let values = collect.call1(unrooted, values);
+            let values = roots.root_result(values)?.cast::<Array>()?;
+
+            (keys, values)
+        };
+
+        // Safety: the dimensions aren't mutated while they're in use.
+        let len = unsafe { keys.dimensions().size() };
+
+        Ok(DictMap {
+            roots,
+            keys,
+            values,
+            idx: 0,
+            len,
+        })
+    }
+}
+
+impl<'de, 'a, 'scope, 'data> MapAccess<'de> for DictMap<'a, 'scope, 'data> {
+    type Error = Box<JlrsError>;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> JlrsResult<Option<K::Value>> {
+        if self.idx >= self.len {
+            return Ok(None);
+        }
+
+        let item = element(self.roots, self.keys, self.idx)?;
+        let de = Deserializer {
+            roots: self.roots,
+            item,
+        };
+        seed.deserialize(de).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> JlrsResult<V::Value> {
+        let item = element(self.roots, self.values, self.idx)?;
+        self.idx += 1;
+
+        let de = Deserializer {
+            roots: self.roots,
+            item,
+        };
+        seed.deserialize(de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.idx)
+    }
+}
+
+struct Enum<'a, 'scope, 'data> {
+    roots: &'a mut Roots<'scope>,
+    variant: &'scope str,
+    item: Item<'scope, 'data>,
+}
+
+impl<'de, 'a, 'scope, 'data> EnumAccess<'de> for Enum<'a, 'scope, 'data> {
+    type Error = Box<JlrsError>;
+    type Variant = Deserializer<'a, 'scope, 'data>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> JlrsResult<(V::Value, Self::Variant)> {
+        let variant = seed.deserialize(str_de(self.variant))?;
+        let de = Deserializer {
+            roots: self.roots,
+            item: self.item,
+        };
+        Ok((variant, de))
+    }
+}
+
+impl<'de, 'a, 'scope, 'data> VariantAccess<'de> for Deserializer<'a, 'scope, 'data> {
+    type Error = Box<JlrsError>;
+
+    fn unit_variant(self) -> JlrsResult<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> JlrsResult<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> JlrsResult<V::Value> {
+        serde::Deserializer::deserialize_any(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> JlrsResult<V::Value> {
+        serde::Deserializer::deserialize_any(self, visitor)
+    }
+}
@@ -0,0 +1,215 @@
+//! Convert Rust data to Julia data and back with `serde`.
+//!
+//! Any type that implements `Serialize` can be converted to Julia data with [`to_value`], and any
+//! type that implements `DeserializeOwned` can be extracted from Julia data with [`from_value`].
+//! Data is mapped as follows:
+//!
+//! | Rust                            | Julia                                       |
+//! |---------------------------------|---------------------------------------------|
+//! | `bool`, integers, floats, `char`| `Bool`, `Int8`..`UInt64`, `Float32`/`64`, `Char` |
+//! | `String`, `&str`                | `String`                                    |
+//! | bytes                           | `Vector{UInt8}`                             |
+//! | `None`, `()`, unit structs      | `nothing`                                   |
+//! | `Some(x)`, newtype structs      | `x`                                         |
+//! | sequences                       | `Vector`                                    |
+//! | tuples, tuple structs           | `Tuple`                                     |
+//! | maps                            | `Dict`                                      |
+//! | structs                         | `NamedTuple` or `Dict{Symbol,Any}`          |
+//! | unit variants                   | `Symbol`                                    |
+//! | other variants                  | `NamedTuple` with a single field            |
+//!
+//! The element type of a `Vector` and the key and value types of a `Dict` are the narrowest
+//! types that contain all elements. How structs are represented can be selected with
+//! [`StructRepr`].
+//!
+//! When deserializing, Julia data is walked based on its type. In addition to the mapping above,
+//! any `AbstractDict` can be deserialized as a map, any array as a sequence in column-major
+//! order, a `Symbol` as a string, and other composite types as a map from field names to
+//! field values.
+//!
+//! Example:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::util::test::JULIA;
+//! use jlrs::convert::serde::{from_value, to_value};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Point {
+//!     x: f64,
+//!     y: f64,
+//! }
+//!
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! # let mut frame = StackFrame::new();
+//! # let mut julia = julia.instance(&mut frame);
+//! julia
+//!     .scope(|mut frame| {
+//!         let point = Point { x: 1.0, y: 2.0 };
+//!
+//!         // Converted to `(x = 1.0, y = 2.0)`
+//!         let value = to_value(&mut frame, &point)?;
+//!         assert!(value.is::<jlrs::data::types::typecheck::NamedTuple>());
+//!
+//!         let point2: Point = from_value(value)?;
+//!         assert_eq!(point, point2);
+//!
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # });
+//! # }
+//! ```
+
+mod de;
+mod ser;
+
+use std::fmt::Display;
+
+use jl_sys::jl_array_ptr_1d_push;
+use serde::{de::DeserializeOwned, Serialize};
+
+use self::{de::Deserializer, ser::Serializer};
+use crate::{
+    data::managed::{
+        array::Array,
+        datatype::DataType,
+        private::ManagedPriv,
+        value::{Value, ValueData, ValueRef},
+        Managed, Ref,
+    },
+    error::{JlrsError, JlrsResult, SerdeError, CANNOT_DISPLAY_VALUE},
+    memory::target::{
+        frame::LocalGcFrame, reusable_slot::LocalReusableSlot, unrooted::Unrooted, Target,
+    },
+    private::Private,
+};
+
+/// How a Rust struct is represented in Julia.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum StructRepr {
+    /// Structs are converted to a `NamedTuple`.
+    #[default]
+    NamedTuple,
+    /// Structs are converted to a `Dict{Symbol,Any}`.
+    Dict,
+}
+
+/// Convert `value` to Julia data, structs are converted to a `NamedTuple`.
+///
+/// If an exception is thrown while the data is converted, it's converted to an error.
+pub fn to_value<'target, T, Tgt>(
+    target: Tgt,
+    value: &T,
+) -> JlrsResult<ValueData<'target, 'static, Tgt>>
+where
+    T: Serialize + ?Sized,
+    Tgt: Target<'target>,
+{
+    to_value_with(target, value, StructRepr::NamedTuple)
+}
+
+/// Convert `value` to Julia data, structs are represented as `repr`.
+///
+/// If an exception is thrown while the data is converted, it's converted to an error.
+pub fn to_value_with<'target, T, Tgt>(
+    target: Tgt,
+    value: &T,
+    repr: StructRepr,
+) -> JlrsResult<ValueData<'target, 'static, Tgt>>
+where
+    T: Serialize + ?Sized,
+    Tgt: Target<'target>,
+{
+    target.with_local_scope::<_, _, 2>(|target, mut frame| {
+        let mut roots = Roots::new(&mut frame)?;
+        let value = value.serialize(Serializer::new(&mut roots, repr))?;
+        Ok(value.root(target))
+    })
+}
+
+/// Convert Julia data to `T`.
+///
+/// Temporary Julia data may need to be allocated, which is rooted until this function returns.
+/// If an exception is thrown while the data is converted, it's converted to an error.
+pub fn from_value<T>(value: Value) -> JlrsResult<T>
+where
+    T: DeserializeOwned,
+{
+    value.unrooted_target().local_scope::<_, _, 2>(|mut frame| {
+        let mut roots = Roots::new(&mut frame)?;
+        T::deserialize(Deserializer::new(&mut roots, value))
+    })
+}
+
+// Roots data created while converting data. Newly created data is stored in a reusable slot
+// before it's pushed to a rooted `Vector{Any}`, which roots it until the conversion completes.
+pub(crate) struct Roots<'scope> {
+    stack: Array<'scope, 'static>,
+    slot: LocalReusableSlot<'scope>,
+}
+
+impl<'scope> Roots<'scope> {
+    fn new<const N: usize>(frame: &mut LocalGcFrame<'scope, N>) -> JlrsResult<Self> {
+        let any = DataType::any_type(&*frame).as_value();
+        let stack = Array::new_for(frame.local_output(), 0, any)
+            .map_err(|e| JlrsError::exception(e.error_string_or(CANNOT_DISPLAY_VALUE)))?;
+        let slot = frame.local_reusable_slot();
+        Ok(Roots { stack, slot })
+    }
+
+    #[inline]
+    fn unrooted(&self) -> Unrooted<'scope> {
+        self.stack.unrooted_target()
+    }
+
+    // Root some data until the conversion completes.
+    fn root<'data, T: Managed<'scope, 'data>>(&mut self, data: Ref<'scope, 'data, T>) -> T {
+        // Safety: the data is stored in the reusable slot while it's pushed to the stack, so it
+        // can't be freed if pushing triggers the GC.
+        unsafe {
+            let ptr = data.ptr();
+            let data = self.slot.temporary::<T>(ptr).as_managed();
+            jl_array_ptr_1d_push(self.stack.unwrap(Private), ptr.as_ptr().cast());
+            data
+        }
+    }
+
+    // Root the result of a function call, if an exception has been thrown it's converted to an
+    // error.
+    fn root_result<'data, T: Managed<'scope, 'data>>(
+        &mut self,
+        result: Result<Ref<'scope, 'data, T>, ValueRef<'scope, 'data>>,
+    ) -> JlrsResult<T> {
+        match result {
+            Ok(v) => Ok(self.root(v)),
+            Err(e) => {
+                let e = self.root(e);
+                Err(JlrsError::exception(
+                    e.error_string_or(CANNOT_DISPLAY_VALUE),
+                ))?
+            }
+        }
+    }
+}
+
+impl serde::ser::Error for Box<JlrsError> {
+    fn custom<T: Display>(msg: T) -> Self {
+        SerdeError::Custom {
+            msg: msg.to_string(),
+        }
+        .into()
+    }
+}
+
+impl serde::de::Error for Box<JlrsError> {
+    fn custom<T: Display>(msg: T) -> Self {
+        SerdeError::Custom {
+            msg: msg.to_string(),
+        }
+        .into()
+    }
+}
@@ -0,0 +1,545 @@
+// Serializer that converts Rust data to Julia data.
+
+use std::ptr::copy_nonoverlapping;
+
+use jl_sys::jl_arrayset;
+use serde::{
+    ser::{
+        SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+        SerializeTupleStruct, SerializeTupleVariant,
+    },
+    Serialize,
+};
+
+use super::{Roots, StructRepr};
+use crate::{
+    call::Call,
+    data::{
+        layout::tuple::Tuple,
+        managed::{
+            array::Array, datatype::DataType, function::Function, private::ManagedPriv,
+            string::JuliaString, symbol::Symbol, union_all::UnionAll, value::Value, Managed,
+        },
+    },
+    error::{JlrsError, JlrsResult},
+    inline_static_ref,
+    private::Private,
+};
+
+pub(super) struct Serializer<'a, 'scope> {
+    roots: &'a mut Roots<'scope>,
+    repr: StructRepr,
+}
+
+impl<'a, 'scope> Serializer<'a, 'scope> {
+    pub(super) fn new(roots: &'a mut Roots<'scope>, repr: StructRepr) -> Self {
+        Serializer { roots, repr }
+    }
+
+    fn new_value<T: crate::convert::into_julia::IntoJulia>(
+        self,
+        value: T,
+    ) -> JlrsResult<Value<'scope, 'static>> {
+        let unrooted = self.roots.unrooted();
+        let v = Value::new(unrooted, value);
+        Ok(self.roots.root(v))
+    }
+
+    fn symbol(&self, name: &str) -> Value<'scope, 'static> {
+        Symbol::new(&self.roots.unrooted(), name).as_value()
+    }
+
+    fn variant(
+        self,
+        variant: &'static str,
+        value: Value<'scope, 'static>,
+    ) -> JlrsResult<Value<'scope, 'static>> {
+        named_tuple(self.roots, &[variant], &[value])
+    }
+}
+
+// `Vector{Any}` that contains `values`.
+fn vector_any<'scope>(
+    roots: &mut Roots<'scope>,
+    values: &[Value<'scope, 'static>],
+) -> JlrsResult<Value<'scope, 'static>> {
+    let unrooted = roots.unrooted();
+    let any = DataType::any_type(&unrooted).as_value();
+    let arr = Array::new_for(unrooted, values.len(), any);
+    let arr = roots.root_result(arr)?.as_value();
+
+    // Safety: the array has been allocated with enough space for all values, which are rooted.
+    unsafe {
+        let arr_ptr = arr.unwrap(Private).cast();
+        for (i, value) in values.iter().enumerate() {
+            jl_arrayset(arr_ptr, value.unwrap(Private), i);
+        }
+    }
+
+    Ok(arr)
+}
+
+// `Vector` of the narrowest element type that contains all `values`.
+fn vector<'scope>(
+    roots: &mut Roots<'scope>,
+    values: &[Value<'scope, 'static>],
+) -> JlrsResult<Value<'scope, 'static>> {
+    let arr = vector_any(roots, values)?;
+    let unrooted = roots.unrooted();
+
+    // Safety: `map(identity, arr)` is safe to call.
+    unsafe {
+        let map = inline_static_ref!(MAP, Function, "Base.map", unrooted);
+        let identity = inline_static_ref!(IDENTITY, Function, "Base.identity", unrooted);
+        let res = map.call2(unrooted, identity.as_value(), arr);
+        roots.root_result(res)
+    }
+}
+
+fn tuple<'scope>(
+    roots: &mut Roots<'scope>,
+    values: &[Value<'scope, 'static>],
+) -> JlrsResult<Value<'scope, 'static>> {
+    let tuple = Tuple::new(roots.unrooted(), values);
+    roots.root_result(tuple)
+}
+
+fn named_tuple<'scope>(
+    roots: &mut Roots<'scope>,
+    names: &[&str],
+    values: &[Value<'scope, 'static>],
+) -> JlrsResult<Value<'scope, 'static>> {
+    let unrooted = roots.unrooted();
+    let names = names
+        .iter()
+        .map(|name| Symbol::new(&unrooted, name).as_value())
+        .collect::<Vec<_>>();
+    let names = tuple(roots, &names)?;
+
+    let field_types = values
+        .iter()
+        .map(|v| v.datatype().as_value())
+        .collect::<Vec<_>>();
+    let field_types = DataType::anytuple_type(&unrooted)
+        .as_value()
+        .apply_type(unrooted, &field_types);
+    let field_types = roots.root_result(field_types)?;
+
+    let ty = UnionAll::namedtuple_type(&unrooted)
+        .as_value()
+        .apply_type(unrooted, [names, field_types]);
+    let ty = roots.root_result(ty)?.cast::<DataType>()?;
+
+    let nt = ty.instantiate(unrooted, values)?;
+    roots.root_result(nt)
+}
+
+fn symbol_dict<'scope>(
+    roots: &mut Roots<'scope>,
+    names: &[&str],
+    values: &[Value<'scope, 'static>],
+) -> JlrsResult<Value<'scope, 'static>> {
+    let unrooted = roots.unrooted();
+
+    // Safety: the constructor and `setindex!` are safe to call.
+    unsafe {
+        let dict = inline_static_ref!(DICT, Value, "Base.Dict", unrooted);
+        let symbol = DataType::symbol_type(&unrooted).as_value();
+        let any = DataType::any_type(&unrooted).as_value();
+        let ty = dict.apply_type(unrooted, [symbol, any]);
+        let ty = roots.root_result(ty)?;
+
+        let dict = ty.call0(unrooted);
+        let dict = roots.root_result(dict)?;
+
+        let setindex = inline_static_ref!(SETINDEX, Function, "Base.setindex!", unrooted);
+        for (name, value) in names.iter().zip(values.iter().copied()) {
+            let key = Symbol::new(&unrooted, name).as_value();
+            let res = setindex.call3(unrooted, dict, value, key);
+            roots.root_result(res)?;
+        }
+
+        Ok(dict)
+    }
+}
+
+fn dict<'scope>(
+    roots: &mut Roots<'scope>,
+    keys: &[Value<'scope, 'static>],
+    values: &[Value<'scope, 'static>],
+) -> JlrsResult<Value<'scope, 'static>> {
+    let keys = vector_any(roots, keys)?;
+    let values = vector_any(roots, values)?;
+    let unrooted = roots.unrooted();
+
+    // Safety: `map(Pair, keys, values)` and `Dict(pairs)` are safe to call.
+    unsafe {
+        let map = inline_static_ref!(MAP, Function, "Base.map", unrooted);
+        let pair = inline_static_ref!(PAIR, Value, "Base.Pair", unrooted);
+        let pairs = map.call3(unrooted, pair, keys, values);
+        let pairs = roots.root_result(pairs)?;
+
+        let dict = inline_static_ref!(DICT, Value, "Base.Dict", unrooted);
+        let dict = dict.call1(unrooted, pairs);
+        roots.root_result(dict)
+    }
+}
+
+impl<'a, 'scope> serde::Serializer for Serializer<'a, 'scope> {
+    type Ok = Value<'scope, 'static>;
+    type Error = Box<JlrsError>;
+
+    type SerializeSeq = SerializeValues<'a, 'scope>;
+    type SerializeTuple = SerializeValues<'a, 'scope>;
+    type SerializeTupleStruct = SerializeValues<'a, 'scope>;
+    type SerializeTupleVariant = SerializeValues<'a, 'scope>;
+    type SerializeMap = SerializeDict<'a, 'scope>;
+    type SerializeStruct = SerializeFields<'a, 'scope>;
+    type SerializeStructVariant = SerializeFields<'a, 'scope>;
+
+    fn serialize_bool(self, v: bool) -> JlrsResult<Self::Ok> {
+        self.new_value(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> JlrsResult<Self::Ok> {
+        self.new_value(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> JlrsResult<Self::Ok> {
+        self.new_value(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> JlrsResult<Self::Ok> {
+        self.new_value(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> JlrsResult<Self::Ok> {
+        self.new_value(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> JlrsResult<Self::Ok> {
+        self.new_value(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> JlrsResult<Self::Ok> {
+        self.new_value(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> JlrsResult<Self::Ok> {
+        self.new_value(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> JlrsResult<Self::Ok> {
+        self.new_value(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> JlrsResult<Self::Ok> {
+        self.new_value(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> JlrsResult<Self::Ok> {
+        self.new_value(v)
+    }
+
+    fn serialize_char(self, v: char) -> JlrsResult<Self::Ok> {
+        self.new_value(v)
+    }
+
+    fn serialize_str(self, v: &str) -> JlrsResult<Self::Ok> {
+        let s = JuliaString::new(self.roots.unrooted(), v);
+        Ok(self.roots.root(s).as_value())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> JlrsResult<Self::Ok> {
+        let unrooted = self.roots.unrooted();
+        let arr = Array::new::<u8, _, _>(unrooted, v.len());
+        let arr = self.roots.root_result(arr)?;
+
+        // Safety: the array has been allocated with enough space for all bytes.
+        unsafe { copy_nonoverlapping(v.as_ptr(), arr.data_ptr().cast(), v.len()) }
+
+        Ok(arr.as_value())
+    }
+
+    fn serialize_none(self) -> JlrsResult<Self::Ok> {
+        Ok(Value::nothing(&self.roots.unrooted()))
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> JlrsResult<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> JlrsResult<Self::Ok> {
+        Ok(Value::nothing(&self.roots.unrooted()))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> JlrsResult<Self::Ok> {
+        Ok(Value::nothing(&self.roots.unrooted()))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> JlrsResult<Self::Ok> {
+        Ok(self.symbol(variant))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> JlrsResult<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> JlrsResult<Self::Ok> {
+        let repr = self.repr;
+        let value = value.serialize(Serializer::new(self.roots, repr))?;
+        self.variant(variant, value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> JlrsResult<Self::SerializeSeq> {
+        Ok(SerializeValues::new(self, len.unwrap_or(0), None))
+    }
+
+    fn serialize_tuple(self, len: usize) -> JlrsResult<Self::SerializeTuple> {
+        Ok(SerializeValues::new(self, len, None))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> JlrsResult<Self::SerializeTupleStruct> {
+        Ok(SerializeValues::new(self, len, None))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> JlrsResult<Self::SerializeTupleVariant> {
+        Ok(SerializeValues::new(self, len, Some(variant)))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> JlrsResult<Self::SerializeMap> {
+        let len = len.unwrap_or(0);
+        Ok(SerializeDict {
+            ser: self,
+            keys: Vec::with_capacity(len),
+            values: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> JlrsResult<Self::SerializeStruct> {
+        Ok(SerializeFields::new(self, len, None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> JlrsResult<Self::SerializeStructVariant> {
+        Ok(SerializeFields::new(self, len, Some(variant)))
+    }
+}
+
+pub(super) struct SerializeValues<'a, 'scope> {
+    ser: Serializer<'a, 'scope>,
+    values: Vec<Value<'scope, 'static>>,
+    variant: Option<&'static str>,
+}
+
+impl<'a, 'scope> SerializeValues<'a, 'scope> {
+    fn new(ser: Serializer<'a, 'scope>, len: usize, variant: Option<&'static str>) -> Self {
+        SerializeValues {
+            ser,
+            values: Vec::with_capacity(len),
+            variant,
+        }
+    }
+
+    fn push<T: ?Sized + Serialize>(&mut self, value: &T) -> JlrsResult<()> {
+        let repr = self.ser.repr;
+        let value = value.serialize(Serializer::new(self.ser.roots, repr))?;
+        self.values.push(value);
+        Ok(())
+    }
+
+    fn end_tuple(self) -> JlrsResult<Value<'scope, 'static>> {
+        let tuple = tuple(self.ser.roots, &self.values)?;
+        match self.variant {
+            Some(variant) => self.ser.variant(variant, tuple),
+            None => Ok(tuple),
+        }
+    }
+}
+
+impl<'a, 'scope> SerializeSeq for SerializeValues<'a, 'scope> {
+    type Ok = Value<'scope, 'static>;
+    type Error = Box<JlrsError>;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> JlrsResult<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> JlrsResult<Self::Ok> {
+        vector(self.ser.roots, &self.values)
+    }
+}
+
+impl<'a, 'scope> SerializeTuple for SerializeValues<'a, 'scope> {
+    type Ok = Value<'scope, 'static>;
+    type Error = Box<JlrsError>;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> JlrsResult<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> JlrsResult<Self::Ok> {
+        self.end_tuple()
+    }
+}
+
+impl<'a, 'scope> SerializeTupleStruct for SerializeValues<'a, 'scope> {
+    type Ok = Value<'scope, 'static>;
+    type Error = Box<JlrsError>;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> JlrsResult<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> JlrsResult<Self::Ok> {
+        self.end_tuple()
+    }
+}
+
+impl<'a, 'scope> SerializeTupleVariant for SerializeValues<'a, 'scope> {
+    type Ok = Value<'scope, 'static>;
+    type Error = Box<JlrsError>;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> JlrsResult<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> JlrsResult<Self::Ok> {
+        self.end_tuple()
+    }
+}
+
+pub(super) struct SerializeDict<'a, 'scope> {
+    ser: Serializer<'a, 'scope>,
+    keys: Vec<Value<'scope, 'static>>,
+    values: Vec<Value<'scope, 'static>>,
+}
+
+impl<'a, 'scope> SerializeMap for SerializeDict<'a, 'scope> {
+    type Ok = Value<'scope, 'static>;
+    type Error = Box<JlrsError>;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> JlrsResult<()> {
+        let repr = self.ser.repr;
+        let key = key.serialize(Serializer::new(self.ser.roots, repr))?;
+        self.keys.push(key);
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> JlrsResult<()> {
+        let repr = self.ser.repr;
+        let value = value.serialize(Serializer::new(self.ser.roots, repr))?;
+        self.values.push(value);
+        Ok(())
+    }
+
+    fn end(self) -> JlrsResult<Self::Ok> {
+        dict(self.ser.roots, &self.keys, &self.values)
+    }
+}
+
+pub(super) struct SerializeFields<'a, 'scope> {
+    ser: Serializer<'a, 'scope>,
+    names: Vec<&'static str>,
+    values: Vec<Value<'scope, 'static>>,
+    variant: Option<&'static str>,
+}
+
+impl<'a, 'scope> SerializeFields<'a, 'scope> {
+    fn new(ser: Serializer<'a, 'scope>, len: usize, variant: Option<&'static str>) -> Self {
+        SerializeFields {
+            ser,
+            names: Vec::with_capacity(len),
+            values: Vec::with_capacity(len),
+            variant,
+        }
+    }
+
+    fn push<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> JlrsResult<()> {
+        let repr = self.ser.repr;
+        let value = value.serialize(Serializer::new(self.ser.roots, repr))?;
+        self.names.push(key);
+        self.values.push(value);
+        Ok(())
+    }
+
+    fn finish(self) -> JlrsResult<Value<'scope, 'static>> {
+        let value = match self.ser.repr {
+            StructRepr::NamedTuple => named_tuple(self.ser.roots, &self.names, &self.values)?,
+            StructRepr::Dict => symbol_dict(self.ser.roots, &self.names, &self.values)?,
+        };
+
+        match self.variant {
+            Some(variant) => self.ser.variant(variant, value),
+            None => Ok(value),
+        }
+    }
+}
+
+impl<'a, 'scope> SerializeStruct for SerializeFields<'a, 'scope> {
+    type Ok = Value<'scope, 'static>;
+    type Error = Box<JlrsError>;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> JlrsResult<()> {
+        self.push(key, value)
+    }
+
+    fn end(self) -> JlrsResult<Self::Ok> {
+        self.finish()
+    }
+}
+
+impl<'a, 'scope> SerializeStructVariant for SerializeFields<'a, 'scope> {
+    type Ok = Value<'scope, 'static>;
+    type Error = Box<JlrsError>;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> JlrsResult<()> {
+        self.push(key, value)
+    }
+
+    fn end(self) -> JlrsResult<Self::Ok> {
+        self.finish()
+    }
+}
@@ -2,13 +2,15 @@
 
 use std::{
     marker::PhantomData,
-    ops::{Index, IndexMut},
+    ops::{Index, IndexMut, Range},
     ptr::{null_mut, NonNull},
     slice,
 };
 
 use jl_sys::{jl_array_ptr_set, jl_array_typetagdata, jl_arrayref, jl_arrayset};
 
+use super::iter::{ArraySubview, AxisIter, IndexedIter};
+
 use crate::{
     catch::catch_exceptions,
     data::{
@@ -435,6 +437,70 @@ where
     }
 }
 
+// Implements the methods that access the elements of an array with an inline layout through an
+// `ArraySubview`.
+macro_rules! impl_subview_methods {
+    ($accessor:ident) => {
+        impl<'borrow, 'array, 'data, T, M: Mutability> $accessor<'borrow, 'array, 'data, T, M> {
+            /// Returns an iterator over the elements and their n-dimensional index in column-major
+            /// order.
+            #[inline]
+            pub fn indexed_iter(&self) -> IndexedIter<'_, T> {
+                self.subview().indexed_iter()
+            }
+
+            /// Returns an iterator over the subviews along `axis`. Each subview has one dimension
+            /// less than the array.
+            ///
+            /// Returns `AccessError::InvalidAxis` if `axis` is not smaller than the rank of the
+            /// array.
+            #[inline]
+            pub fn axis_iter(&self, axis: usize) -> JlrsResult<AxisIter<'_, T>> {
+                self.subview().axis_iter(axis)
+            }
+
+            /// Returns the `j`th column of a two-dimensional array.
+            ///
+            /// Returns `ArrayLayoutError::RankMismatch` if the array is not two-dimensional, or
+            /// `AccessError::OutOfBoundsAxisIndex` if `j` is out of bounds. The elements of a
+            /// column are stored contiguously.
+            #[inline]
+            pub fn column(&self, j: usize) -> JlrsResult<ArraySubview<'_, T>> {
+                self.subview().column(j)
+            }
+
+            /// Returns the `i`th row of a two-dimensional array.
+            ///
+            /// Returns `ArrayLayoutError::RankMismatch` if the array is not two-dimensional, or
+            /// `AccessError::OutOfBoundsAxisIndex` if `i` is out of bounds.
+            #[inline]
+            pub fn row(&self, i: usize) -> JlrsResult<ArraySubview<'_, T>> {
+                self.subview().row(i)
+            }
+
+            /// Returns the subview that contains the elements in `ranges`, one range must be
+            /// provided for each dimension.
+            ///
+            /// Returns `ArrayLayoutError::RankMismatch` if the number of ranges is not equal to the
+            /// rank of the array, or `AccessError::OutOfBoundsRange` if a range is out of bounds.
+            #[inline]
+            pub fn get_range(&self, ranges: &[Range<usize>]) -> JlrsResult<ArraySubview<'_, T>> {
+                self.subview().get_range(ranges)
+            }
+
+            #[inline]
+            fn subview(&self) -> ArraySubview<'_, T> {
+                let dims = self.dimensions();
+                let dims = (0..dims.rank()).map(|dim| dims.n_elements(dim)).collect();
+                ArraySubview::new(self.as_slice(), dims)
+            }
+        }
+    };
+}
+
+impl_subview_methods!(BitsArrayAccessor);
+impl_subview_methods!(InlinePtrArrayAccessor);
+
 impl<'borrow, 'array, 'data, T, M: Mutability> BitsArrayAccessor<'borrow, 'array, 'data, T, M> {
     /// Get a reference to the value at `index`, or `None` if the index is out of bounds.
     #[inline]
@@ -665,7 +731,7 @@ impl<'borrow, 'array, 'data, M: Mutability> UnionArrayAccessor<'borrow, 'array,
 
             if let Some(ty) = nth_union_component(elty, &mut tag) {
                 if T::valid_field(ty) {
-                    let offset = idx * self.array.unwrap_non_null(Private).as_ref().elsize as usize;
+                    let offset = idx * self.array.element_size();
                     let ptr = self.array.data_ptr().cast::<i8>().add(offset).cast::<T>();
                     return Ok((&*ptr).clone());
                 }
@@ -712,7 +778,7 @@ impl<'borrow, 'array, 'data> UnionArrayAccessor<'borrow, 'array, 'data, Mutable<
         let idx = self.dimensions().index_of(&index)?;
         // Safety: The data can be stored in this array, the tag is updated accordingly.
         {
-            let offset = idx * self.array.unwrap_non_null(Private).as_ref().elsize as usize;
+            let offset = idx * self.array.element_size();
             self.array
                 .data_ptr()
                 .cast::<i8>()
@@ -0,0 +1,433 @@
+//! Iterate over the contents of Julia arrays and access parts of them.
+//!
+//! The accessors for arrays with inline elements, [`BitsArrayAccessor`] and
+//! [`InlinePtrArrayAccessor`], can iterate over their elements together with their
+//! n-dimensional index with `indexed_iter`, and split the array into subviews along an axis
+//! with `axis_iter`. Single columns and rows of a matrix can be accessed with `column` and `row`,
+//! and a block of elements with `get_range`.
+//!
+//! Julia arrays are column-major: all iterators visit the elements in column-major order, i.e.
+//! the first index changes fastest. Indices start at 0.
+//!
+//! [`BitsArrayAccessor`]: crate::data::managed::array::data::accessor::BitsArrayAccessor
+//! [`InlinePtrArrayAccessor`]: crate::data::managed::array::data::accessor::InlinePtrArrayAccessor
+
+use std::{
+    iter::FusedIterator,
+    ops::{Index, Range},
+};
+
+use crate::{
+    data::managed::array::dimensions::{Dimensions, Dims},
+    error::{AccessError, ArrayLayoutError, JlrsResult},
+};
+
+/// A view of part of the elements of an array accessor.
+///
+/// A subview has its own dimensions, the elements of a subview are not necessarily stored
+/// contiguously.
+#[derive(Debug)]
+pub struct ArraySubview<'borrow, T> {
+    data: &'borrow [T],
+    offset: usize,
+    dims: Box<[usize]>,
+    strides: Box<[usize]>,
+}
+
+impl<'borrow, T> ArraySubview<'borrow, T> {
+    // Create a view of all elements of an array with dimensions `dims`.
+    pub(crate) fn new(data: &'borrow [T], dims: Box<[usize]>) -> Self {
+        let mut strides = Vec::with_capacity(dims.len());
+        let mut stride = 1;
+        for &dim in dims.iter() {
+            strides.push(stride);
+            stride *= dim;
+        }
+
+        ArraySubview {
+            data,
+            offset: 0,
+            dims,
+            strides: strides.into_boxed_slice(),
+        }
+    }
+
+    /// Returns the dimensions of this subview.
+    #[inline]
+    pub fn dimensions(&self) -> Dimensions {
+        Dimensions::from_dims(&self.dims.as_ref())
+    }
+
+    /// Returns the strides of this subview in number of elements.
+    #[inline]
+    pub fn strides(&self) -> &[usize] {
+        &self.strides
+    }
+
+    /// Returns the number of elements in this subview.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.dims.iter().product()
+    }
+
+    /// Returns `true` if this subview has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a reference to the element at `index`, or `None` if the index is out of bounds.
+    pub fn get<D: Dims>(&self, index: D) -> Option<&'borrow T> {
+        let rank = self.dims.len();
+        if index.rank() != rank {
+            return None;
+        }
+
+        let mut offset = self.offset;
+        for dim in 0..rank {
+            let idx = index.n_elements(dim);
+            if idx >= self.dims[dim] {
+                return None;
+            }
+
+            offset += idx * self.strides[dim];
+        }
+
+        self.data.get(offset)
+    }
+
+    /// Returns the elements as a slice if they're stored contiguously in column-major order.
+    pub fn as_slice(&self) -> Option<&'borrow [T]> {
+        let mut stride = 1;
+        for (&dim, &s) in self.dims.iter().zip(self.strides.iter()) {
+            if dim > 1 && s != stride {
+                return None;
+            }
+
+            stride *= dim;
+        }
+
+        self.data.get(self.offset..self.offset + self.len())
+    }
+
+    /// Copy the elements to a `Vec` in column-major order.
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+
+    /// Returns an iterator over the elements in column-major order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'borrow, T> {
+        Iter {
+            indexed: self.indexed_iter(),
+        }
+    }
+
+    /// Returns an iterator over the elements and their n-dimensional index in column-major
+    /// order.
+    #[inline]
+    pub fn indexed_iter(&self) -> IndexedIter<'borrow, T> {
+        IndexedIter {
+            view: self.clone(),
+            index: vec![0; self.dims.len()].into_boxed_slice(),
+            remaining: self.len(),
+        }
+    }
+
+    /// Returns an iterator over the subviews along `axis`. Each subview has one dimension less
+    /// than this subview.
+    ///
+    /// Returns `AccessError::InvalidAxis` if `axis` is not smaller than the rank.
+    pub fn axis_iter(&self, axis: usize) -> JlrsResult<AxisIter<'borrow, T>> {
+        let rank = self.dims.len();
+        if axis >= rank {
+            Err(AccessError::InvalidAxis { axis, rank })?
+        }
+
+        Ok(AxisIter {
+            view: self.clone(),
+            axis,
+            index: 0,
+        })
+    }
+
+    /// Returns the subview at position `index` along `axis`, this subview has one dimension less
+    /// than this subview.
+    ///
+    /// Returns `AccessError::InvalidAxis` if `axis` is not smaller than the rank, or
+    /// `AccessError::OutOfBoundsAxisIndex` if `index` is out of bounds.
+    pub fn index_axis(&self, axis: usize, index: usize) -> JlrsResult<ArraySubview<'borrow, T>> {
+        let rank = self.dims.len();
+        if axis >= rank {
+            Err(AccessError::InvalidAxis { axis, rank })?
+        }
+
+        let len = self.dims[axis];
+        if index >= len {
+            Err(AccessError::OutOfBoundsAxisIndex {
+                idx: index,
+                axis,
+                len,
+            })?
+        }
+
+        Ok(self.index_axis_unchecked(axis, index))
+    }
+
+    /// Returns the subview that contains the elements in `ranges`, one range must be provided
+    /// for each dimension.
+    ///
+    /// Returns `ArrayLayoutError::RankMismatch` if the number of ranges is not equal to the rank,
+    /// or `AccessError::OutOfBoundsRange` if a range is out of bounds.
+    pub fn get_range(&self, ranges: &[Range<usize>]) -> JlrsResult<ArraySubview<'borrow, T>> {
+        let rank = self.dims.len();
+        if ranges.len() != rank {
+            Err(ArrayLayoutError::RankMismatch {
+                found: rank as isize,
+                provided: ranges.len() as isize,
+            })?
+        }
+
+        let mut offset = self.offset;
+        let mut dims = Vec::with_capacity(rank);
+        for (axis, range) in ranges.iter().enumerate() {
+            let len = self.dims[axis];
+            if range.start > range.end || range.end > len {
+                Err(AccessError::OutOfBoundsRange {
+                    start: range.start,
+                    end: range.end,
+                    axis,
+                    len,
+                })?
+            }
+
+            if range.start < range.end {
+                offset += range.start * self.strides[axis];
+            }
+
+            dims.push(range.end - range.start);
+        }
+
+        Ok(ArraySubview {
+            data: self.data,
+            offset,
+            dims: dims.into_boxed_slice(),
+            strides: self.strides.clone(),
+        })
+    }
+
+    // Returns the column `j` of a matrix.
+    pub(crate) fn column(&self, j: usize) -> JlrsResult<ArraySubview<'borrow, T>> {
+        self.check_matrix()?;
+        self.index_axis(1, j)
+    }
+
+    // Returns the row `i` of a matrix.
+    pub(crate) fn row(&self, i: usize) -> JlrsResult<ArraySubview<'borrow, T>> {
+        self.check_matrix()?;
+        self.index_axis(0, i)
+    }
+
+    fn check_matrix(&self) -> JlrsResult<()> {
+        if self.dims.len() != 2 {
+            Err(ArrayLayoutError::RankMismatch {
+                found: self.dims.len() as isize,
+                provided: 2,
+            })?
+        }
+
+        Ok(())
+    }
+
+    fn index_axis_unchecked(&self, axis: usize, index: usize) -> ArraySubview<'borrow, T> {
+        let mut dims = self.dims.to_vec();
+        let mut strides = self.strides.to_vec();
+        let offset = self.offset + index * strides[axis];
+        dims.remove(axis);
+        strides.remove(axis);
+
+        ArraySubview {
+            data: self.data,
+            offset,
+            dims: dims.into_boxed_slice(),
+            strides: strides.into_boxed_slice(),
+        }
+    }
+}
+
+impl<T> Clone for ArraySubview<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ArraySubview {
+            data: self.data,
+            offset: self.offset,
+            dims: self.dims.clone(),
+            strides: self.strides.clone(),
+        }
+    }
+}
+
+impl<T, D: Dims> Index<D> for ArraySubview<'_, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: D) -> &Self::Output {
+        let dims = index.into_dimensions();
+        match self.get(index) {
+            Some(elem) => elem,
+            None => panic!(
+                "index {} is out of bounds for subview with shape {}",
+                dims,
+                self.dimensions()
+            ),
+        }
+    }
+}
+
+/// Iterator over the elements of an array or subview in column-major order.
+#[derive(Debug)]
+pub struct Iter<'borrow, T> {
+    indexed: IndexedIter<'borrow, T>,
+}
+
+impl<T> Clone for Iter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Iter {
+            indexed: self.indexed.clone(),
+        }
+    }
+}
+
+impl<'borrow, T> Iterator for Iter<'borrow, T> {
+    type Item = &'borrow T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.indexed.next_offset()?;
+        self.indexed.view.data.get(offset)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indexed.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// Iterator over the elements of an array or subview and their n-dimensional index in
+/// column-major order.
+#[derive(Debug)]
+pub struct IndexedIter<'borrow, T> {
+    view: ArraySubview<'borrow, T>,
+    index: Box<[usize]>,
+    remaining: usize,
+}
+
+impl<T> Clone for IndexedIter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        IndexedIter {
+            view: self.view.clone(),
+            index: self.index.clone(),
+            remaining: self.remaining,
+        }
+    }
+}
+
+impl<T> IndexedIter<'_, T> {
+    // Returns the offset of the current element and advances the index.
+    fn next_offset(&mut self) -> Option<usize> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let offset = self
+            .index
+            .iter()
+            .zip(self.view.strides.iter())
+            .fold(self.view.offset, |acc, (idx, stride)| acc + idx * stride);
+
+        self.remaining -= 1;
+        for (idx, &dim) in self.index.iter_mut().zip(self.view.dims.iter()) {
+            *idx += 1;
+            if *idx < dim {
+                break;
+            }
+
+            *idx = 0;
+        }
+
+        Some(offset)
+    }
+}
+
+impl<'borrow, T> Iterator for IndexedIter<'borrow, T> {
+    type Item = (Dimensions, &'borrow T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = Dimensions::from_dims(&self.index.as_ref());
+        let offset = self.next_offset()?;
+        Some((index, self.view.data.get(offset)?))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for IndexedIter<'_, T> {}
+
+impl<T> FusedIterator for IndexedIter<'_, T> {}
+
+/// Iterator over the subviews of an array or subview along an axis.
+#[derive(Debug)]
+pub struct AxisIter<'borrow, T> {
+    view: ArraySubview<'borrow, T>,
+    axis: usize,
+    index: usize,
+}
+
+impl<T> Clone for AxisIter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        AxisIter {
+            view: self.view.clone(),
+            axis: self.axis,
+            index: self.index,
+        }
+    }
+}
+
+impl<'borrow, T> Iterator for AxisIter<'borrow, T> {
+    type Item = ArraySubview<'borrow, T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.view.dims[self.axis] {
+            return None;
+        }
+
+        let subview = self.view.index_axis_unchecked(self.axis, self.index);
+        self.index += 1;
+        Some(subview)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.view.dims[self.axis] - self.index;
+        (n, Some(n))
+    }
+}
+
+impl<T> ExactSizeIterator for AxisIter<'_, T> {}
+
+impl<T> FusedIterator for AxisIter<'_, T> {}
@@ -6,3 +6,4 @@
 
 pub mod accessor;
 pub mod copied;
+pub mod iter;
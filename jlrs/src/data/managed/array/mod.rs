@@ -27,14 +27,19 @@ use std::{
     mem,
     mem::MaybeUninit,
     ptr::{null_mut, NonNull},
-    slice,
 };
 
 use jl_sys::{
-    jl_apply_array_type, jl_array_data, jl_array_del_beg, jl_array_del_end, jl_array_dims_ptr,
-    jl_array_eltype, jl_array_grow_beg, jl_array_grow_end, jl_array_ndims, jl_array_t,
-    jl_gc_add_ptr_finalizer, jl_new_struct_uninit, jl_pchar_to_array, jl_reshape_array,
+    jl_apply_array_type, jl_array_data, jl_array_del_beg, jl_array_del_end, jl_array_eltype,
+    jl_array_grow_beg, jl_array_grow_end, jl_array_t, jl_gc_add_ptr_finalizer,
+    jl_new_struct_uninit, jl_pchar_to_array, jl_reshape_array,
 };
+#[cfg(not(feature = "julia-1-11"))]
+use jl_sys::{jl_array_dims_ptr, jl_array_ndims};
+#[cfg(feature = "julia-1-11")]
+use jl_sys::{jl_genericmemory_elsize, jl_genericmemory_isboxed, jl_genericmemory_t};
+#[cfg(feature = "jlrs-ndarray")]
+use jl_sys::{jl_ptr_to_array, jlrs_dimtuple_type};
 
 use self::{
     data::accessor::{
@@ -46,11 +51,15 @@ use self::{
     dimensions::DimsExt,
     tracked::{TrackedArray, TrackedArrayMut},
 };
+#[cfg(feature = "julia-1-11")]
+use super::memory::{GenericMemoryRef, Memory};
 use super::{
     union_all::UnionAll,
     value::{typed::TypedValue, ValueRef},
     Ref,
 };
+#[cfg(feature = "jlrs-ndarray")]
+use crate::convert::ndarray::ndarray_into_vec;
 use crate::{
     catch::catch_exceptions,
     convert::{
@@ -94,6 +103,7 @@ use crate::{
 
 pub mod data;
 pub mod dimensions;
+pub mod strided;
 pub mod tracked;
 
 /// An n-dimensional Julia array.
@@ -349,12 +359,7 @@ impl<'data> Array<'_, 'data> {
                     .alloc_array_with_data(&target, array_type, data.as_mut_ptr().cast())
                     .ptr();
 
-                jl_gc_add_ptr_finalizer(
-                    get_tls(),
-                    array.as_ptr().cast(),
-                    droparray::<T> as *mut c_void,
-                );
-
+                add_drop_finalizer::<T>(array);
                 array
             };
 
@@ -400,14 +405,67 @@ impl<'data> Array<'_, 'data> {
             .alloc_array_with_data(&target, array_type, data.as_mut_ptr().cast())
             .ptr();
 
-        jl_gc_add_ptr_finalizer(
-            get_tls(),
-            array.as_ptr().cast(),
-            droparray::<T> as *mut c_void,
-        );
+        add_drop_finalizer::<T>(array);
         Ok(target.data_from_ptr(array, Private))
     }
 
+    /// Create a new n-dimensional Julia array that takes ownership of the data of an owned
+    /// ndarray without copying it.
+    ///
+    /// Julia arrays are column-major. If the ndarray is stored in Fortran order the Julia array
+    /// has the same shape, if it's stored in standard (row-major) order the shape is reversed
+    /// and Julia sees the transpose of that ndarray. Arrays which are not contiguous in either
+    /// order, or which are a slice of their data, can't be moved and an error is returned. If the
+    /// buffer of the ndarray has excess capacity it's shrunk first, which copies the data.
+    ///
+    /// This method can only be used in combination with types that implement `IntoJulia`. Because
+    /// the data is allocated by Rust, operations that can change the size of the array (e.g.
+    /// `push!`) will fail.
+    ///
+    /// If the array size is too large, Julia will throw an error. This error is caught and
+    /// returned.
+    #[cfg(feature = "jlrs-ndarray")]
+    pub fn from_ndarray<'target, T, D, Tgt>(
+        target: Tgt,
+        data: ndarray::Array<T, D>,
+    ) -> JlrsResult<ArrayResult<'target, 'static, Tgt>>
+    where
+        T: IntoJulia + ConstructType,
+        D: ndarray::Dimension,
+        Tgt: Target<'target>,
+    {
+        let (mut data, dims) = ndarray_into_vec(data)?;
+
+        // The data is freed as a boxed slice, so any excess capacity must be released first.
+        // This reallocates, and copies, the data if the buffer has excess capacity.
+        data.shrink_to_fit();
+        let data = Box::leak(data.into_boxed_slice());
+
+        // Safety: The array type is rooted until the array has been constructed, all C API
+        // functions are called with valid data.
+        unsafe {
+            let callback = || {
+                let elty = T::construct_type(&target).as_value();
+                let array_type = jl_apply_array_type(elty.unwrap(Private), dims.len());
+                let array = target
+                    .local_scope::<_, _, 1>(|mut frame| {
+                        let tuple = unsized_dim_tuple(&mut frame, &dims);
+                        let data = data.as_mut_ptr().cast();
+                        Ok(jl_ptr_to_array(array_type, data, tuple.unwrap(Private), 0))
+                    })
+                    .unwrap_unchecked();
+
+                let array = NonNull::new_unchecked(array);
+                add_drop_finalizer::<T>(array);
+                array
+            };
+
+            let exc = |err: Value| err.unwrap_non_null(Private);
+            let res = catch_exceptions(callback, exc);
+            Ok(target.result_from_ptr(res, Private))
+        }
+    }
+
     /// Convert a string to a Julia array.
     #[inline]
     pub fn from_string<'target, A, Tgt>(target: Tgt, data: A) -> ArrayData<'target, 'static, Tgt>
@@ -428,7 +486,7 @@ impl<'data> Array<'_, 'data> {
     #[inline]
     pub(crate) fn data_ptr(self) -> *mut c_void {
         // Safety: the pointer points to valid data.
-        unsafe { self.unwrap_non_null(Private).as_ref().data }
+        unsafe { jl_array_data(self.unwrap(Private).cast()) }
     }
 }
 
@@ -456,7 +514,39 @@ impl<'scope, 'data> Array<'scope, 'data> {
     #[inline]
     pub fn element_size(self) -> usize {
         // Safety: the pointer points to valid data.
-        unsafe { self.unwrap_non_null(Private).as_ref().elsize as usize }
+        #[cfg(not(feature = "julia-1-11"))]
+        unsafe {
+            self.unwrap_non_null(Private).as_ref().elsize as usize
+        }
+
+        // Safety: the pointer points to valid data.
+        #[cfg(feature = "julia-1-11")]
+        unsafe {
+            jl_genericmemory_elsize(self.unwrap_non_null(Private).as_ref().ref_.mem)
+        }
+    }
+
+    /// Returns the buffer that backs this array.
+    ///
+    /// This method is only available if the `julia-1-11` feature is enabled.
+    #[cfg(feature = "julia-1-11")]
+    #[inline]
+    pub fn memory(self) -> Memory<'scope, 'data> {
+        // Safety: the pointer points to valid data, the buffer is reachable from the array.
+        unsafe {
+            let mem = self.unwrap_non_null(Private).as_ref().ref_.mem;
+            Memory::wrap_non_null(NonNull::new_unchecked(mem), Private)
+        }
+    }
+
+    /// Returns a reference to the first element of this array in its backing buffer.
+    ///
+    /// This method is only available if the `julia-1-11` feature is enabled.
+    #[cfg(feature = "julia-1-11")]
+    #[inline]
+    pub fn memory_ref(self) -> GenericMemoryRef<'scope, 'data> {
+        // Safety: the pointer points to valid data, the buffer is reachable from the array.
+        unsafe { GenericMemoryRef::from_raw(self.unwrap_non_null(Private).as_ref().ref_) }
     }
 
     /// Returns `true` if the layout of the elements is compatible with `T`.
@@ -477,7 +567,16 @@ impl<'scope, 'data> Array<'scope, 'data> {
     #[inline]
     pub fn is_inline_array(self) -> bool {
         // Safety: the pointer points to valid data.
-        unsafe { self.unwrap_non_null(Private).as_ref().flags.ptrarray() == 0 }
+        #[cfg(not(feature = "julia-1-11"))]
+        unsafe {
+            self.unwrap_non_null(Private).as_ref().flags.ptrarray() == 0
+        }
+
+        // Safety: the pointer points to valid data.
+        #[cfg(feature = "julia-1-11")]
+        unsafe {
+            !jl_genericmemory_isboxed(self.unwrap_non_null(Private).as_ref().ref_.mem)
+        }
     }
 
     /// Returns `true` if the elements of the array are stored inline and the element type is a
@@ -492,28 +591,37 @@ impl<'scope, 'data> Array<'scope, 'data> {
     #[inline]
     pub fn has_inlined_pointers(self) -> bool {
         // Safety: the pointer points to valid data.
+        #[cfg(not(feature = "julia-1-11"))]
         unsafe {
             let flags = self.unwrap_non_null(Private).as_ref().flags;
             self.is_inline_array() && flags.hasptr() != 0
         }
+
+        // The flags of the array have been removed in Julia 1.11, bits unions never contain
+        // pointers so only the layout of inline non-union elements has to be checked.
+        #[cfg(feature = "julia-1-11")]
+        {
+            self.is_inline_array()
+                && self
+                    .element_type()
+                    .cast::<DataType>()
+                    .and_then(|dt| dt.has_pointer_fields())
+                    .unwrap_or(false)
+        }
     }
 
     /// Returns `true` if elements of this array are zero-initialized.
     #[inline]
     pub fn zero_init(self) -> bool {
-        // Safety: the pointer points to valid data.
-        unsafe {
-            let flags = self.unwrap_non_null(Private).as_ref().flags;
-            if flags.ptrarray() == 1 || flags.hasptr() == 1 {
-                return true;
-            }
+        if !self.is_inline_array() || self.has_inlined_pointers() {
+            return true;
+        }
 
-            let elty = self.element_type();
-            if let Ok(dt) = elty.cast::<DataType>() {
-                return dt.zero_init();
-            } else {
-                false
-            }
+        let elty = self.element_type();
+        if let Ok(dt) = elty.cast::<DataType>() {
+            dt.zero_init()
+        } else {
+            false
         }
     }
 
@@ -1509,6 +1617,44 @@ where
 
         Ok(target.data_from_ptr(res.unwrap_non_null(Private), Private))
     }
+
+    /// Create a new n-dimensional Julia array that takes ownership of the data of an owned
+    /// ndarray without copying it.
+    ///
+    /// Julia arrays are column-major. If the ndarray is stored in Fortran order the Julia array
+    /// has the same shape, if it's stored in standard (row-major) order the shape is reversed
+    /// and Julia sees the transpose of that ndarray. Arrays which are not contiguous in either
+    /// order, or which are a slice of their data, can't be moved and an error is returned. If the
+    /// buffer of the ndarray has excess capacity it's shrunk first, which copies the data.
+    ///
+    /// This method can only be used in combination with types that implement `IntoJulia`. Because
+    /// the data is allocated by Rust, operations that can change the size of the array (e.g.
+    /// `push!`) will fail.
+    ///
+    /// If the array size is too large, Julia will throw an error. This error is caught and
+    /// returned.
+    #[cfg(feature = "jlrs-ndarray")]
+    pub fn from_ndarray<'target, D, Tgt>(
+        target: Tgt,
+        data: ndarray::Array<T, D>,
+    ) -> JlrsResult<TypedArrayResult<'target, 'static, Tgt, T>>
+    where
+        T: IntoJulia + ConstructType,
+        D: ndarray::Dimension,
+        Tgt: Target<'target>,
+    {
+        unsafe {
+            let res = match Array::from_ndarray::<T, _, _>(&target, data)? {
+                Ok(arr) => Ok(arr
+                    .as_managed()
+                    .as_typed_unchecked::<T>()
+                    .unwrap_non_null(Private)),
+                Err(e) => Err(e.as_managed().unwrap_non_null(Private)),
+            };
+
+            Ok(target.result_from_ptr(res, Private))
+        }
+    }
 }
 
 impl<'data, T> TypedArray<'_, 'data, T>
@@ -2121,7 +2267,7 @@ unsafe impl<T> Send for AssumeThreadsafe<T> {}
 unsafe impl<T> Sync for AssumeThreadsafe<T> {}
 
 #[inline]
-pub(self) fn sized_dim_tuple<'target, D, Tgt>(
+pub(crate) fn sized_dim_tuple<'target, D, Tgt>(
     target: Tgt,
     dims: &D,
 ) -> ValueData<'target, 'static, Tgt>
@@ -2143,8 +2289,37 @@ where
     }
 }
 
+// Create the dimensions tuple of an array whose rank is not known at compile time.
+#[cfg(feature = "jlrs-ndarray")]
+#[inline]
+fn unsized_dim_tuple<'target, Tgt>(target: Tgt, dims: &[usize]) -> ValueData<'target, 'static, Tgt>
+where
+    Tgt: Target<'target>,
+{
+    unsafe {
+        let dims_type = jlrs_dimtuple_type(dims.len());
+        let tuple = jl_new_struct_uninit(dims_type);
+        std::ptr::copy_nonoverlapping(dims.as_ptr(), tuple.cast(), dims.len());
+        Value::wrap_non_null(NonNull::new_unchecked(tuple), Private).root(target)
+    }
+}
+
+// Register a finalizer that drops the data of an array that has been moved from Rust to Julia.
+#[inline]
+unsafe fn add_drop_finalizer<T>(array: NonNull<jl_array_t>) {
+    #[cfg(not(feature = "julia-1-11"))]
+    let owner = array.as_ptr().cast();
+
+    // Since Julia 1.11 the data is owned by the backing memory, which can outlive the array.
+    #[cfg(feature = "julia-1-11")]
+    let owner = array.as_ref().ref_.mem.cast();
+
+    jl_gc_add_ptr_finalizer(get_tls(), owner, droparray::<T> as *mut c_void);
+}
+
 // Safety: must be used as a finalizer when moving array data from Rust to Julia
 // to ensure it's freed correctly.
+#[cfg(not(feature = "julia-1-11"))]
 unsafe extern "C" fn droparray<T>(a: Array) {
     // The data of a moved array is allocated by Rust, this function is called by
     // a finalizer in order to ensure it's also freed by Rust.
@@ -2163,7 +2338,7 @@ unsafe extern "C" fn droparray<T>(a: Array) {
     let arr_ptr = arr_nn_ptr.as_ptr();
     let dims_ptr = jl_array_dims_ptr(arr_ptr);
     let n_dims = jl_array_ndims(arr_ptr);
-    for dim in slice::from_raw_parts_mut(dims_ptr, n_dims as _) {
+    for dim in std::slice::from_raw_parts_mut(dims_ptr, n_dims as _) {
         *dim = 0;
     }
 
@@ -2172,6 +2347,24 @@ unsafe extern "C" fn droparray<T>(a: Array) {
     mem::drop(data);
 }
 
+// Safety: must be used as a finalizer when moving array data from Rust to Julia
+// to ensure it's freed correctly.
+#[cfg(feature = "julia-1-11")]
+unsafe extern "C" fn droparray<T>(mut m: NonNull<jl_genericmemory_t>) {
+    // The data of a moved array is allocated by Rust, this function is called by
+    // a finalizer of the memory that backs the array in order to ensure it's also
+    // freed by Rust.
+    let mem = m.as_mut();
+    let data_ptr = mem.ptr.cast::<T>();
+    let len = mem.length;
+    mem.ptr = null_mut();
+    mem.length = 0;
+
+    // Drop the data
+    let data = Vec::from_raw_parts(data_ptr, len, len);
+    mem::drop(data);
+}
+
 /// A reference to a [`Array`] that has not been explicitly rooted.
 pub type ArrayRef<'scope, 'data> = Ref<'scope, 'data, Array<'scope, 'data>>;
 
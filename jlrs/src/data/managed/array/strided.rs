@@ -0,0 +1,538 @@
+//! Managed types for strided views of arrays.
+//!
+//! Many functions in Julia return a view of an array rather than a new array, e.g. `view(A, :,
+//! 2:5)` returns a `SubArray`, `PermutedDimsArray(A, (2, 1))` a `PermutedDimsArray`, and
+//! `reshape(view(A, 1:4), 2, 2)` a `ReshapedArray`. These views are not instances of `Array`,
+//! but if their elements are bits types and their parent is strided, their data can be accessed
+//! directly with a [`StridedArrayAccessor`].
+//!
+//! Example:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::util::test::JULIA;
+//! use jlrs::data::managed::array::strided::SubArray;
+//!
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! # let mut frame = StackFrame::new();
+//! # let mut julia = julia.instance(&mut frame);
+//! julia
+//!     .scope(|mut frame| unsafe {
+//!         let view = Value::eval_string(&mut frame, "view(reshape(collect(1:12), 3, 4), 2, :)")
+//!             .into_jlrs_result()?
+//!             .cast::<SubArray>()?;
+//!
+//!         assert_eq!(view.strides()?, vec![3]);
+//!         let data = view.bits_data::<i64>()?;
+//!         assert_eq!(data[3], 11);
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # });
+//! # }
+//! ```
+
+use std::{
+    ffi::c_void,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+    ptr::NonNull,
+};
+
+use jl_sys::jl_value_t;
+
+use super::{
+    data::accessor::{Immutable, Mutability, Mutable},
+    dimensions::{Dimensions, Dims},
+};
+use crate::{
+    call::Call,
+    convert::into_jlrs_result::IntoJlrsResult,
+    data::{
+        layout::valid_layout::{ValidField, ValidLayout},
+        managed::{
+            datatype::DataType,
+            function::Function,
+            private::ManagedPriv,
+            union_all::UnionAll,
+            value::{Value, ValueData},
+            Managed, Ref,
+        },
+        types::{construct_type::ConstructType, typecheck::Typecheck},
+    },
+    error::{AccessError, ArrayLayoutError, JlrsResult, CANNOT_DISPLAY_TYPE},
+    inline_static_ref,
+    memory::target::{unrooted::Unrooted, Target, TargetResult, TargetType},
+    private::Private,
+};
+
+macro_rules! impl_strided_array {
+    ($(#[$meta:meta])* $name:ident, $ref:ident, $ret:ident, $data:ident, $result:ident,
+     $path:literal) => {
+        $(#[$meta])*
+        #[derive(Copy, Clone)]
+        #[repr(transparent)]
+        pub struct $name<'scope, 'data>(
+            NonNull<jl_value_t>,
+            PhantomData<&'scope ()>,
+            PhantomData<&'data mut ()>,
+        );
+
+        impl<'scope, 'data> $name<'scope, 'data> {
+            /// Returns the array this is a view of.
+            #[inline]
+            pub fn parent(self) -> Value<'scope, 'data> {
+                // Safety: the parent is reachable from the view.
+                unsafe {
+                    self.as_value()
+                        .get_field_ref("parent")
+                        .expect("no parent field")
+                        .expect("parent is undefined")
+                        .as_value()
+                }
+            }
+
+            /// Returns the type of the elements.
+            #[inline]
+            pub fn element_type(self) -> Value<'scope, 'static> {
+                element_type(self.as_value())
+            }
+
+            /// Returns the dimensions of this view.
+            ///
+            /// If an exception is thrown, it's converted to an error.
+            #[inline]
+            pub fn dimensions(self) -> JlrsResult<Dimensions> {
+                dimensions(self.as_value())
+            }
+
+            /// Returns the strides of this view in number of elements.
+            ///
+            /// If an exception is thrown, e.g. because the view is not strided, it's converted to
+            /// an error.
+            #[inline]
+            pub fn strides(self) -> JlrsResult<Vec<isize>> {
+                strides(self.as_value())
+            }
+
+            /// Immutably access the contents of this view. The elements must be stored inline
+            /// and mustn't contain any references to Julia data.
+            ///
+            /// Returns `ArrayLayoutError::NotBits` if the elements are not a bits type,
+            /// `AccessError::InvalidLayout` if `T` is not compatible with the element type, or an
+            /// error if an exception is thrown while the strides are computed.
+            ///
+            /// Safety: It's not checked if the parent has already been mutably borrowed.
+            #[inline]
+            pub unsafe fn bits_data<'borrow, T>(
+                &'borrow self,
+            ) -> JlrsResult<StridedArrayAccessor<'borrow, 'data, T, Immutable<'borrow, T>>>
+            where
+                T: ValidField,
+            {
+                StridedArrayAccessor::new(self.as_value())
+            }
+
+            /// Mutably access the contents of this view. The elements must be stored inline
+            /// and mustn't contain any references to Julia data.
+            ///
+            /// Returns `ArrayLayoutError::NotBits` if the elements are not a bits type,
+            /// `AccessError::InvalidLayout` if `T` is not compatible with the element type, or an
+            /// error if an exception is thrown while the strides are computed.
+            ///
+            /// Safety: It's not checked if the parent has already been borrowed. Mutating Julia
+            /// data is generally unsafe because it can't be guaranteed mutating this value is
+            /// allowed.
+            #[inline]
+            pub unsafe fn bits_data_mut<'borrow, T>(
+                &'borrow mut self,
+            ) -> JlrsResult<StridedArrayAccessor<'borrow, 'data, T, Mutable<'borrow, T>>>
+            where
+                T: ValidField,
+            {
+                StridedArrayAccessor::new(self.as_value())
+            }
+        }
+
+        impl_debug!($name<'_, '_>);
+
+        impl<'scope, 'data> ManagedPriv<'scope, 'data> for $name<'scope, 'data> {
+            type Wraps = jl_value_t;
+            type TypeConstructorPriv<'target, 'da> = $name<'target, 'da>;
+            const NAME: &'static str = stringify!($name);
+
+            // Safety: `inner` must not have been freed yet, the result must never be
+            // used after the GC might have freed it.
+            #[inline]
+            unsafe fn wrap_non_null(inner: NonNull<Self::Wraps>, _: Private) -> Self {
+                Self(inner, PhantomData, PhantomData)
+            }
+
+            #[inline]
+            fn unwrap_non_null(self, _: Private) -> NonNull<Self::Wraps> {
+                self.0
+            }
+        }
+
+        impl_ccall_arg_managed!($name, 2);
+
+        // Safety: the type name of the type must be the type name of the base type.
+        unsafe impl Typecheck for $name<'_, '_> {
+            #[inline]
+            fn typecheck(t: DataType) -> bool {
+                let unrooted = unsafe { Unrooted::new() };
+                let base = inline_static_ref!(BASE_TYPE, UnionAll, $path, &unrooted);
+                t.type_name() == base.base_type().type_name()
+            }
+        }
+
+        unsafe impl ConstructType for $name<'_, '_> {
+            type Static = $name<'static, 'static>;
+
+            #[inline]
+            fn construct_type_uncached<'target, Tgt>(
+                target: Tgt,
+            ) -> ValueData<'target, 'static, Tgt>
+            where
+                Tgt: Target<'target>,
+            {
+                Self::base_type(&target).unwrap().root(target)
+            }
+
+            #[inline]
+            fn base_type<'target, Tgt>(target: &Tgt) -> Option<Value<'target, 'static>>
+            where
+                Tgt: Target<'target>,
+            {
+                Some(inline_static_ref!(BASE_TYPE, Value, $path, target))
+            }
+        }
+
+        #[doc = concat!("A reference to a [`", stringify!($name),
+            "`] that has not been explicitly rooted.")]
+        pub type $ref<'scope, 'data> = Ref<'scope, 'data, $name<'scope, 'data>>;
+
+        #[doc = concat!("A [`", stringify!($ref), "`] with static lifetimes. This is a useful ",
+            "shorthand for signatures of `ccall`able functions that return a [`",
+            stringify!($name), "`].")]
+        pub type $ret = Ref<'static, 'static, $name<'static, 'static>>;
+
+        unsafe impl ValidLayout for $ref<'_, '_> {
+            #[inline]
+            fn valid_layout(v: Value) -> bool {
+                if let Ok(dt) = v.cast::<DataType>() {
+                    dt.is::<$name>()
+                } else if let Ok(ua) = v.cast::<UnionAll>() {
+                    ua.base_type().is::<$name>()
+                } else {
+                    false
+                }
+            }
+
+            #[inline]
+            fn type_object<'target, Tgt: Target<'target>>(
+                target: &Tgt,
+            ) -> Value<'target, 'static> {
+                $name::base_type(target).unwrap()
+            }
+
+            const IS_REF: bool = true;
+        }
+
+        unsafe impl ValidField for Option<$ref<'_, '_>> {
+            #[inline]
+            fn valid_field(v: Value) -> bool {
+                <$ref as ValidLayout>::valid_layout(v)
+            }
+        }
+
+        #[doc = concat!("`", stringify!($name), "` or `", stringify!($ref),
+            "`, depending on the target type `T`.")]
+        pub type $data<'target, 'data, T> =
+            <T as TargetType<'target>>::Data<'data, $name<'target, 'data>>;
+
+        #[doc = concat!("`JuliaResult<", stringify!($name), ">` or `JuliaResultRef<",
+            stringify!($ref), ">`, depending on the target type `T`.")]
+        pub type $result<'target, 'data, T> =
+            TargetResult<'target, 'data, $name<'target, 'data>, T>;
+    };
+}
+
+impl_strided_array!(
+    /// A `SubArray`, the view returned by functions like `view`.
+    SubArray,
+    SubArrayRef,
+    SubArrayRet,
+    SubArrayData,
+    SubArrayResult,
+    "Base.SubArray"
+);
+
+impl_strided_array!(
+    /// A `PermutedDimsArray`, a view of an array whose dimensions have been permuted.
+    PermutedDimsArray,
+    PermutedDimsArrayRef,
+    PermutedDimsArrayRet,
+    PermutedDimsArrayData,
+    PermutedDimsArrayResult,
+    "Base.PermutedDimsArray"
+);
+
+impl_strided_array!(
+    /// A `ReshapedArray`, the view returned by `reshape` if the parent is not an `Array`.
+    ReshapedArray,
+    ReshapedArrayRef,
+    ReshapedArrayRet,
+    ReshapedArrayData,
+    ReshapedArrayResult,
+    "Base.ReshapedArray"
+);
+
+impl<'scope, 'data> SubArray<'scope, 'data> {
+    /// Returns the tuple of indices into the parent that this view was created with.
+    #[inline]
+    pub fn indices(self) -> Value<'scope, 'data> {
+        // Safety: the indices are reachable from the view.
+        unsafe {
+            self.as_value()
+                .get_field_ref("indices")
+                .expect("no indices field")
+                .expect("indices are undefined")
+                .as_value()
+        }
+    }
+
+    /// Returns the `offset1` field of this view.
+    ///
+    /// If the view supports fast linear indexing, the 1-based linear index into the parent of
+    /// the `i`th element of the view is `offset1 + stride1 * i`. Otherwise this field is 0.
+    #[inline]
+    pub fn offset1(self) -> isize {
+        self.as_value()
+            .field_accessor()
+            .field("offset1")
+            .and_then(|offset| offset.access::<isize>())
+            .expect("SubArray has no offset1 field")
+    }
+
+    /// Returns the `stride1` field of this view, the stride of linear indices into the parent.
+    ///
+    /// This field is only meaningful if the view supports fast linear indexing, otherwise it's 0.
+    #[inline]
+    pub fn stride1(self) -> isize {
+        self.as_value()
+            .field_accessor()
+            .field("stride1")
+            .and_then(|stride| stride.access::<isize>())
+            .expect("SubArray has no stride1 field")
+    }
+}
+
+impl<'scope, 'data> PermutedDimsArray<'scope, 'data> {
+    /// Returns the permutation of the dimensions of the parent, the `n`th dimension of this view
+    /// is the `permutation[n]`th dimension of the parent. Dimensions are 1-based.
+    pub fn permutation(self) -> Vec<usize> {
+        // Safety: the permutation is a tuple of `Int`s, which is the third type parameter.
+        unsafe {
+            let perm = self.as_value().datatype().parameters().data().as_slice()[2]
+                .expect("permutation is undefined")
+                .as_value();
+            read_int_tuple(perm).iter().map(|&p| p as usize).collect()
+        }
+    }
+}
+
+/// Strided access to the elements of a view.
+///
+/// An accessor can be created with the `bits_data` and `bits_data_mut` methods of [`SubArray`],
+/// [`PermutedDimsArray`] and [`ReshapedArray`]. Elements can be accessed with an n-dimensional
+/// index, the elements are not necessarily stored contiguously.
+pub struct StridedArrayAccessor<'borrow, 'data, T, M: Mutability> {
+    ptr: *mut T,
+    dims: Dimensions,
+    strides: Box<[isize]>,
+    _data: PhantomData<&'data ()>,
+    _borrow: PhantomData<&'borrow ()>,
+    _mutability: PhantomData<M>,
+}
+
+impl<'borrow, 'data, T, M> StridedArrayAccessor<'borrow, 'data, T, M>
+where
+    T: ValidField,
+    M: Mutability,
+{
+    // Safety: the view must not be accessed mutably while the accessor exists.
+    unsafe fn new(view: Value<'_, 'data>) -> JlrsResult<Self> {
+        let element_type = element_type(view);
+        match element_type.cast::<DataType>() {
+            Ok(dt) if dt.is_bits() => (),
+            _ => Err(ArrayLayoutError::NotBits {
+                element_type: element_type.display_string_or(CANNOT_DISPLAY_TYPE),
+            })?,
+        }
+
+        if !T::valid_field(element_type) {
+            Err(AccessError::InvalidLayout {
+                value_type: element_type.display_string_or(CANNOT_DISPLAY_TYPE),
+            })?
+        }
+
+        let dims = dimensions(view)?;
+        let strides = strides(view)?.into_boxed_slice();
+        let ptr = view.unrooted_target().local_scope::<_, _, 1>(|mut frame| {
+            let pointer = inline_static_ref!(POINTER, Function, "Base.pointer", &frame);
+            let ptr = pointer.call1(&mut frame, view).into_jlrs_result()?;
+            Ok(ptr.data_ptr().cast::<*mut c_void>().as_ptr().read())
+        })?;
+
+        Ok(StridedArrayAccessor {
+            ptr: ptr.cast(),
+            dims,
+            strides,
+            _data: PhantomData,
+            _borrow: PhantomData,
+            _mutability: PhantomData,
+        })
+    }
+
+    /// Returns the dimensions of the view.
+    #[inline]
+    pub fn dimensions(&self) -> &Dimensions {
+        &self.dims
+    }
+
+    /// Returns the strides of the view in number of elements.
+    #[inline]
+    pub fn strides(&self) -> &[isize] {
+        &self.strides
+    }
+
+    /// Returns a pointer to the first element of the view.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr
+    }
+
+    /// Returns a reference to the element at `index`, or `None` if the index is out of bounds.
+    #[inline]
+    pub fn get<D: Dims>(&self, index: D) -> Option<&T> {
+        let offset = self.offset_of(&index)?;
+        // Safety: the index is in bounds.
+        unsafe { Some(&*self.ptr.offset(offset)) }
+    }
+
+    fn offset_of<D: Dims>(&self, index: &D) -> Option<isize> {
+        let rank = self.dims.rank();
+        if index.rank() != rank {
+            return None;
+        }
+
+        let mut offset = 0;
+        for dim in 0..rank {
+            let idx = index.n_elements(dim);
+            if idx >= self.dims.n_elements(dim) {
+                return None;
+            }
+
+            offset += idx as isize * self.strides[dim];
+        }
+
+        Some(offset)
+    }
+}
+
+impl<'borrow, 'data, T> StridedArrayAccessor<'borrow, 'data, T, Mutable<'borrow, T>>
+where
+    T: ValidField,
+{
+    /// Returns a mutable reference to the element at `index`, or `None` if the index is out of
+    /// bounds.
+    #[inline]
+    pub fn get_mut<D: Dims>(&mut self, index: D) -> Option<&mut T> {
+        let offset = self.offset_of(&index)?;
+        // Safety: the index is in bounds, the view is borrowed mutably.
+        unsafe { Some(&mut *self.ptr.offset(offset)) }
+    }
+
+    /// Returns a mutable pointer to the first element of the view.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.ptr
+    }
+}
+
+impl<T, M, D> Index<D> for StridedArrayAccessor<'_, '_, T, M>
+where
+    T: ValidField,
+    M: Mutability,
+    D: Dims,
+{
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: D) -> &Self::Output {
+        let dims = index.into_dimensions();
+        match self.get(index) {
+            Some(elem) => elem,
+            None => panic!("index {} is out of bounds for shape {}", dims, self.dims),
+        }
+    }
+}
+
+impl<'borrow, T, D> IndexMut<D> for StridedArrayAccessor<'borrow, '_, T, Mutable<'borrow, T>>
+where
+    T: ValidField,
+    D: Dims,
+{
+    #[inline]
+    fn index_mut(&mut self, index: D) -> &mut Self::Output {
+        let dims = index.into_dimensions();
+        let shape = self.dims.clone();
+        match self.get_mut(index) {
+            Some(elem) => elem,
+            None => panic!("index {} is out of bounds for shape {}", dims, shape),
+        }
+    }
+}
+
+// The element type is the first type parameter of all strided array types.
+#[inline]
+fn element_type<'scope>(view: Value<'scope, '_>) -> Value<'scope, 'static> {
+    // Safety: the type parameters are rooted.
+    unsafe {
+        view.datatype().parameters().data().as_slice()[0]
+            .expect("element type is undefined")
+            .as_value()
+    }
+}
+
+fn dimensions(view: Value) -> JlrsResult<Dimensions> {
+    view.unrooted_target()
+        .local_scope::<_, _, 1>(|mut frame| unsafe {
+            let size = inline_static_ref!(SIZE, Function, "Base.size", &frame);
+            let dims = size.call1(&mut frame, view).into_jlrs_result()?;
+            let dims = read_int_tuple(dims)
+                .iter()
+                .map(|&d| d as usize)
+                .collect::<Vec<_>>();
+            Ok(Dimensions::from_dims(&dims.as_slice()))
+        })
+}
+
+fn strides(view: Value) -> JlrsResult<Vec<isize>> {
+    view.unrooted_target()
+        .local_scope::<_, _, 1>(|mut frame| unsafe {
+            let strides = inline_static_ref!(STRIDES, Function, "Base.strides", &frame);
+            let strides = strides.call1(&mut frame, view).into_jlrs_result()?;
+            Ok(read_int_tuple(strides).to_vec())
+        })
+}
+
+// Safety: `tuple` must be a tuple of `Int`s.
+#[inline]
+unsafe fn read_int_tuple<'a>(tuple: Value<'a, '_>) -> &'a [isize] {
+    let n = tuple.n_fields();
+    std::slice::from_raw_parts(tuple.data_ptr().cast::<isize>().as_ptr(), n)
+}
@@ -0,0 +1,354 @@
+//! Managed type for `BitArray`, an n-dimensional array of packed booleans.
+//!
+//! Comparisons like `a .> 0` return a `BitArray` rather than an `Array{Bool}`. The elements of a
+//! `BitArray` are stored as bits in a vector of `UInt64`s, so they can't be accessed as a slice
+//! of `bool`s. A [`BitArray`] provides methods to read and write individual elements, and to
+//! convert all elements to a `Vec<bool>`. A new `BitArray` can be created from an iterator of
+//! `bool`s with [`BitArray::from_iter`] or a slice with [`BitArray::from_slice`].
+//!
+//! Example:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::util::test::JULIA;
+//! use jlrs::data::managed::bit_array::BitArray;
+//!
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! # let mut frame = StackFrame::new();
+//! # let mut julia = julia.instance(&mut frame);
+//! julia
+//!     .scope(|mut frame| unsafe {
+//!         let bits = Value::eval_string(&mut frame, "[1, -2, 3] .> 0")
+//!             .into_jlrs_result()?
+//!             .cast::<BitArray>()?;
+//!
+//!         assert_eq!(bits.to_vec(), vec![true, false, true]);
+//!         assert_eq!(bits.get(1), Some(false));
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # });
+//! # }
+//! ```
+
+use std::{marker::PhantomData, ptr::NonNull};
+
+use jl_sys::{jl_array_t, jl_value_t};
+
+use super::{
+    array::{
+        dimensions::{Dimensions, Dims, DimsExt},
+        sized_dim_tuple, Array,
+    },
+    value::ValueData,
+    Ref,
+};
+use crate::{
+    call::Call,
+    convert::into_jlrs_result::IntoJlrsResult,
+    data::{
+        layout::valid_layout::{ValidField, ValidLayout},
+        managed::{
+            datatype::DataType, function::Function, private::ManagedPriv, union_all::UnionAll,
+            value::Value, Managed,
+        },
+        types::{construct_type::ConstructType, typecheck::Typecheck},
+    },
+    error::{InstantiationError, JlrsResult},
+    inline_static_ref,
+    memory::target::{unrooted::Unrooted, Target, TargetResult, TargetType},
+    private::Private,
+};
+
+/// A `BitArray{N}`.
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+pub struct BitArray<'scope>(NonNull<jl_value_t>, PhantomData<&'scope ()>);
+
+// The layout of `BitArray{N}`, the dimensions are stored after `len` as `NTuple{N, Int}`.
+#[repr(C)]
+struct BitArrayLayout {
+    chunks: NonNull<jl_array_t>,
+    len: usize,
+}
+
+impl BitArray<'_> {
+    /// Create a new `BitArray` with dimensions `dims` whose elements are all `false`.
+    ///
+    /// If an exception is thrown, it's converted to an error.
+    pub fn new<'target, D, Tgt>(target: Tgt, dims: D) -> JlrsResult<BitArrayData<'target, Tgt>>
+    where
+        D: DimsExt,
+        Tgt: Target<'target>,
+    {
+        target.with_local_scope::<_, _, 2>(|target, mut frame| unsafe {
+            let falses = inline_static_ref!(FALSES, Function, "Base.falses", &frame);
+            let dims = sized_dim_tuple(&mut frame, &dims);
+            let bits = falses.call1(&mut frame, dims).into_jlrs_result()?;
+            Ok(bits.cast_unchecked::<BitArray>().root(target))
+        })
+    }
+
+    /// Create a new `BitArray` with dimensions `dims` from the `bool`s in `data`.
+    ///
+    /// The elements are provided in column-major order. If the number of elements doesn't match
+    /// the size of `dims`, `InstantiationError::ArraySizeMismatch` is returned.
+    pub fn from_iter<'target, I, D, Tgt>(
+        target: Tgt,
+        data: I,
+        dims: D,
+    ) -> JlrsResult<BitArrayData<'target, Tgt>>
+    where
+        I: IntoIterator<Item = bool>,
+        D: DimsExt,
+        Tgt: Target<'target>,
+    {
+        let size = dims.size();
+        let mut chunks = vec![0u64; n_chunks(size)];
+        let mut len = 0;
+        // At most one element more than `size` is consumed to detect that `data` is too long.
+        for (idx, bit) in data.into_iter().take(size + 1).enumerate() {
+            if idx < size && bit {
+                chunks[idx >> 6] |= 1 << (idx & 63);
+            }
+
+            len += 1;
+        }
+
+        if len != size {
+            Err(InstantiationError::ArraySizeMismatch {
+                vec_size: len,
+                dim_size: size,
+            })?;
+        }
+
+        target.with_local_scope::<_, _, 1>(|target, mut frame| {
+            let bits = Self::new(&mut frame, dims)?;
+            // Safety: the array was just created, it has the same number of chunks.
+            unsafe { bits.chunks_mut().copy_from_slice(&chunks) };
+            Ok(bits.root(target))
+        })
+    }
+
+    /// Create a new `BitArray` with dimensions `dims` from the `bool`s in `data`.
+    ///
+    /// The elements are provided in column-major order. If the number of elements doesn't match
+    /// the size of `dims`, `InstantiationError::ArraySizeMismatch` is returned.
+    #[inline]
+    pub fn from_slice<'target, D, Tgt>(
+        target: Tgt,
+        data: &[bool],
+        dims: D,
+    ) -> JlrsResult<BitArrayData<'target, Tgt>>
+    where
+        D: DimsExt,
+        Tgt: Target<'target>,
+    {
+        Self::from_iter(target, data.iter().copied(), dims)
+    }
+}
+
+impl<'scope> BitArray<'scope> {
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(self) -> usize {
+        self.layout().len
+    }
+
+    /// Returns `true` if this array has no elements.
+    #[inline]
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the rank of this array.
+    #[inline]
+    pub fn rank(self) -> usize {
+        // Safety: the rank is the first type parameter, it's an `Int`.
+        unsafe {
+            self.as_value().datatype().parameters().data().as_slice()[0]
+                .expect("rank is undefined")
+                .as_value()
+                .unbox_unchecked::<isize>() as usize
+        }
+    }
+
+    /// Returns the dimensions of this array.
+    pub fn dimensions(self) -> Dimensions {
+        let rank = self.rank();
+        // The dimensions of a `BitVector` are not stored, its only dimension is its length.
+        if rank == 1 {
+            return Dimensions::from_dims(&self.len());
+        }
+
+        // Safety: the dimensions are stored after the length.
+        unsafe {
+            let layout = self.unwrap_non_null(Private).cast::<BitArrayLayout>();
+            let dims = layout.as_ptr().add(1).cast::<usize>();
+            let dims = std::slice::from_raw_parts(dims, rank);
+            Dimensions::from_dims(&dims)
+        }
+    }
+
+    /// Returns the element at `index`, or `None` if the index is out of bounds.
+    pub fn get<D: Dims>(self, index: D) -> Option<bool> {
+        let idx = self.dimensions().index_of(&index).ok()?;
+        // Safety: the index is in bounds.
+        let chunk = unsafe { self.chunks()[idx >> 6] };
+        Some(chunk & (1 << (idx & 63)) != 0)
+    }
+
+    /// Set the element at `index` to `value`. Returns an error if the index is out of bounds.
+    ///
+    /// Safety: Mutating Julia data is generally unsafe because it can't be guaranteed mutating this
+    /// value is allowed.
+    pub unsafe fn set<D: Dims>(self, index: D, value: bool) -> JlrsResult<()> {
+        let idx = self.dimensions().index_of(&index)?;
+        let chunk = &mut self.chunks_mut()[idx >> 6];
+        if value {
+            *chunk |= 1 << (idx & 63);
+        } else {
+            *chunk &= !(1 << (idx & 63));
+        }
+
+        Ok(())
+    }
+
+    /// Copy the elements to a `Vec` in column-major order.
+    pub fn to_vec(self) -> Vec<bool> {
+        // Safety: the chunks are only read while the elements are copied.
+        let chunks = unsafe { self.chunks() };
+        (0..self.len())
+            .map(|idx| chunks[idx >> 6] & (1 << (idx & 63)) != 0)
+            .collect()
+    }
+
+    #[inline]
+    fn layout(&self) -> &BitArrayLayout {
+        // Safety: the layout matches the layout of `BitArray`.
+        unsafe {
+            self.unwrap_non_null(Private)
+                .cast::<BitArrayLayout>()
+                .as_ref()
+        }
+    }
+
+    // Safety: the chunks must not be mutated while the slice exists.
+    #[inline]
+    unsafe fn chunks(self) -> &'scope [u64] {
+        let chunks = Array::wrap_non_null(self.layout().chunks, Private);
+        std::slice::from_raw_parts(chunks.data_ptr().cast(), n_chunks(self.len()))
+    }
+
+    // Safety: the chunks must not be accessed while the slice exists.
+    #[inline]
+    unsafe fn chunks_mut(self) -> &'scope mut [u64] {
+        let chunks = Array::wrap_non_null(self.layout().chunks, Private);
+        std::slice::from_raw_parts_mut(chunks.data_ptr().cast(), n_chunks(self.len()))
+    }
+}
+
+// Returns the number of `UInt64` chunks that store `len` bits.
+#[inline]
+const fn n_chunks(len: usize) -> usize {
+    (len + 63) / 64
+}
+
+impl_debug!(BitArray<'_>);
+
+impl<'scope> ManagedPriv<'scope, '_> for BitArray<'scope> {
+    type Wraps = jl_value_t;
+    type TypeConstructorPriv<'target, 'da> = BitArray<'target>;
+    const NAME: &'static str = "BitArray";
+
+    // Safety: `inner` must not have been freed yet, the result must never be
+    // used after the GC might have freed it.
+    #[inline]
+    unsafe fn wrap_non_null(inner: NonNull<Self::Wraps>, _: Private) -> Self {
+        Self(inner, PhantomData)
+    }
+
+    #[inline]
+    fn unwrap_non_null(self, _: Private) -> NonNull<Self::Wraps> {
+        self.0
+    }
+}
+
+impl_ccall_arg_managed!(BitArray, 1);
+
+// Safety: the type name of the type must be the type name of `BitArray`.
+unsafe impl Typecheck for BitArray<'_> {
+    #[inline]
+    fn typecheck(t: DataType) -> bool {
+        let unrooted = unsafe { Unrooted::new() };
+        let base = inline_static_ref!(BIT_ARRAY, UnionAll, "Base.BitArray", &unrooted);
+        t.type_name() == base.base_type().type_name()
+    }
+}
+
+unsafe impl ConstructType for BitArray<'_> {
+    type Static = BitArray<'static>;
+
+    #[inline]
+    fn construct_type_uncached<'target, Tgt>(target: Tgt) -> ValueData<'target, 'static, Tgt>
+    where
+        Tgt: Target<'target>,
+    {
+        Self::base_type(&target).unwrap().root(target)
+    }
+
+    #[inline]
+    fn base_type<'target, Tgt>(target: &Tgt) -> Option<Value<'target, 'static>>
+    where
+        Tgt: Target<'target>,
+    {
+        Some(inline_static_ref!(
+            BIT_ARRAY,
+            Value,
+            "Base.BitArray",
+            target
+        ))
+    }
+}
+
+/// A reference to a [`BitArray`] that has not been explicitly rooted.
+pub type BitArrayRef<'scope> = Ref<'scope, 'static, BitArray<'scope>>;
+
+/// A [`BitArrayRef`] with static lifetimes. This is a useful shorthand for signatures of
+/// `ccall`able functions that return a [`BitArray`].
+pub type BitArrayRet = Ref<'static, 'static, BitArray<'static>>;
+
+unsafe impl ValidLayout for BitArrayRef<'_> {
+    #[inline]
+    fn valid_layout(v: Value) -> bool {
+        if let Ok(dt) = v.cast::<DataType>() {
+            dt.is::<BitArray>()
+        } else if let Ok(ua) = v.cast::<UnionAll>() {
+            ua.base_type().is::<BitArray>()
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn type_object<'target, Tgt: Target<'target>>(target: &Tgt) -> Value<'target, 'static> {
+        BitArray::base_type(target).unwrap()
+    }
+
+    const IS_REF: bool = true;
+}
+
+unsafe impl ValidField for Option<BitArrayRef<'_>> {
+    #[inline]
+    fn valid_field(v: Value) -> bool {
+        <BitArrayRef as ValidLayout>::valid_layout(v)
+    }
+}
+
+/// `BitArray` or `BitArrayRef`, depending on the target type `T`.
+pub type BitArrayData<'target, T> = <T as TargetType<'target>>::Data<'static, BitArray<'target>>;
+
+/// `JuliaResult<BitArray>` or `JuliaResultRef<BitArrayRef>`, depending on the target type `T`.
+pub type BitArrayResult<'target, T> = TargetResult<'target, 'static, BitArray<'target>, T>;
@@ -0,0 +1,521 @@
+//! Managed type for `Base.Dict`.
+//!
+//! A [`Dict`] is a `Value` whose type is `Dict{K, V}`, where `K` and `V` are the types
+//! constructed from its type parameters. New dictionaries can be created from Rust maps with
+//! [`Dict::from_map`], and converted back with [`Dict::to_hash_map`] and
+//! [`Dict::to_btree_map`]. Entries can be accessed and inserted directly without calling
+//! `getindex` and `setindex!` manually.
+//!
+//! Example:
+//!
+//! ```
+//! # use jlrs::prelude::*;
+//! # use jlrs::util::test::JULIA;
+//! use std::collections::HashMap;
+//!
+//! use jlrs::data::managed::dict::Dict;
+//!
+//! # fn main() {
+//! # JULIA.with(|j| {
+//! # let mut julia = j.borrow_mut();
+//! # let mut frame = StackFrame::new();
+//! # let mut julia = julia.instance(&mut frame);
+//! julia
+//!     .scope(|mut frame| {
+//!         let mut map = HashMap::new();
+//!         map.insert(1i64, 2.0f64);
+//!
+//!         // Converted to a `Dict{Int64, Float64}`
+//!         let dict = Dict::<i64, f64>::from_map(&mut frame, &map)?;
+//!         assert_eq!(dict.len()?, 1);
+//!
+//!         let value = dict.get(&mut frame, 1)?.unwrap();
+//!         assert_eq!(value.unbox::<f64>()?, 2.0);
+//!
+//!         assert_eq!(dict.to_hash_map()?, map);
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! # });
+//! # }
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{Debug, Formatter, Result as FmtResult},
+    hash::Hash,
+    marker::PhantomData,
+    ptr::{read, NonNull},
+};
+
+use jl_sys::jl_value_t;
+
+use super::{
+    array::Array,
+    value::{ValueData, ValueRef},
+    Ref,
+};
+use crate::{
+    call::Call,
+    convert::{
+        ccall_types::{CCallArg, CCallReturn},
+        into_jlrs_result::IntoJlrsResult,
+        into_julia::IntoJulia,
+        unbox::Unbox,
+    },
+    data::{
+        layout::{
+            is_bits::IsBits,
+            valid_layout::{ValidField, ValidLayout},
+        },
+        managed::{
+            datatype::DataType, function::Function, private::ManagedPriv, value::Value, Managed,
+        },
+        types::{construct_type::ConstructType, typecheck::Typecheck},
+    },
+    error::{JlrsResult, TypeError, CANNOT_DISPLAY_TYPE, CANNOT_DISPLAY_VALUE},
+    inline_static_ref,
+    memory::target::{Target, TargetResult, TargetType},
+    private::Private,
+};
+
+/// A `Dict{K, V}`.
+#[repr(transparent)]
+pub struct Dict<'scope, 'data, K, V>(
+    NonNull<jl_value_t>,
+    PhantomData<&'scope ()>,
+    PhantomData<&'data mut ()>,
+    PhantomData<fn() -> (K, V)>,
+);
+
+impl<K, V> Dict<'_, '_, K, V>
+where
+    K: ConstructType,
+    V: ConstructType,
+{
+    /// Create a new, empty `Dict{K, V}`.
+    pub fn new<'target, Tgt>(target: Tgt) -> JlrsResult<DictData<'target, 'static, K, V, Tgt>>
+    where
+        Tgt: Target<'target>,
+    {
+        target.with_local_scope::<_, _, 2>(|target, mut frame| unsafe {
+            let ty = Self::construct_type(&mut frame);
+            let dict = ty.call0(&mut frame).into_jlrs_result()?;
+            Ok(dict.cast_unchecked::<Dict<K, V>>().root(target))
+        })
+    }
+
+    /// Create a new `Dict{K, V}` that contains all entries in `map`.
+    ///
+    /// Any map that can be iterated over by reference can be used, including `HashMap` and
+    /// `BTreeMap`. The keys and values are converted from the borrowed entries, so `K` and `V`
+    /// must be `isbits` types. If an exception is thrown while an entry is inserted, it's
+    /// converted to an error.
+    pub fn from_map<'target, 'map, M, Tgt>(
+        target: Tgt,
+        map: M,
+    ) -> JlrsResult<DictData<'target, 'static, K, V, Tgt>>
+    where
+        K: IntoJulia + IsBits + 'map,
+        V: IntoJulia + IsBits + 'map,
+        M: IntoIterator<Item = (&'map K, &'map V)>,
+        Tgt: Target<'target>,
+    {
+        target.with_local_scope::<_, _, 1>(|target, mut frame| {
+            let dict = Self::new(&mut frame)?;
+            for (key, value) in map {
+                frame.local_scope::<_, _, 2>(|mut frame| unsafe {
+                    // Safety: `K` and `V` are isbits types, so a bitwise copy is a valid
+                    // instance. The copy is moved into Julia and never dropped by Rust. The
+                    // dict was just created and isn't shared.
+                    let key = Value::new(&mut frame, read(key));
+                    let value = Value::new(&mut frame, read(value));
+                    dict.setindex(key, value)
+                })?;
+            }
+
+            Ok(dict.root(target))
+        })
+    }
+}
+
+impl<'scope, 'data, K, V> Dict<'scope, 'data, K, V>
+where
+    K: ConstructType,
+    V: ConstructType,
+{
+    /// Returns the number of entries in this dictionary.
+    ///
+    /// An error is returned if the `count` field of the dictionary can't be accessed.
+    pub fn len(self) -> JlrsResult<usize> {
+        let count = self
+            .as_value()
+            .field_accessor()
+            .field("count")?
+            .access::<isize>()?;
+        Ok(count as usize)
+    }
+
+    /// Returns `true` if this dictionary has no entries.
+    #[inline]
+    pub fn is_empty(self) -> JlrsResult<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Returns the value associated with `key`, or `None` if there is no such entry.
+    ///
+    /// If an exception is thrown, it's converted to an error.
+    pub fn get<'target, Tgt>(
+        self,
+        target: Tgt,
+        key: K,
+    ) -> JlrsResult<Option<ValueData<'target, 'data, Tgt>>>
+    where
+        K: IntoJulia,
+        Tgt: Target<'target>,
+    {
+        target.with_local_scope::<_, _, 2>(|target, mut frame| unsafe {
+            // Base uses this token to distinguish missing entries from stored values in methods
+            // like `get!`.
+            let token = inline_static_ref!(TOKEN, Value, "Base.secret_table_token", &frame);
+            let get = inline_static_ref!(GET, Function, "Base.get", &frame);
+
+            let key = Value::new(&mut frame, key);
+            let value = get
+                .call3(&mut frame, self.as_value(), key, token)
+                .into_jlrs_result()?;
+
+            if value == token {
+                Ok(None)
+            } else {
+                Ok(Some(value.root(target)))
+            }
+        })
+    }
+
+    /// Insert a new entry, if an entry with the same key already exists its value is replaced.
+    ///
+    /// If an exception is thrown, it's converted to an error.
+    ///
+    /// Safety: The dictionary must not be accessed from another thread or task while the entry is
+    /// inserted, or be iterated over by Julia code.
+    pub unsafe fn insert(self, key: K, value: V) -> JlrsResult<()>
+    where
+        K: IntoJulia,
+        V: IntoJulia,
+    {
+        self.as_value()
+            .unrooted_target()
+            .local_scope::<_, _, 2>(|mut frame| {
+                let key = Value::new(&mut frame, key);
+                let value = Value::new(&mut frame, value);
+                self.setindex(key, value)
+            })
+    }
+
+    // Calls `setindex!(self, value, key)`, the same safety requirements as `Dict::insert` apply.
+    unsafe fn setindex(self, key: Value, value: Value) -> JlrsResult<()> {
+        self.as_value()
+            .unrooted_target()
+            .local_scope::<_, _, 1>(|mut frame| {
+                let setindex = inline_static_ref!(SETINDEX, Function, "Base.setindex!", &frame);
+                setindex
+                    .call3(&mut frame, self.as_value(), value, key)
+                    .into_jlrs_result()?;
+                Ok(())
+            })
+    }
+
+    /// Returns an iterator over the entries of this dictionary.
+    ///
+    /// The entries are collected into a `Vector{Any}` which is rooted in `target`, the keys and
+    /// values returned by the iterator are reachable from that vector. Like `Base.iterate`, the
+    /// iterator skips entries whose key or value is undefined. If an exception is thrown, it's
+    /// converted to an error.
+    pub fn iter<'target, Tgt>(self, target: Tgt) -> JlrsResult<DictIter<'target, 'data>>
+    where
+        Tgt: Target<'target, Data<'data, Array<'target, 'data>> = Array<'target, 'data>>,
+    {
+        target.with_local_scope::<_, _, 2>(|target, mut frame| unsafe {
+            let collect = inline_static_ref!(COLLECT, Function, "Base.collect", &frame);
+            let flatten = inline_static_ref!(FLATTEN, Function, "Base.Iterators.flatten", &frame);
+            let any = DataType::any_type(&frame).as_value();
+
+            let entries = flatten
+                .call1(&mut frame, self.as_value())
+                .into_jlrs_result()?;
+            let entries = collect
+                .call2(&mut frame, any, entries)
+                .into_jlrs_result()?
+                .cast_unchecked::<Array<'_, 'data>>()
+                .root(target);
+
+            Ok(DictIter {
+                entries: entries.into_slice_unchecked(),
+                index: 0,
+            })
+        })
+    }
+
+    /// Convert this dictionary to a `HashMap`.
+    pub fn to_hash_map(self) -> JlrsResult<HashMap<K::Output, V::Output>>
+    where
+        K: Unbox + Typecheck,
+        V: Unbox + Typecheck,
+        K::Output: Hash + Eq,
+    {
+        self.as_value()
+            .unrooted_target()
+            .local_scope::<_, _, 1>(|mut frame| {
+                // Safety: the entries are only read while the frame is active.
+                let dict = unsafe { self.assume_static() };
+                let mut map = HashMap::with_capacity(self.len()?);
+                for (key, value) in dict.iter(&mut frame)? {
+                    map.insert(key.unbox::<K>()?, value.unbox::<V>()?);
+                }
+
+                Ok(map)
+            })
+    }
+
+    /// Convert this dictionary to a `BTreeMap`.
+    pub fn to_btree_map(self) -> JlrsResult<BTreeMap<K::Output, V::Output>>
+    where
+        K: Unbox + Typecheck,
+        V: Unbox + Typecheck,
+        K::Output: Ord,
+    {
+        self.as_value()
+            .unrooted_target()
+            .local_scope::<_, _, 1>(|mut frame| {
+                // Safety: the entries are only read while the frame is active.
+                let dict = unsafe { self.assume_static() };
+                let mut map = BTreeMap::new();
+                for (key, value) in dict.iter(&mut frame)? {
+                    map.insert(key.unbox::<K>()?, value.unbox::<V>()?);
+                }
+
+                Ok(map)
+            })
+    }
+
+    // Used to collect the entries in a local scope, which requires `'data: 'static`.
+    #[inline]
+    unsafe fn assume_static(self) -> Dict<'scope, 'static, K, V> {
+        Dict::wrap_non_null(self.unwrap_non_null(Private), Private)
+    }
+
+    /// Create a new `Dict` from an existing value, an error is returned if `value` is not a
+    /// `Dict{K, V}`.
+    pub fn from_value(value: Value<'scope, 'data>) -> JlrsResult<Self> {
+        if value.is::<Self>() {
+            unsafe { Ok(value.cast_unchecked()) }
+        } else {
+            value.unrooted_target().local_scope::<_, _, 1>(|mut frame| {
+                let ty = Self::construct_type(&mut frame);
+                Err(TypeError::NotA {
+                    value: value.display_string_or(CANNOT_DISPLAY_VALUE),
+                    field_type: ty.display_string_or(CANNOT_DISPLAY_TYPE),
+                })?
+            })
+        }
+    }
+}
+
+/// Iterator over the entries of a [`Dict`], created by [`Dict::iter`].
+pub struct DictIter<'scope, 'data> {
+    entries: &'scope [Option<ValueRef<'scope, 'data>>],
+    index: usize,
+}
+
+impl<'scope, 'data> Iterator for DictIter<'scope, 'data> {
+    type Item = (Value<'scope, 'data>, Value<'scope, 'data>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let key = self.entries.get(self.index)?;
+            let value = self.entries.get(self.index + 1)?;
+            self.index += 2;
+
+            if let (Some(key), Some(value)) = (*key, *value) {
+                // Safety: the entries are reachable from a rooted vector.
+                unsafe { return Some((key.as_value(), value.as_value())) }
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.entries.len() - self.index) / 2;
+        (0, Some(n))
+    }
+}
+
+impl<K, V> Clone for Dict<'_, '_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for Dict<'_, '_, K, V> {}
+
+impl<K, V> Debug for Dict<'_, '_, K, V>
+where
+    K: ConstructType,
+    V: ConstructType,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.display_string() {
+            Ok(s) => f.write_str(&s),
+            Err(e) => f.write_fmt(format_args!("<Cannot display value: {}>", e)),
+        }
+    }
+}
+
+impl<'scope, 'data, K, V> ManagedPriv<'scope, 'data> for Dict<'scope, 'data, K, V>
+where
+    K: ConstructType,
+    V: ConstructType,
+{
+    type Wraps = jl_value_t;
+    type TypeConstructorPriv<'target, 'da> = Dict<'target, 'da, K, V>;
+    const NAME: &'static str = "Dict";
+
+    // Safety: `inner` must not have been freed yet, the result must never be
+    // used after the GC might have freed it.
+    #[inline]
+    unsafe fn wrap_non_null(inner: NonNull<Self::Wraps>, _: Private) -> Self {
+        Self(inner, PhantomData, PhantomData, PhantomData)
+    }
+
+    #[inline]
+    fn unwrap_non_null(self, _: Private) -> NonNull<Self::Wraps> {
+        self.0
+    }
+}
+
+// Safety: the type is checked against the constructed type `Dict{K, V}`.
+unsafe impl<K, V> Typecheck for Dict<'_, '_, K, V>
+where
+    K: ConstructType,
+    V: ConstructType,
+{
+    fn typecheck(ty: DataType) -> bool {
+        ty.unrooted_target()
+            .local_scope::<_, _, 1>(|mut frame| {
+                let dict_ty = Self::construct_type(&mut frame);
+                Ok(ty.as_value() == dict_ty)
+            })
+            .unwrap()
+    }
+}
+
+unsafe impl<K, V> ConstructType for Dict<'_, '_, K, V>
+where
+    K: ConstructType,
+    V: ConstructType,
+{
+    type Static = Dict<'static, 'static, K::Static, V::Static>;
+
+    fn construct_type_uncached<'target, Tgt>(target: Tgt) -> ValueData<'target, 'static, Tgt>
+    where
+        Tgt: Target<'target>,
+    {
+        target
+            .with_local_scope::<_, _, 3>(|target, mut frame| {
+                let key_param = K::construct_type(&mut frame);
+                let value_param = V::construct_type(&mut frame);
+                let params = [key_param, value_param];
+                unsafe {
+                    let applied = Self::base_type(&frame)
+                        .unwrap()
+                        .apply_type_unchecked(&mut frame, params);
+                    Ok(applied.root(target))
+                }
+            })
+            .unwrap()
+    }
+
+    #[inline]
+    fn base_type<'target, Tgt>(target: &Tgt) -> Option<Value<'target, 'static>>
+    where
+        Tgt: Target<'target>,
+    {
+        let value = inline_static_ref!(STATIC, Value, "Base.Dict", target);
+        Some(value)
+    }
+}
+
+/// A reference to a [`Dict`] that has not been explicitly rooted.
+pub type DictRef<'scope, 'data, K, V> = Ref<'scope, 'data, Dict<'scope, 'data, K, V>>;
+
+/// A [`DictRef`] with static lifetimes. This is a useful shorthand for signatures of
+/// `ccall`able functions that return a [`Dict`].
+pub type DictRet<K, V> = Ref<'static, 'static, Dict<'static, 'static, K, V>>;
+
+// Safety: DictRef is valid for ty if ty is the constructed type `Dict{K, V}`
+unsafe impl<K, V> ValidLayout for DictRef<'_, '_, K, V>
+where
+    K: ConstructType,
+    V: ConstructType,
+{
+    #[inline]
+    fn valid_layout(ty: Value) -> bool {
+        if let Ok(dt) = ty.cast::<DataType>() {
+            Dict::<K, V>::typecheck(dt)
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn type_object<'target, Tgt: Target<'target>>(target: &Tgt) -> Value<'target, 'static> {
+        Dict::<K, V>::base_type(target).unwrap()
+    }
+
+    const IS_REF: bool = true;
+}
+
+unsafe impl<K, V> ValidField for Option<DictRef<'_, '_, K, V>>
+where
+    K: ConstructType,
+    V: ConstructType,
+{
+    #[inline]
+    fn valid_field(ty: Value) -> bool {
+        DictRef::<K, V>::valid_layout(ty)
+    }
+}
+
+/// `Dict` or `DictRef`, depending on the target type `Tgt`.
+pub type DictData<'target, 'data, K, V, Tgt> =
+    <Tgt as TargetType<'target>>::Data<'data, Dict<'target, 'data, K, V>>;
+
+/// `JuliaResult<Dict>` or `JuliaResultRef<DictRef>`, depending on the target type `Tgt`.
+pub type DictResult<'target, 'data, K, V, Tgt> =
+    TargetResult<'target, 'data, Dict<'target, 'data, K, V>, Tgt>;
+
+unsafe impl<'scope, 'data, K, V> CCallArg for Dict<'scope, 'data, K, V>
+where
+    K: ConstructType,
+    V: ConstructType,
+{
+    type CCallArgType = Value<'scope, 'data>;
+    type FunctionArgType = Self;
+}
+
+unsafe impl<K, V> CCallReturn for DictRet<K, V>
+where
+    K: ConstructType,
+    V: ConstructType,
+{
+    type CCallReturnType = Value<'static, 'static>;
+    type FunctionReturnType = Dict<'static, 'static, K, V>;
+    type ReturnAs = Self;
+
+    #[inline]
+    unsafe fn return_or_throw(self) -> Self::ReturnAs {
+        self
+    }
+}
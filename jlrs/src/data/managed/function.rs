@@ -4,27 +4,52 @@
 //! of the [`Call`] trait. You don't need to cast a [`Value`] to a [`Function`] in order to call
 //! it because [`Value`] also implements [`Call`].
 //!
+//! The arity and argument types of a [`Function`] aren't checked until it's called. A
+//! [`TypedFunction`] checks that a matching method exists when it's created, and can be called
+//! with Rust data directly.
+//!
 //! [`Call`]: crate::call::Call
 
-use std::{marker::PhantomData, ptr::NonNull};
+#[cfg(feature = "internal-types")]
+#[julia_version(since = "1.8")]
+use std::cell::Cell;
+use std::{fmt, marker::PhantomData, ptr::NonNull};
 
 use jl_sys::jl_value_t;
+#[cfg(feature = "internal-types")]
+#[julia_version(since = "1.8")]
+use jl_sys::{jl_get_world_counter, jl_invoke};
+#[cfg(feature = "internal-types")]
+use jlrs_macros::julia_version;
 
 use super::{value::ValueResult, Ref};
+#[cfg(feature = "internal-types")]
+use crate::data::managed::{
+    array::Array,
+    internal::method::{Method, MethodRef},
+};
 use crate::{
-    args::Values,
+    args::{TypedArgs, Values},
     call::{Call, ProvideKeywords, WithKeywords},
-    convert::ccall_types::{CCallArg, CCallReturn},
+    convert::{
+        ccall_types::{CCallArg, CCallReturn},
+        into_jlrs_result::IntoJlrsResult,
+        unbox::Unbox,
+    },
     data::{
         layout::valid_layout::{ValidField, ValidLayout},
         managed::{datatype::DataType, private::ManagedPriv, value::Value, Managed},
         types::{construct_type::ConstructType, typecheck::Typecheck},
     },
-    error::JlrsResult,
+    error::{JlrsResult, TypeError, CANNOT_DISPLAY_VALUE},
+    inline_static_ref,
     memory::target::{unrooted::Unrooted, Target, TargetResult},
     prelude::ValueData,
     private::Private,
 };
+#[cfg(feature = "internal-types")]
+#[julia_version(since = "1.8")]
+use crate::{catch::catch_exceptions, data::managed::internal::method_instance::MethodInstance};
 
 /// A Julia function.
 #[derive(Clone, Copy)]
@@ -41,8 +66,339 @@ impl<'scope, 'data> Function<'scope, 'data> {
     pub fn datatype(self) -> DataType<'scope> {
         self.as_value().datatype()
     }
+
+    /// Compile the method of this function that is called with arguments of the types in
+    /// `arg_types` without calling it.
+    ///
+    /// The method is compiled with `Base.precompile`, which returns `false` if no matching method
+    /// exists or it couldn't be compiled. Calling a function for the first time is expensive
+    /// because it must be compiled first, precompiling it ahead of time moves this cost out of
+    /// the first call. If an exception is thrown, it's converted to an error.
+    pub fn precompile(self, arg_types: &[Value]) -> JlrsResult<bool> {
+        self.unrooted_target().local_scope::<_, _, 2>(|mut frame| {
+            let precompile = inline_static_ref!(PRECOMPILE, Function, "Base.precompile", &frame);
+            let signature = DataType::anytuple_type(&frame)
+                .as_value()
+                .apply_type(&mut frame, arg_types)
+                .into_jlrs_result()?;
+
+            // Safety: `precompile` doesn't mutate its arguments.
+            unsafe {
+                let compiled = precompile
+                    .call2(&mut frame, self.as_value(), signature)
+                    .into_jlrs_result()?
+                    .unbox::<bool>()?
+                    .as_bool();
+
+                Ok(compiled)
+            }
+        })
+    }
+
+    /// Returns an iterator over the methods of this function.
+    ///
+    /// The methods are collected with `Base.methods`, every [`Method`] provides its module, file,
+    /// line and signature. The methods are referenced by the method table of this function, so
+    /// they don't need to be rooted separately. If an exception is thrown, it's converted to an
+    /// error.
+    ///
+    /// [`Method`]: crate::data::managed::internal::method::Method
+    #[cfg(feature = "internal-types")]
+    pub fn methods(self) -> JlrsResult<Methods<'scope>> {
+        self.unrooted_target().local_scope::<_, _, 2>(|mut frame| {
+            let methods = inline_static_ref!(METHODS, Function, "Base.methods", &frame);
+            let collect = inline_static_ref!(COLLECT, Function, "Base.collect", &frame);
+
+            // Safety: `methods` and `collect` don't mutate their arguments. The `Vector{Method}`
+            // is rooted while its contents are read.
+            unsafe {
+                let list = methods
+                    .call1(&mut frame, self.as_value())
+                    .into_jlrs_result()?;
+                let list = collect
+                    .call1(&mut frame, list)
+                    .into_jlrs_result()?
+                    .cast::<Array>()?;
+                let list =
+                    Array::<'_, 'static>::wrap_non_null(list.unwrap_non_null(Private), Private);
+
+                let accessor = list.managed_data::<MethodRef>()?;
+                let methods = accessor
+                    .as_slice()
+                    .iter()
+                    .flatten()
+                    .map(|m| Method::wrap_non_null(m.ptr(), Private))
+                    .collect::<Vec<_>>();
+
+                Ok(Methods {
+                    methods: methods.into_iter(),
+                })
+            }
+        })
+    }
+
+    /// Returns the method that is called when this function is called with arguments of the
+    /// types in `arg_types`.
+    ///
+    /// The method is looked up with `Base.which`. An error is returned if no unique matching
+    /// method exists.
+    #[cfg(feature = "internal-types")]
+    pub fn which(self, arg_types: &[Value]) -> JlrsResult<Method<'scope>> {
+        self.unrooted_target().local_scope::<_, _, 2>(|mut frame| {
+            let which = inline_static_ref!(WHICH, Function, "Base.which", &frame);
+            let signature = DataType::anytuple_type(&frame)
+                .as_value()
+                .apply_type(&mut frame, arg_types)
+                .into_jlrs_result()?;
+
+            // Safety: `which` doesn't mutate its arguments. The method is referenced by the
+            // method table of this function.
+            unsafe {
+                let method = which
+                    .call2(&mut frame, self.as_value(), signature)
+                    .into_jlrs_result()?
+                    .cast::<Method>()?;
+
+                Ok(Method::wrap_non_null(
+                    method.unwrap_non_null(Private),
+                    Private,
+                ))
+            }
+        })
+    }
+
+    /// Resolve the method instance that is called when this function is called with arguments of
+    /// the types in `arg_types`.
+    ///
+    /// All argument types must be concrete, otherwise `TypeError::NotConcrete` is returned. The
+    /// returned [`ResolvedMethod`] invokes the method instance directly, which avoids the cost of
+    /// dynamic dispatch. If no unique matching method exists an error is returned.
+    ///
+    /// This method is only available when Julia 1.8 or later is used.
+    #[cfg(feature = "internal-types")]
+    #[julia_version(since = "1.8")]
+    pub fn resolve(self, arg_types: &[Value]) -> JlrsResult<ResolvedMethod<'scope, 'data>> {
+        self.unrooted_target().local_scope::<_, _, 1>(|mut frame| {
+            let mut types = Vec::with_capacity(arg_types.len() + 1);
+            types.push(self.datatype().as_value());
+            types.extend_from_slice(arg_types);
+
+            let signature = DataType::anytuple_type(&frame)
+                .as_value()
+                .apply_type(&mut frame, types)
+                .into_jlrs_result()?;
+
+            let dt = signature.cast::<DataType>()?;
+            if !dt.is_concrete_type() {
+                Err(TypeError::NotConcrete {
+                    value: signature.display_string_or(CANNOT_DISPLAY_VALUE),
+                })?
+            }
+
+            // Safety: the world counter is read before the method instance is resolved.
+            let world = unsafe { jl_get_world_counter() };
+            let instance = resolve_method_instance(dt)?;
+
+            // Safety: the signature is a concrete tuple type, which is cached and referenced by
+            // the method instance.
+            let signature =
+                unsafe { DataType::wrap_non_null(dt.unwrap_non_null(Private), Private) };
+
+            Ok(ResolvedMethod {
+                func: self,
+                signature,
+                instance: Cell::new(instance),
+                world: Cell::new(world),
+            })
+        })
+    }
+}
+
+/// A method instance resolved for a concrete signature, created by [`Function::resolve`].
+///
+/// Calling a `ResolvedMethod` invokes the method instance directly with `jl_invoke` instead of
+/// looking up the method that must be called every time. The world age is checked every time
+/// it's called, if it has changed since the method instance was resolved it's resolved again.
+/// This ensures methods that have been redefined are called.
+#[cfg(feature = "internal-types")]
+#[julia_version(since = "1.8")]
+pub struct ResolvedMethod<'scope, 'data> {
+    func: Function<'scope, 'data>,
+    signature: DataType<'scope>,
+    instance: Cell<MethodInstance<'scope>>,
+    world: Cell<usize>,
+}
+
+#[cfg(feature = "internal-types")]
+#[julia_version(since = "1.8")]
+impl<'scope, 'data> ResolvedMethod<'scope, 'data> {
+    /// Returns the function.
+    #[inline]
+    pub fn function(&self) -> Function<'scope, 'data> {
+        self.func
+    }
+
+    /// Returns the signature the method instance has been resolved for, i.e.
+    /// `Tuple{typeof(func), arg_types...}`.
+    #[inline]
+    pub fn signature(&self) -> DataType<'scope> {
+        self.signature
+    }
+
+    /// Returns the method instance that was most recently resolved.
+    #[inline]
+    pub fn method_instance(&self) -> MethodInstance<'scope> {
+        self.instance.get()
+    }
+
+    /// Returns the world age the method instance was most recently resolved in.
+    #[inline]
+    pub fn world(&self) -> usize {
+        self.world.get()
+    }
+
+    /// Invoke the method instance with `args`. If an exception is thrown, it's caught and
+    /// returned.
+    ///
+    /// If the world age has changed since the method instance was resolved, it's resolved again
+    /// first. If the types of the arguments don't match the signature, or the method instance
+    /// can't be resolved again, the function is called with dynamic dispatch instead.
+    ///
+    /// Safety: this method lets you call arbitrary Julia functions which can't be checked for
+    /// correctness. More information can be found in the [`safety`] module. This method doesn't
+    /// check if any of the arguments is currently borrowed from Rust.
+    ///
+    /// [`safety`]: crate::safety
+    pub unsafe fn call<'target, 'value, V, Tgt, const N: usize>(
+        &self,
+        target: Tgt,
+        args: V,
+    ) -> ValueResult<'target, 'data, Tgt>
+    where
+        V: Values<'value, 'data, N>,
+        Tgt: Target<'target>,
+    {
+        if !self.matches(args.as_slice(Private)) {
+            return self.func.call(target, args);
+        }
+
+        let world = jl_get_world_counter();
+        if world != self.world.get() {
+            match resolve_method_instance(self.signature) {
+                Ok(instance) => {
+                    self.instance.set(instance);
+                    self.world.set(world);
+                }
+                Err(_) => return self.func.call(target, args),
+            }
+        }
+
+        let func = self.func.unwrap(Private);
+        let instance = self.instance.get().unwrap(Private);
+        let args = args.as_pointers(Private);
+
+        let callback = || {
+            let mut task = NonNull::new_unchecked(jl_sys::jl_get_current_task());
+
+            let last_age = task.as_ref().world_age;
+            task.as_mut().world_age = world;
+            let res = jl_invoke(func, args.as_ptr() as *mut _, args.len() as _, instance);
+            task.as_mut().world_age = last_age;
+
+            NonNull::new_unchecked(res)
+        };
+        let exc = |err: Value| err.unwrap_non_null(Private);
+
+        let res = catch_exceptions(callback, exc);
+        target.result_from_ptr(res, Private)
+    }
+
+    // Returns true if the types of the arguments are the types in the signature.
+    fn matches(&self, args: &[Value]) -> bool {
+        let params = self.signature.parameters();
+        let params = params.data();
+        let params = params.as_slice();
+
+        if params.len() != args.len() + 1 {
+            return false;
+        }
+
+        args.iter()
+            .zip(&params[1..])
+            .all(|(arg, param)| match param {
+                Some(param) => param.ptr().cast() == arg.datatype().unwrap_non_null(Private),
+                None => false,
+            })
+    }
+}
+
+#[cfg(feature = "internal-types")]
+#[julia_version(since = "1.8")]
+impl fmt::Debug for ResolvedMethod<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.signature, f)
+    }
 }
 
+// Resolve the method instance for `signature` in the current world age. `Base._which` is
+// available since Julia 1.8.
+#[cfg(feature = "internal-types")]
+#[julia_version(since = "1.8")]
+fn resolve_method_instance<'scope>(signature: DataType) -> JlrsResult<MethodInstance<'scope>> {
+    signature
+        .unrooted_target()
+        .local_scope::<_, _, 2>(|mut frame| {
+            let which = inline_static_ref!(WHICH_MATCH, Function, "Base._which", &frame);
+            let specialize = inline_static_ref!(
+                SPECIALIZE_METHOD,
+                Function,
+                "Core.Compiler.specialize_method",
+                &frame
+            );
+
+            // Safety: these functions don't mutate their arguments. Method instances are cached by
+            // their method, which is referenced by the method table of the function.
+            unsafe {
+                let method_match = which
+                    .call1(&mut frame, signature.as_value())
+                    .into_jlrs_result()?;
+                let instance = specialize
+                    .call1(&mut frame, method_match)
+                    .into_jlrs_result()?
+                    .cast::<MethodInstance>()?;
+
+                Ok(MethodInstance::wrap_non_null(
+                    instance.unwrap_non_null(Private),
+                    Private,
+                ))
+            }
+        })
+}
+
+/// Iterator over the methods of a [`Function`], created by [`Function::methods`].
+#[cfg(feature = "internal-types")]
+pub struct Methods<'scope> {
+    methods: std::vec::IntoIter<Method<'scope>>,
+}
+
+#[cfg(feature = "internal-types")]
+impl<'scope> Iterator for Methods<'scope> {
+    type Item = Method<'scope>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.methods.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.methods.size_hint()
+    }
+}
+
+#[cfg(feature = "internal-types")]
+impl ExactSizeIterator for Methods<'_> {}
+
 // Safety: The trait is implemented correctly by using the implementation
 // of ValidLayout for FunctionRef
 unsafe impl Typecheck for Function<'_, '_> {
@@ -149,6 +505,19 @@ impl<'data> Call<'data> for Function<'_, 'data> {
     {
         self.as_value().call(target, args)
     }
+
+    #[inline]
+    unsafe fn call_latest<'target, 'value, V, T, const N: usize>(
+        self,
+        target: T,
+        args: V,
+    ) -> ValueResult<'target, 'data, T>
+    where
+        V: Values<'value, 'data, N>,
+        T: Target<'target>,
+    {
+        self.as_value().call_latest(target, args)
+    }
 }
 
 impl<'value, 'data> ProvideKeywords<'value, 'data> for Function<'value, 'data> {
@@ -161,6 +530,132 @@ impl<'value, 'data> ProvideKeywords<'value, 'data> for Function<'value, 'data> {
     }
 }
 
+/// A Julia function with a known signature.
+///
+/// When a `TypedFunction` is created, `Base.hasmethod` is used to check that the function has a
+/// method that can be called with arguments of the types in `Args`. `Args` is a tuple of types
+/// that implement [`IntoArg`], the function returns data that is unboxed as `Ret`.
+///
+/// Example:
+///
+/// ```
+/// # use jlrs::prelude::*;
+/// # use jlrs::util::test::JULIA;
+/// use jlrs::data::managed::function::TypedFunction;
+///
+/// # fn main() {
+/// # JULIA.with(|j| {
+/// # let mut julia = j.borrow_mut();
+/// # let mut frame = StackFrame::new();
+/// # let mut julia = julia.instance(&mut frame);
+/// julia.scope(|mut frame| unsafe {
+///     let func = Module::base(&frame).function(&frame, "+")?.as_managed();
+///     let add = TypedFunction::<(f64, f64), f64>::new(func)?;
+///     assert_eq!(add.call(&mut frame, (1.0, 2.0))?, 3.0);
+///
+///     // `sqrt` has no method that takes two arguments.
+///     let sqrt = Module::base(&frame).function(&frame, "sqrt")?.as_managed();
+///     assert!(TypedFunction::<(f64, f64), f64>::new(sqrt).is_err());
+///
+///     Ok(())
+/// })
+/// # .unwrap();
+/// # });
+/// # }
+/// ```
+///
+/// [`IntoArg`]: crate::args::IntoArg
+#[repr(transparent)]
+pub struct TypedFunction<'scope, 'data, Args, Ret> {
+    func: Function<'scope, 'data>,
+    _marker: PhantomData<fn(Args) -> Ret>,
+}
+
+impl<'scope, 'data, Args, Ret> TypedFunction<'scope, 'data, Args, Ret>
+where
+    Args: TypedArgs<'data>,
+    Ret: Unbox + Typecheck,
+{
+    /// Convert `func` to a `TypedFunction`. An error is returned if `func` has no method that can
+    /// be called with arguments of the types in `Args`.
+    pub fn new(func: Function<'scope, 'data>) -> JlrsResult<Self> {
+        func.unrooted_target().local_scope::<_, _, 2>(|mut frame| {
+            let hasmethod = inline_static_ref!(HASMETHOD, Function, "Base.hasmethod", &frame);
+            let signature = Args::Signature::construct_type(&mut frame);
+
+            // Safety: `hasmethod` doesn't mutate its arguments.
+            let has_method = unsafe {
+                hasmethod
+                    .call2(&mut frame, func.as_value(), signature)
+                    .into_jlrs_result()?
+                    .unbox::<bool>()?
+                    .as_bool()
+            };
+
+            if !has_method {
+                Err(TypeError::NoMatchingMethod {
+                    function: func.display_string_or(CANNOT_DISPLAY_VALUE),
+                    signature: signature.display_string_or(CANNOT_DISPLAY_VALUE),
+                })?
+            }
+
+            Ok(TypedFunction {
+                func,
+                _marker: PhantomData,
+            })
+        })
+    }
+
+    /// Convert `func` to a `TypedFunction` without checking if a matching method exists.
+    ///
+    /// Safety: `func` must have a method that can be called with arguments of the types in `Args`.
+    #[inline]
+    pub unsafe fn new_unchecked(func: Function<'scope, 'data>) -> Self {
+        TypedFunction {
+            func,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the untyped function.
+    #[inline]
+    pub fn function(self) -> Function<'scope, 'data> {
+        self.func
+    }
+
+    /// Call the function with `args` and unbox the result as `Ret`. If an exception is thrown or
+    /// the result is not a `Ret` an error is returned.
+    ///
+    /// Safety: this method lets you call arbitrary Julia functions which can't be checked for
+    /// correctness. More information can be found in the [`safety`] module. This method doesn't
+    /// check if any of the arguments is currently borrowed from Rust.
+    ///
+    /// [`safety`]: crate::safety
+    #[inline]
+    pub unsafe fn call<'target>(
+        self,
+        target: impl Target<'target>,
+        args: Args,
+    ) -> JlrsResult<Ret::Output> {
+        self.func.call_typed::<Ret, _>(target, args)
+    }
+}
+
+impl<Args, Ret> Clone for TypedFunction<'_, '_, Args, Ret> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Args, Ret> Copy for TypedFunction<'_, '_, Args, Ret> {}
+
+impl<Args, Ret> fmt::Debug for TypedFunction<'_, '_, Args, Ret> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.func, f)
+    }
+}
+
 /// A reference to an [`Function`] that has not been explicitly rooted.
 pub type FunctionRef<'scope, 'data> = Ref<'scope, 'data, Function<'scope, 'data>>;
 
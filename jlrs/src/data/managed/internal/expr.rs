@@ -1,17 +1,36 @@
 //! Managed type for `Expr`.
+//!
+//! Besides accessing the contents of existing expressions, Julia code can be constructed with
+//! [`Expr::new`] and evaluated with [`Module::eval_expr`]. Symbols that are used as arguments
+//! are identifiers, e.g. the names of variables and functions. Other data can be interpolated
+//! as a literal by wrapping it in a `QuoteNode` with [`Expr::quote_node`], which avoids
+//! generating and escaping code as a string.
+//!
+//! [`Module::eval_expr`]: crate::data::managed::module::Module::eval_expr
 
 use std::{marker::PhantomData, ptr::NonNull};
 
 use jl_sys::{jl_expr_t, jl_expr_type};
 
 use crate::{
-    data::managed::{
-        array::{ArrayData, ArrayRef},
-        private::ManagedPriv,
-        symbol::Symbol,
-        Ref,
+    args::private::ValuesPriv,
+    call::{Call, ProvideKeywords},
+    convert::{into_jlrs_result::IntoJlrsResult, to_symbol::ToSymbol},
+    data::{
+        layout::nothing::Nothing,
+        managed::{
+            array::{ArrayData, ArrayRef},
+            function::Function,
+            module::Module,
+            private::ManagedPriv,
+            string::JuliaString,
+            symbol::Symbol,
+            value::{Value, ValueData, ValueRef},
+            Managed, Ref,
+        },
     },
-    impl_julia_typecheck,
+    error::{JlrsResult, ParseError, CANNOT_DISPLAY_VALUE},
+    impl_julia_typecheck, inline_static_ref,
     memory::target::{Target, TargetResult},
     private::Private,
 };
@@ -53,6 +72,213 @@ impl<'scope> Expr<'scope> {
     }
 }
 
+impl Expr<'_> {
+    /// Create a new expression with the given head and arguments, e.g.
+    /// `Expr::new(&mut frame, "call", &[func, arg])` creates the expression `func(arg)`.
+    ///
+    /// Symbols in `args` are treated as identifiers, other data is interpolated as is. Use
+    /// [`Expr::quote_node`] to interpolate a symbol or expression as a literal.
+    pub fn new<'target, H, T>(
+        target: T,
+        head: H,
+        args: &[Value<'_, 'static>],
+    ) -> ExprData<'target, T>
+    where
+        H: ToSymbol,
+        T: Target<'target>,
+    {
+        // Safety: the arguments are valid, the constructor can't throw if the head is a Symbol.
+        unsafe {
+            let head = head.to_symbol_priv(Private).as_value();
+            let args = args.into_extended_with_start([head], Private);
+            let expr = inline_static_ref!(EXPR, Value, "Core.Expr", &target)
+                .call_unchecked(&target, args.as_ref());
+
+            target.data_from_ptr(expr.ptr().cast(), Private)
+        }
+    }
+
+    /// Wrap `value` in a `QuoteNode`.
+    ///
+    /// A `QuoteNode` evaluates to the value it contains, it can be used to interpolate any value
+    /// as a literal. Symbols and expressions that are wrapped in a `QuoteNode` aren't evaluated.
+    pub fn quote_node<'target, T>(
+        target: T,
+        value: Value<'_, 'static>,
+    ) -> ValueData<'target, 'static, T>
+    where
+        T: Target<'target>,
+    {
+        // Safety: the constructor can't throw.
+        unsafe {
+            inline_static_ref!(QUOTE_NODE, Value, "Core.QuoteNode", &target)
+                .call_unchecked(target, [value])
+        }
+    }
+
+    /// Create a `GlobalRef` to the global `name` in `module`.
+    ///
+    /// A `GlobalRef` refers to a global in a specific module, unlike a symbol it's not resolved
+    /// relative to the module the expression is evaluated in.
+    pub fn global_ref<'target, N, T>(
+        target: T,
+        module: Module,
+        name: N,
+    ) -> ValueData<'target, 'static, T>
+    where
+        N: ToSymbol,
+        T: Target<'target>,
+    {
+        // Safety: the constructor can't throw.
+        unsafe {
+            let name = name.to_symbol_priv(Private).as_value();
+            inline_static_ref!(GLOBAL_REF, Value, "Core.GlobalRef", &target)
+                .call_unchecked(target, [module.as_value(), name])
+        }
+    }
+
+    /// Create a `LineNumberNode`, which sets the line number and file of the expressions that
+    /// follow it in a block.
+    pub fn line_number_node<'target, F, T>(
+        target: T,
+        line: usize,
+        file: F,
+    ) -> ValueData<'target, 'static, T>
+    where
+        F: ToSymbol,
+        T: Target<'target>,
+    {
+        // Safety: the constructor can't throw.
+        unsafe {
+            target
+                .with_local_scope::<_, _, 1>(|target, mut frame| {
+                    let line = Value::new(&mut frame, line as isize);
+                    let file = file.to_symbol_priv(Private).as_value();
+                    Ok(
+                        inline_static_ref!(LINE_NUMBER_NODE, Value, "Core.LineNumberNode", &frame)
+                            .call_unchecked(target, [line, file]),
+                    )
+                })
+                .unwrap()
+        }
+    }
+
+    /// Parse `code` without evaluating it.
+    ///
+    /// The code is parsed one top-level expression at a time with `Meta.parse`, the parsed
+    /// expressions are returned as the arguments of a `:toplevel` expression. If an expression
+    /// can't be parsed a `ParseError` is returned, which contains the message returned by the
+    /// parser and the position where that top-level expression starts.
+    pub fn parse<'target, C, T>(target: T, code: C) -> JlrsResult<ExprData<'target, T>>
+    where
+        C: AsRef<str>,
+        T: Target<'target>,
+    {
+        let code = code.as_ref();
+
+        // Safety: parsing code doesn't evaluate it, all data is rooted while it's used.
+        unsafe {
+            target.with_local_scope::<_, _, 3>(|target, mut frame| {
+                let code_jl = JuliaString::new(&mut frame, code).as_value();
+
+                let toplevel = Symbol::new(&frame, "toplevel").as_value();
+                let expr = inline_static_ref!(EXPR, Value, "Core.Expr", &frame)
+                    .call1(&mut frame, toplevel)
+                    .into_jlrs_result()?
+                    .cast_unchecked::<Expr>();
+                let args = expr.args(&frame).unwrap().as_managed().as_value();
+
+                let greedy = Symbol::new(&frame, "greedy");
+                let raise = Symbol::new(&frame, "raise");
+                let t = Value::true_v(&frame);
+                let f = Value::false_v(&frame);
+                let kws = Value::new_named_tuple(&mut frame, &[(greedy, t), (raise, f)]);
+
+                let parse = inline_static_ref!(PARSE, Function, "Base.Meta.parse", &frame);
+                let push = inline_static_ref!(PUSH, Function, "Base.push!", &frame);
+
+                let mut pos = 1;
+                while pos <= code.len() {
+                    let next = frame.local_scope::<_, _, 3>(|mut frame| {
+                        let start = Value::new(&mut frame, pos as isize);
+                        let res = parse
+                            .provide_keywords(kws)?
+                            .call2(&mut frame, code_jl, start)
+                            .into_jlrs_result()?;
+
+                        let next = res.field_accessor().field(1)?.access::<isize>()? as usize;
+                        let stmt = res
+                            .field_accessor()
+                            .field(0)?
+                            .access::<ValueRef>()?
+                            .as_value();
+
+                        // `nothing` is returned if there's nothing left to parse.
+                        if stmt.is::<Nothing>() {
+                            return Ok(None);
+                        }
+
+                        if let Ok(ex) = stmt.cast::<Expr>() {
+                            let head = ex.head().map(|h| h.as_str().unwrap_or(""));
+                            if let Some(head @ ("error" | "incomplete")) = head {
+                                let msg = Self::parse_error_message(&frame, ex);
+                                let (expr_line, expr_column) = Self::position(code, pos - 1);
+                                let incomplete = head == "incomplete";
+                                Err(ParseError::new(msg, expr_line, expr_column, incomplete))?
+                            }
+                        }
+
+                        push.call2(&mut frame, args, stmt).into_jlrs_result()?;
+                        Ok(Some(next))
+                    })?;
+
+                    match next {
+                        Some(next) if next > pos => pos = next,
+                        _ => break,
+                    }
+                }
+
+                Ok(target.data_from_ptr(expr.unwrap_non_null(Private), Private))
+            })
+        }
+    }
+
+    // Returns the message of an `:error` or `:incomplete` expression.
+    unsafe fn parse_error_message<'target, T>(target: &T, ex: Expr) -> String
+    where
+        T: Target<'target>,
+    {
+        let first = inline_static_ref!(FIRST, Function, "Base.first", target);
+        let args = ex.args(target).unwrap().as_managed().as_value();
+        match first.call1(target, args) {
+            Ok(msg) => {
+                let msg = msg.as_value();
+                match msg.cast::<JuliaString>() {
+                    Ok(s) => s.as_str().unwrap_or(CANNOT_DISPLAY_VALUE).to_string(),
+                    Err(_) => msg.error_string_or(CANNOT_DISPLAY_VALUE),
+                }
+            }
+            Err(_) => String::from(CANNOT_DISPLAY_VALUE),
+        }
+    }
+
+    // Converts the byte offset of an expression to the line and column of its first character.
+    fn position(code: &str, offset: usize) -> (usize, usize) {
+        let rest = &code[offset..];
+        let start = offset
+            + rest
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or(rest.len());
+
+        let before = &code[..start];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+        let column = before[line_start..].chars().count() + 1;
+
+        (line, column)
+    }
+}
+
 impl_julia_typecheck!(Expr<'scope>, jl_expr_type, 'scope);
 impl_debug!(Expr<'_>);
 
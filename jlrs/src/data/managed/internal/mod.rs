@@ -1,7 +1,7 @@
 //! Managed types that are mostly used internally in Julia.
 //!
 //! To use these types you must enable the `internal-types` feature.
-#[cfg(feature = "julia-1-10")]
+#[cfg(any(feature = "julia-1-10", feature = "julia-1-11"))]
 pub mod binding;
 pub mod code_instance;
 pub mod expr;
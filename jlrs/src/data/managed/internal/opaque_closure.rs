@@ -185,6 +185,18 @@ impl<'data> Call<'data> for OpaqueClosure<'_> {
     {
         self.as_value().call(target, args)
     }
+
+    unsafe fn call_latest<'target, 'value, V, T, const N: usize>(
+        self,
+        target: T,
+        args: V,
+    ) -> ValueResult<'target, 'data, T>
+    where
+        V: Values<'value, 'data, N>,
+        T: Target<'target>,
+    {
+        self.as_value().call_latest(target, args)
+    }
 }
 
 /// A reference to an [`OpaqueClosure`] that has not been explicitly rooted.
@@ -0,0 +1,738 @@
+//! Managed type for `GenericMemory`, the fixed-size buffer that backs arrays since Julia 1.11.
+//!
+//! Since Julia 1.11 the elements of an `Array` are stored in a `Memory{T}`, which is an alias of
+//! `GenericMemory{:not_atomic, T, Core.CPU}`. Unlike an array, a memory buffer is always
+//! one-dimensional and can't be resized. The buffer that backs an array can be accessed with
+//! [`Array::memory`].
+//!
+//! A reference to an element of a buffer, Julia's `MemoryRef{T}`, is represented by
+//! [`GenericMemoryRef`]. The name `MemoryRef` is used for a reference to a [`Memory`] that has
+//! not been explicitly rooted, like the `Ref` aliases of all other managed types.
+//!
+//! This module is only available if the `julia-1-11` feature is enabled.
+//!
+//! [`Array::memory`]: crate::data::managed::array::Array::memory
+
+use std::{ffi::c_void, marker::PhantomData, ptr::NonNull, slice};
+
+use jl_sys::{
+    jl_alloc_genericmemory, jl_genericmemory_elsize, jl_genericmemory_isbitsunion,
+    jl_genericmemory_isboxed, jl_genericmemory_t, jl_genericmemoryref_t, jl_memoryrefget,
+    jl_memoryrefindex, jl_memoryrefset,
+};
+
+use super::{
+    union_all::UnionAll,
+    value::{ValueData, ValueRef},
+    Ref,
+};
+use crate::{
+    catch::catch_exceptions,
+    convert::{
+        ccall_types::{CCallArg, CCallReturn},
+        unbox::Unbox,
+    },
+    data::{
+        layout::valid_layout::{ValidField, ValidLayout},
+        managed::{
+            datatype::DataType, private::ManagedPriv, symbol::Symbol, union::Union, value::Value,
+            Managed,
+        },
+        types::{construct_type::ConstructType, typecheck::Typecheck},
+    },
+    error::{AccessError, JlrsResult, TypeError, CANNOT_DISPLAY_TYPE},
+    inline_static_ref,
+    memory::target::{unrooted::Unrooted, Target, TargetResult, TargetType},
+    private::Private,
+};
+
+/// A fixed-size, one-dimensional buffer of elements.
+///
+/// Like an [`Array`], each element is either stored as a [`Value`] or inline. If the inline data
+/// is a bits union, the flag indicating the active variant is stored after the elements.
+///
+/// [`Array`]: crate::data::managed::array::Array
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+pub struct Memory<'scope, 'data>(
+    NonNull<jl_genericmemory_t>,
+    PhantomData<&'scope ()>,
+    PhantomData<&'data mut ()>,
+);
+
+impl Memory<'_, '_> {
+    /// Allocate a new `Memory{T}` that can hold `len` elements.
+    ///
+    /// If the size is too large, Julia will throw an error. This error is caught and returned.
+    pub fn new<'target, T, Tgt>(target: Tgt, len: usize) -> MemoryResult<'target, 'static, Tgt>
+    where
+        T: ConstructType,
+        Tgt: Target<'target>,
+    {
+        // Safety: The memory type is rooted until the memory has been allocated, all C API
+        // functions are called with valid data.
+        unsafe {
+            let callback = || {
+                let memory_type = MemoryType::<T>::construct_type(&target).as_value();
+                let memory = jl_alloc_genericmemory(memory_type.unwrap(Private), len);
+                NonNull::new_unchecked(memory)
+            };
+
+            let exc = |err: Value| err.unwrap_non_null(Private);
+            let res = catch_exceptions(callback, exc);
+
+            target.result_from_ptr(res, Private)
+        }
+    }
+
+    /// Allocate a new `Memory{ty}` that can hold `len` elements.
+    ///
+    /// If the size is too large or if the type is invalid, Julia will throw an error. This error
+    /// is caught and returned.
+    pub fn new_for<'target, Tgt>(
+        target: Tgt,
+        len: usize,
+        ty: Value,
+    ) -> MemoryResult<'target, 'static, Tgt>
+    where
+        Tgt: Target<'target>,
+    {
+        // Safety: The memory type is rooted until the memory has been allocated, all C API
+        // functions are called with valid data.
+        unsafe {
+            let callback = || {
+                let memory_type = inline_static_ref!(MEMORY, UnionAll, "Core.Memory", &target)
+                    .as_value()
+                    .apply_type_unchecked(&target, [ty])
+                    .as_value();
+                let memory = jl_alloc_genericmemory(memory_type.unwrap(Private), len);
+                NonNull::new_unchecked(memory)
+            };
+
+            let exc = |err: Value| err.unwrap_non_null(Private);
+            let res = catch_exceptions(callback, exc);
+
+            target.result_from_ptr(res, Private)
+        }
+    }
+}
+
+impl<'scope, 'data> Memory<'scope, 'data> {
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(self) -> usize {
+        // Safety: the pointer points to valid data.
+        unsafe { self.0.as_ref().length }
+    }
+
+    /// Returns `true` if the buffer has no elements.
+    #[inline]
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the type of the elements.
+    #[inline]
+    pub fn element_type(self) -> Value<'scope, 'static> {
+        // Safety: the element type is the second parameter of `GenericMemory`.
+        unsafe {
+            self.as_value().datatype().parameters().data().as_slice()[1]
+                .unwrap()
+                .as_value()
+        }
+    }
+
+    /// Returns the size of the elements.
+    #[inline]
+    pub fn element_size(self) -> usize {
+        // Safety: the pointer points to valid data.
+        unsafe { jl_genericmemory_elsize(self.unwrap(Private)) }
+    }
+
+    /// Returns `true` if the elements are stored inline.
+    #[inline]
+    pub fn is_inline(self) -> bool {
+        // Safety: the pointer points to valid data.
+        unsafe { !jl_genericmemory_isboxed(self.unwrap(Private)) }
+    }
+
+    /// Returns `true` if the elements are stored inline and the element type is a union type.
+    #[inline]
+    pub fn is_union(self) -> bool {
+        // Safety: the pointer points to valid data.
+        unsafe { jl_genericmemory_isbitsunion(self.unwrap(Private)) }
+    }
+
+    /// Returns `true` if the layout of the elements is compatible with `T`.
+    #[inline]
+    pub fn contains<T: ValidField>(self) -> bool {
+        T::valid_field(self.element_type())
+    }
+
+    /// Convert `self` to a `TypedMemory<T>`. Returns an error if the layout of the elements is
+    /// incompatible with `T` or if the element type is a bits union.
+    pub fn as_typed<T: ValidField>(self) -> JlrsResult<TypedMemory<'scope, 'data, T>> {
+        if !self.contains::<T>() || self.is_union() {
+            Err(AccessError::InvalidLayout {
+                value_type: self.element_type().display_string_or(CANNOT_DISPLAY_TYPE),
+            })?
+        }
+
+        // Safety: the layout has been checked.
+        unsafe { Ok(self.as_typed_unchecked()) }
+    }
+
+    /// Convert `self` to a `TypedMemory<T>` without checking if the layout is compatible.
+    ///
+    /// Safety: the layout of the elements must be compatible with `T` and the element type must
+    /// not be a bits union.
+    #[inline]
+    pub unsafe fn as_typed_unchecked<T: ValidField>(self) -> TypedMemory<'scope, 'data, T> {
+        TypedMemory::wrap_non_null(self.0, Private)
+    }
+
+    /// Returns the element at `index`, or `None` if it's an undefined reference. Elements that
+    /// are stored inline are boxed, an error is returned if the index is out of bounds.
+    pub fn get<'target, Tgt>(
+        self,
+        target: Tgt,
+        index: usize,
+    ) -> JlrsResult<Option<ValueData<'target, 'data, Tgt>>>
+    where
+        Tgt: Target<'target>,
+    {
+        let len = self.len();
+        if index >= len {
+            Err(AccessError::OutOfBoundsMemory { idx: index, len })?
+        }
+
+        // Safety: the index is in bounds, undefined references are checked before they're
+        // returned.
+        unsafe {
+            if !self.is_inline() {
+                let ptr = self.data_ptr().cast::<Option<ValueRef>>().add(index);
+                return Ok(ptr.read().map(|v| v.root(target)));
+            }
+
+            let r = jl_memoryrefindex(self.memory_ref_at_start(), index);
+            let value = jl_memoryrefget(r, 0);
+            Ok(Some(
+                target.data_from_ptr(NonNull::new_unchecked(value), Private),
+            ))
+        }
+    }
+
+    /// Set the element at `index` to `value`. An error is returned if the index is out of bounds
+    /// or if `value` is not an instance of the element type.
+    ///
+    /// Safety: mutating Julia data is generally unsafe because it can't be guaranteed mutating
+    /// this value is allowed.
+    pub unsafe fn set(self, index: usize, value: Value<'_, 'data>) -> JlrsResult<()> {
+        let len = self.len();
+        if index >= len {
+            Err(AccessError::OutOfBoundsMemory { idx: index, len })?
+        }
+
+        let element_type = self.element_type();
+        if !value.isa(element_type) {
+            Err(TypeError::IncompatibleType {
+                element_type: element_type.display_string_or(CANNOT_DISPLAY_TYPE),
+                value_type: value.datatype().display_string_or(CANNOT_DISPLAY_TYPE),
+            })?
+        }
+
+        let r = jl_memoryrefindex(self.memory_ref_at_start(), index);
+        jl_memoryrefset(r, value.unwrap(Private), 0);
+        Ok(())
+    }
+
+    /// Returns a reference to the element at `index`, an error is returned if the index is out
+    /// of bounds.
+    pub fn memory_ref(self, index: usize) -> JlrsResult<GenericMemoryRef<'scope, 'data>> {
+        let len = self.len();
+        if index >= len {
+            Err(AccessError::OutOfBoundsMemory { idx: index, len })?
+        }
+
+        // Safety: the index is in bounds.
+        unsafe {
+            let r = jl_memoryrefindex(self.memory_ref_at_start(), index);
+            Ok(GenericMemoryRef::from_raw(r))
+        }
+    }
+
+    #[inline]
+    pub(crate) fn data_ptr(self) -> *mut c_void {
+        // Safety: the pointer points to valid data.
+        unsafe { self.0.as_ref().ptr }
+    }
+
+    // The offset of a reference to a bits-union or zero-sized element is stored as an index
+    // rather than as a pointer.
+    #[inline]
+    fn stores_offset(self) -> bool {
+        self.is_union() || self.element_size() == 0
+    }
+
+    #[inline]
+    unsafe fn memory_ref_at_start(self) -> jl_genericmemoryref_t {
+        let ptr_or_offset = if self.stores_offset() {
+            std::ptr::null_mut()
+        } else {
+            self.data_ptr()
+        };
+
+        jl_genericmemoryref_t {
+            ptr_or_offset,
+            mem: self.unwrap(Private),
+        }
+    }
+}
+
+// Safety: the type name of the type must be the type name of `GenericMemory`.
+unsafe impl Typecheck for Memory<'_, '_> {
+    #[inline]
+    fn typecheck(t: DataType) -> bool {
+        let unrooted = unsafe { Unrooted::new() };
+        let base = inline_static_ref!(GENERIC_MEMORY, UnionAll, "Core.GenericMemory", &unrooted);
+        if t.type_name() != base.base_type().type_name() {
+            return false;
+        }
+
+        // `AtomicMemory` shares the type name, the first parameter is `:not_atomic` for `Memory`.
+        // Safety: the first parameter of `GenericMemory` is a symbol.
+        unsafe {
+            let kind = t.parameters().data().as_slice()[0].unwrap().as_value();
+            kind == Symbol::new(&unrooted, "not_atomic")
+        }
+    }
+}
+
+impl_debug!(Memory<'_, '_>);
+
+impl<'scope, 'data> ManagedPriv<'scope, 'data> for Memory<'scope, 'data> {
+    type Wraps = jl_genericmemory_t;
+    type TypeConstructorPriv<'target, 'da> = Memory<'target, 'da>;
+    const NAME: &'static str = "Memory";
+
+    // Safety: `inner` must not have been freed yet, the result must never be
+    // used after the GC might have freed it.
+    #[inline]
+    unsafe fn wrap_non_null(inner: NonNull<Self::Wraps>, _: Private) -> Self {
+        Self(inner, PhantomData, PhantomData)
+    }
+
+    #[inline]
+    fn unwrap_non_null(self, _: Private) -> NonNull<Self::Wraps> {
+        self.0
+    }
+}
+
+impl_ccall_arg_managed!(Memory, 2);
+
+unsafe impl ConstructType for Memory<'_, '_> {
+    type Static = Memory<'static, 'static>;
+
+    const CACHEABLE: bool = false;
+
+    #[inline]
+    fn construct_type_uncached<'target, Tgt>(target: Tgt) -> ValueData<'target, 'static, Tgt>
+    where
+        Tgt: Target<'target>,
+    {
+        Self::base_type(&target).unwrap().root(target)
+    }
+
+    #[inline]
+    fn base_type<'target, Tgt>(target: &Tgt) -> Option<Value<'target, 'static>>
+    where
+        Tgt: Target<'target>,
+    {
+        Some(inline_static_ref!(MEMORY, Value, "Core.Memory", target))
+    }
+}
+
+/// Exactly the same as [`Memory`], except it has an explicit element type `T`.
+///
+/// The elements of a `TypedMemory` are never a bits union.
+#[repr(transparent)]
+pub struct TypedMemory<'scope, 'data, T>(
+    NonNull<jl_genericmemory_t>,
+    PhantomData<&'scope ()>,
+    PhantomData<&'data mut ()>,
+    PhantomData<T>,
+);
+
+impl<T: ConstructType + ValidField> TypedMemory<'_, '_, T> {
+    /// Allocate a new `Memory{T}` that can hold `len` elements.
+    ///
+    /// If the size is too large, Julia will throw an error. This error is caught and returned.
+    pub fn new<'target, Tgt>(target: Tgt, len: usize) -> TypedMemoryResult<'target, 'static, Tgt, T>
+    where
+        Tgt: Target<'target>,
+    {
+        // Safety: The memory type is rooted until the memory has been allocated, all C API
+        // functions are called with valid data.
+        unsafe {
+            let callback = || {
+                let memory_type = MemoryType::<T>::construct_type(&target).as_value();
+                let memory = jl_alloc_genericmemory(memory_type.unwrap(Private), len);
+                NonNull::new_unchecked(memory)
+            };
+
+            let exc = |err: Value| err.unwrap_non_null(Private);
+            let res = catch_exceptions(callback, exc);
+
+            target.result_from_ptr(res, Private)
+        }
+    }
+}
+
+impl<'scope, 'data, T: ValidField> TypedMemory<'scope, 'data, T> {
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(self) -> usize {
+        self.as_memory().len()
+    }
+
+    /// Returns `true` if the buffer has no elements.
+    #[inline]
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the type of the elements.
+    #[inline]
+    pub fn element_type(self) -> Value<'scope, 'static> {
+        self.as_memory().element_type()
+    }
+
+    /// Convert `self` to `Memory`.
+    #[inline]
+    pub fn as_memory(self) -> Memory<'scope, 'data> {
+        // Safety: the pointer points to valid data.
+        unsafe { Memory::wrap_non_null(self.0, Private) }
+    }
+
+    /// Returns the elements as a slice.
+    ///
+    /// Safety: the elements must not be mutated while the slice exists.
+    #[inline]
+    pub unsafe fn as_slice<'borrow>(&'borrow self) -> &'borrow [T] {
+        slice::from_raw_parts(self.as_memory().data_ptr().cast(), self.len())
+    }
+
+    /// Returns the elements as a mutable slice.
+    ///
+    /// Safety: mutating Julia data is generally unsafe because it can't be guaranteed mutating
+    /// this value is allowed. The elements must not be accessed by Julia while the slice exists.
+    #[inline]
+    pub unsafe fn as_mut_slice<'borrow>(&'borrow mut self) -> &'borrow mut [T] {
+        slice::from_raw_parts_mut(self.as_memory().data_ptr().cast(), self.len())
+    }
+
+    /// Copy the elements to a `Vec`.
+    #[inline]
+    pub fn to_vec(self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        // Safety: the slice only exists while the elements are copied.
+        unsafe { self.as_slice().to_vec() }
+    }
+}
+
+impl<T: ValidField> Clone for TypedMemory<'_, '_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ValidField> Copy for TypedMemory<'_, '_, T> {}
+
+// Safety: the type must be a `GenericMemory` whose elements have a layout compatible with `T`.
+unsafe impl<T: ValidField> Typecheck for TypedMemory<'_, '_, T> {
+    #[inline]
+    fn typecheck(t: DataType) -> bool {
+        // Safety: the element type is the second parameter of `GenericMemory`.
+        unsafe {
+            t.is::<Memory>()
+                && !t.parameters().data().as_slice()[1]
+                    .unwrap()
+                    .as_value()
+                    .is::<Union>()
+                && T::valid_field(t.parameters().data().as_slice()[1].unwrap().as_value())
+        }
+    }
+}
+
+impl<T: ValidField> std::fmt::Debug for TypedMemory<'_, '_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.display_string() {
+            Ok(s) => f.write_str(&s),
+            Err(e) => f.write_fmt(format_args!("<Cannot display value: {}>", e)),
+        }
+    }
+}
+
+impl<'scope, 'data, T: ValidField> ManagedPriv<'scope, 'data> for TypedMemory<'scope, 'data, T> {
+    type Wraps = jl_genericmemory_t;
+    type TypeConstructorPriv<'target, 'da> = TypedMemory<'target, 'da, T>;
+    const NAME: &'static str = "Memory";
+
+    // Safety: `inner` must not have been freed yet, the result must never be
+    // used after the GC might have freed it. T must be correct
+    #[inline]
+    unsafe fn wrap_non_null(inner: NonNull<Self::Wraps>, _: Private) -> Self {
+        Self(inner, PhantomData, PhantomData, PhantomData)
+    }
+
+    #[inline]
+    fn unwrap_non_null(self, _: Private) -> NonNull<Self::Wraps> {
+        self.0
+    }
+}
+
+unsafe impl<T: ValidField + ConstructType> ConstructType for TypedMemory<'_, '_, T> {
+    type Static = MemoryType<T::Static>;
+
+    #[inline]
+    fn construct_type_uncached<'target, Tgt>(target: Tgt) -> ValueData<'target, 'static, Tgt>
+    where
+        Tgt: Target<'target>,
+    {
+        MemoryType::<T>::construct_type_uncached(target)
+    }
+
+    #[inline]
+    fn base_type<'target, Tgt>(target: &Tgt) -> Option<Value<'target, 'static>>
+    where
+        Tgt: Target<'target>,
+    {
+        Memory::base_type(target)
+    }
+}
+
+/// Type constructor for `Memory{T}`.
+pub struct MemoryType<T>(PhantomData<T>);
+
+unsafe impl<T: ConstructType> ConstructType for MemoryType<T> {
+    type Static = MemoryType<T::Static>;
+
+    fn construct_type_uncached<'target, Tgt>(target: Tgt) -> ValueData<'target, 'static, Tgt>
+    where
+        Tgt: Target<'target>,
+    {
+        target
+            .with_local_scope::<_, _, 1>(|target, mut frame| {
+                let param = T::construct_type(&mut frame);
+                unsafe {
+                    let applied = Self::base_type(&frame)
+                        .unwrap()
+                        .apply_type_unchecked(&mut frame, [param]);
+                    Ok(applied.root(target))
+                }
+            })
+            .unwrap()
+    }
+
+    #[inline]
+    fn base_type<'target, Tgt>(target: &Tgt) -> Option<Value<'target, 'static>>
+    where
+        Tgt: Target<'target>,
+    {
+        Memory::base_type(target)
+    }
+}
+
+/// A reference to an element of a [`Memory`], Julia's `MemoryRef{T}`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct GenericMemoryRef<'scope, 'data> {
+    ptr_or_offset: *mut c_void,
+    mem: Memory<'scope, 'data>,
+}
+
+impl<'scope, 'data> GenericMemoryRef<'scope, 'data> {
+    // Safety: the reference must point to valid data, the memory must be rooted.
+    #[inline]
+    pub(crate) unsafe fn from_raw(r: jl_genericmemoryref_t) -> Self {
+        GenericMemoryRef {
+            ptr_or_offset: r.ptr_or_offset,
+            mem: Memory::wrap_non_null(NonNull::new_unchecked(r.mem), Private),
+        }
+    }
+
+    /// Returns the memory this reference points into.
+    #[inline]
+    pub fn memory(self) -> Memory<'scope, 'data> {
+        self.mem
+    }
+
+    /// Returns the index of the referenced element.
+    #[inline]
+    pub fn index(self) -> usize {
+        if self.mem.stores_offset() {
+            self.ptr_or_offset as usize
+        } else {
+            let offset = self.ptr_or_offset as usize - self.mem.data_ptr() as usize;
+            offset / self.mem.element_size()
+        }
+    }
+
+    /// Returns the referenced element, or `None` if it's an undefined reference.
+    #[inline]
+    pub fn get<'target, Tgt>(
+        self,
+        target: Tgt,
+    ) -> JlrsResult<Option<ValueData<'target, 'data, Tgt>>>
+    where
+        Tgt: Target<'target>,
+    {
+        self.mem.get(target, self.index())
+    }
+}
+
+// Safety: the layout of `GenericMemoryRef` matches the layout of `jl_genericmemoryref_t`.
+unsafe impl ValidLayout for GenericMemoryRef<'_, '_> {
+    #[inline]
+    fn valid_layout(ty: Value) -> bool {
+        if let Ok(dt) = ty.cast::<DataType>() {
+            dt.is::<GenericMemoryRef>()
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn type_object<'target, Tgt: Target<'target>>(target: &Tgt) -> Value<'target, 'static> {
+        inline_static_ref!(GENERIC_MEMORY_REF, Value, "Core.GenericMemoryRef", target)
+    }
+}
+
+unsafe impl ValidField for GenericMemoryRef<'_, '_> {
+    #[inline]
+    fn valid_field(ty: Value) -> bool {
+        <Self as ValidLayout>::valid_layout(ty)
+    }
+}
+
+// Safety: the type name of the type must be the type name of `GenericMemoryRef`.
+unsafe impl Typecheck for GenericMemoryRef<'_, '_> {
+    #[inline]
+    fn typecheck(t: DataType) -> bool {
+        let unrooted = unsafe { Unrooted::new() };
+        let base = inline_static_ref!(
+            GENERIC_MEMORY_REF,
+            UnionAll,
+            "Core.GenericMemoryRef",
+            &unrooted
+        );
+        t.type_name() == base.base_type().type_name()
+    }
+}
+
+unsafe impl Unbox for GenericMemoryRef<'_, '_> {
+    type Output = Self;
+}
+
+/// A reference to a [`Memory`] that has not been explicitly rooted.
+pub type MemoryRef<'scope, 'data> = Ref<'scope, 'data, Memory<'scope, 'data>>;
+
+/// A [`MemoryRef`] with static lifetimes. This is a useful shorthand for signatures of
+/// `ccall`able functions that return a [`Memory`].
+pub type MemoryRet = Ref<'static, 'static, Memory<'static, 'static>>;
+
+unsafe impl ValidLayout for MemoryRef<'_, '_> {
+    #[inline]
+    fn valid_layout(v: Value) -> bool {
+        if let Ok(dt) = v.cast::<DataType>() {
+            dt.is::<Memory>()
+        } else if let Ok(ua) = v.cast::<UnionAll>() {
+            ua.base_type().is::<Memory>()
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn type_object<'target, Tgt: Target<'target>>(target: &Tgt) -> Value<'target, 'static> {
+        Memory::base_type(target).unwrap()
+    }
+
+    const IS_REF: bool = true;
+}
+
+unsafe impl ValidField for Option<MemoryRef<'_, '_>> {
+    #[inline]
+    fn valid_field(v: Value) -> bool {
+        <MemoryRef as ValidLayout>::valid_layout(v)
+    }
+}
+
+/// A reference to a [`TypedMemory`] that has not been explicitly rooted.
+pub type TypedMemoryRef<'scope, 'data, T> = Ref<'scope, 'data, TypedMemory<'scope, 'data, T>>;
+
+/// A [`TypedMemoryRef`] with static lifetimes. This is a useful shorthand for signatures of
+/// `ccall`able functions that return a [`TypedMemory`].
+pub type TypedMemoryRet<T> = Ref<'static, 'static, TypedMemory<'static, 'static, T>>;
+
+unsafe impl<T: ValidField> ValidLayout for TypedMemoryRef<'_, '_, T> {
+    #[inline]
+    fn valid_layout(v: Value) -> bool {
+        if let Ok(dt) = v.cast::<DataType>() {
+            dt.is::<TypedMemory<T>>()
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn type_object<'target, Tgt: Target<'target>>(target: &Tgt) -> Value<'target, 'static> {
+        Memory::base_type(target).unwrap()
+    }
+
+    const IS_REF: bool = true;
+}
+
+unsafe impl<T: ValidField> ValidField for Option<TypedMemoryRef<'_, '_, T>> {
+    #[inline]
+    fn valid_field(v: Value) -> bool {
+        <TypedMemoryRef<T> as ValidLayout>::valid_layout(v)
+    }
+}
+
+/// `Memory` or `MemoryRef`, depending on the target type `T`.
+pub type MemoryData<'target, 'data, T> =
+    <T as TargetType<'target>>::Data<'data, Memory<'target, 'data>>;
+
+/// `JuliaResult<Memory>` or `JuliaResultRef<MemoryRef>`, depending on the target type `T`.
+pub type MemoryResult<'target, 'data, T> = TargetResult<'target, 'data, Memory<'target, 'data>, T>;
+
+/// `TypedMemory<U>` or `TypedMemoryRef<U>`, depending on the target type `T`.
+pub type TypedMemoryData<'target, 'data, T, U> =
+    <T as TargetType<'target>>::Data<'data, TypedMemory<'target, 'data, U>>;
+
+/// `JuliaResult<TypedMemory<U>>` or `JuliaResultRef<TypedMemoryRef<U>>`, depending on the
+/// target type `T`.
+pub type TypedMemoryResult<'target, 'data, T, U> =
+    TargetResult<'target, 'data, TypedMemory<'target, 'data, U>, T>;
+
+unsafe impl<'scope, 'data, T: ValidField + ConstructType> CCallArg
+    for TypedMemory<'scope, 'data, T>
+{
+    type CCallArgType = Value<'scope, 'data>;
+    type FunctionArgType = Self;
+}
+
+unsafe impl<T: ValidField + ConstructType> CCallReturn for TypedMemoryRet<T> {
+    type CCallReturnType = Value<'static, 'static>;
+    type FunctionReturnType = TypedMemory<'static, 'static, T>;
+    type ReturnAs = Self;
+
+    #[inline]
+    unsafe fn return_or_throw(self) -> Self::ReturnAs {
+        self
+    }
+}
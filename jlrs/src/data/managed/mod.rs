@@ -267,11 +267,15 @@ macro_rules! impl_debug {
 }
 
 pub mod array;
+pub mod bit_array;
 pub mod ccall_ref;
 pub mod datatype;
+pub mod dict;
 pub mod function;
 #[cfg(feature = "internal-types")]
 pub mod internal;
+#[cfg(feature = "julia-1-11")]
+pub mod memory;
 pub mod module;
 pub mod parachute;
 pub mod rust_result;
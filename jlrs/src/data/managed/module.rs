@@ -32,7 +32,10 @@ use crate::{
     convert::to_symbol::ToSymbol,
     data::{
         layout::nothing::Nothing,
-        managed::{function::Function, private::ManagedPriv, symbol::Symbol, value::Value},
+        managed::{
+            function::Function, private::ManagedPriv, string::JuliaString, symbol::Symbol,
+            value::Value,
+        },
         types::{construct_type::ConstructType, typecheck::Typecheck},
     },
     error::{AccessError, JlrsResult, TypeError},
@@ -516,6 +519,77 @@ impl<'scope> Module<'scope> {
                 module.to_symbol_priv(Private).as_value(),
             )
     }
+
+    /// Evaluate `code` in this module. This is equivalent to
+    /// `Module::include_string(target, code, "string")`.
+    ///
+    /// Safety: The code can't be checked for correctness, nothing prevents you from causing a
+    /// segmentation fault with code like `unsafe_load(Ptr{Float64}(C_NULL))`.
+    #[inline]
+    pub unsafe fn eval_string<'target, C, T>(
+        self,
+        target: T,
+        code: C,
+    ) -> JlrsResult<ValueResult<'target, 'static, T>>
+    where
+        C: AsRef<str>,
+        T: Target<'target>,
+    {
+        self.include_string(target, code, "string")
+    }
+
+    /// Evaluate `code` in this module by calling `Base.include_string`, the result of the last
+    /// expression is returned. `filename` is used as the name of the file in error messages and
+    /// line number information of the evaluated code.
+    ///
+    /// Safety: The code can't be checked for correctness, nothing prevents you from causing a
+    /// segmentation fault with code like `unsafe_load(Ptr{Float64}(C_NULL))`.
+    pub unsafe fn include_string<'target, C, F, T>(
+        self,
+        target: T,
+        code: C,
+        filename: F,
+    ) -> JlrsResult<ValueResult<'target, 'static, T>>
+    where
+        C: AsRef<str>,
+        F: AsRef<str>,
+        T: Target<'target>,
+    {
+        target.with_local_scope::<_, _, 2>(|target, mut frame| {
+            let code = JuliaString::new(&mut frame, code).as_value();
+            let filename = JuliaString::new(&mut frame, filename).as_value();
+            let include_string =
+                inline_static_ref!(INCLUDE_STRING, Function, "Base.include_string", &frame);
+
+            Ok(include_string.call3(target, self.as_value(), code, filename))
+        })
+    }
+
+    /// Evaluate the expression `expr` in this module by calling `Core.eval`.
+    ///
+    /// Expressions can be constructed with [`Expr::new`] and parsed with [`Expr::parse`] if the
+    /// `internal-types` feature is enabled.
+    ///
+    /// Safety: The expression can't be checked for correctness, nothing prevents you from
+    /// causing a segmentation fault with code like `unsafe_load(Ptr{Float64}(C_NULL))`.
+    ///
+    /// [`Expr::new`]: crate::data::managed::internal::expr::Expr::new
+    /// [`Expr::parse`]: crate::data::managed::internal::expr::Expr::parse
+    #[inline]
+    pub unsafe fn eval_expr<'target, T>(
+        self,
+        target: T,
+        expr: Value<'_, 'static>,
+    ) -> ValueResult<'target, 'static, T>
+    where
+        T: Target<'target>,
+    {
+        inline_static_ref!(EVAL, Function, "Core.eval", &target).call2(
+            target,
+            self.as_value(),
+            expr,
+        )
+    }
 }
 
 impl_julia_typecheck!(Module<'target>, jl_module_type, 'target);
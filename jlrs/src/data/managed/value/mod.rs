@@ -151,7 +151,7 @@ use self::{field_accessor::FieldAccessor, typed::TypedValue};
 use super::Ref;
 use crate::{
     args::Values,
-    call::{Call, ProvideKeywords, WithKeywords},
+    call::{invoke_latest, Call, ProvideKeywords, WithKeywords},
     catch::catch_exceptions,
     convert::{into_julia::IntoJulia, to_symbol::ToSymbol, unbox::Unbox},
     data::{
@@ -1692,6 +1692,19 @@ impl<'data> Call<'data> for Value<'_, 'data> {
 
         target.result_from_ptr(res, Private)
     }
+
+    #[inline]
+    unsafe fn call_latest<'target, 'value, V, T, const N: usize>(
+        self,
+        target: T,
+        args: V,
+    ) -> ValueResult<'target, 'data, T>
+    where
+        V: Values<'value, 'data, N>,
+        T: Target<'target>,
+    {
+        invoke_latest(target, [self.unwrap(Private)], args)
+    }
 }
 
 impl<'value, 'data> ProvideKeywords<'value, 'data> for Value<'value, 'data> {
@@ -0,0 +1,161 @@
+//! Exception types
+//!
+//! These types can be used with the `ConstructType` and `Typecheck` traits, e.g. to catch only
+//! exceptions of a specific type with [`catch_exceptions_of`]. The abstract `Exception` type can
+//! be found in the [`abstract_types`] module.
+//!
+//! [`catch_exceptions_of`]: crate::catch::catch_exceptions_of
+//! [`abstract_types`]: crate::data::types::abstract_types
+
+use super::{construct_type::ConstructType, typecheck::Typecheck};
+use crate::{
+    data::managed::{
+        datatype::DataType,
+        value::{Value, ValueData},
+        Managed,
+    },
+    inline_static_ref,
+    memory::target::{unrooted::Unrooted, Target},
+};
+
+macro_rules! impl_exception_type {
+    ($(#[$meta:meta])* $ty:ident, $path:expr) => {
+        $(#[$meta])*
+        pub struct $ty;
+
+        unsafe impl ConstructType for $ty {
+            type Static = $ty;
+
+            const CACHEABLE: bool = false;
+
+            #[inline]
+            fn construct_type_uncached<'target, Tgt>(
+                target: Tgt,
+            ) -> ValueData<'target, 'static, Tgt>
+            where
+                Tgt: Target<'target>,
+            {
+                Self::base_type(&target).unwrap().root(target)
+            }
+
+            #[inline]
+            fn base_type<'target, Tgt>(target: &Tgt) -> Option<Value<'target, 'static>>
+            where
+                Tgt: Target<'target>,
+            {
+                let value = inline_static_ref!(STATIC, Value, $path, target);
+                Some(value)
+            }
+        }
+
+        unsafe impl Typecheck for $ty {
+            #[inline]
+            fn typecheck(t: DataType) -> bool {
+                let unrooted = unsafe { Unrooted::new() };
+                t == inline_static_ref!(STATIC, DataType, $path, &unrooted)
+            }
+        }
+    };
+}
+
+impl_exception_type!(
+    /// Construct a new `Core.ArgumentError` type object.
+    ArgumentError,
+    "Core.ArgumentError"
+);
+
+impl_exception_type!(
+    /// Construct a new `Core.AssertionError` type object.
+    AssertionError,
+    "Core.AssertionError"
+);
+
+impl_exception_type!(
+    /// Construct a new `Core.BoundsError` type object.
+    BoundsError,
+    "Core.BoundsError"
+);
+
+impl_exception_type!(
+    /// Construct a new `Base.DimensionMismatch` type object.
+    DimensionMismatch,
+    "Base.DimensionMismatch"
+);
+
+impl_exception_type!(
+    /// Construct a new `Core.DivideError` type object.
+    DivideError,
+    "Core.DivideError"
+);
+
+impl_exception_type!(
+    /// Construct a new `Core.DomainError` type object.
+    DomainError,
+    "Core.DomainError"
+);
+
+impl_exception_type!(
+    /// Construct a new `Core.ErrorException` type object.
+    ErrorException,
+    "Core.ErrorException"
+);
+
+impl_exception_type!(
+    /// Construct a new `Core.InexactError` type object.
+    InexactError,
+    "Core.InexactError"
+);
+
+impl_exception_type!(
+    /// Construct a new `Core.InterruptException` type object.
+    InterruptException,
+    "Core.InterruptException"
+);
+
+impl_exception_type!(
+    /// Construct a new `Base.KeyError` type object.
+    KeyError,
+    "Base.KeyError"
+);
+
+impl_exception_type!(
+    /// Construct a new `Core.MethodError` type object.
+    MethodError,
+    "Core.MethodError"
+);
+
+impl_exception_type!(
+    /// Construct a new `Core.OutOfMemoryError` type object.
+    OutOfMemoryError,
+    "Core.OutOfMemoryError"
+);
+
+impl_exception_type!(
+    /// Construct a new `Core.OverflowError` type object.
+    OverflowError,
+    "Core.OverflowError"
+);
+
+impl_exception_type!(
+    /// Construct a new `Core.StackOverflowError` type object.
+    StackOverflowError,
+    "Core.StackOverflowError"
+);
+
+impl_exception_type!(
+    /// Construct a new `Core.TypeError` type object.
+    TypeError,
+    "Core.TypeError"
+);
+
+impl_exception_type!(
+    /// Construct a new `Core.UndefRefError` type object.
+    UndefRefError,
+    "Core.UndefRefError"
+);
+
+impl_exception_type!(
+    /// Construct a new `Core.UndefVarError` type object.
+    UndefVarError,
+    "Core.UndefVarError"
+);
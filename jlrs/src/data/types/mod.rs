@@ -2,5 +2,6 @@
 
 pub mod abstract_types;
 pub mod construct_type;
+pub mod exception_types;
 pub mod foreign_type;
 pub mod typecheck;
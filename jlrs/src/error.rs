@@ -1,12 +1,30 @@
 //! Everything related to errors.
 
-use std::error::Error as StdErr;
+#[julia_version(windows_lts = false)]
+use std::ptr::null_mut;
+use std::{error::Error as StdErr, ffi::c_void, fmt, ptr::NonNull, time::Duration};
 
+#[julia_version(windows_lts = false)]
+use jl_sys::jlrs_catch_backtrace;
+use jl_sys::jlrs_lookup_code_address;
+use jlrs_macros::julia_version;
 use thiserror::Error;
 
-use crate::data::managed::{
-    array::dimensions::Dimensions,
-    value::{Value, ValueRef},
+use crate::{
+    data::{
+        layout::bool::Bool,
+        managed::{
+            array::dimensions::Dimensions,
+            private::ManagedPriv,
+            simple_vector::SimpleVector,
+            symbol::Symbol,
+            value::{Value, ValueData, ValueRef},
+            Managed,
+        },
+    },
+    inline_static_ref,
+    memory::target::{Target, TargetType},
+    private::Private,
 };
 
 pub(crate) static CANNOT_DISPLAY_TYPE: &'static str = "<Cannot display type>";
@@ -36,6 +54,12 @@ pub enum RuntimeError {
     ChannelClosed,
     #[error("channel full")]
     ChannelFull,
+    #[error("invalid value for {option}: {reason}")]
+    InvalidOption { option: String, reason: String },
+    #[error("task was cancelled")]
+    Cancelled,
+    #[error("no async runtime is running on this thread")]
+    NoAsyncRuntime,
 }
 
 /// IO errors.
@@ -45,6 +69,19 @@ pub enum IOError {
     NotFound { path: String },
 }
 
+/// Package manager errors.
+#[derive(Debug, Error)]
+pub enum PkgError {
+    #[error("project does not exist: {path}")]
+    ProjectNotFound { path: String },
+    #[error("package {name} is not installed")]
+    NotInstalled { name: String },
+    #[error("unsatisfiable requirements: {msg}")]
+    Unsatisfiable { msg: String },
+    #[error("{msg}")]
+    Failed { msg: String },
+}
+
 /// Type errors.
 #[derive(Debug, Error)]
 pub enum TypeError {
@@ -78,6 +115,8 @@ pub enum TypeError {
     LayoutNone { ty: String },
     #[error("The layout of this type is incompatible with {base_type}")]
     IncompatibleBaseType { base_type: String },
+    #[error("{function} has no method matching {signature}")]
+    NoMatchingMethod { function: String, signature: String },
 }
 
 /// Array layout errors.
@@ -128,8 +167,21 @@ pub enum AccessError {
     },
     #[error("index {idx} is out-of-bounds for SimpleVector of length {len}")]
     OutOfBoundsSVec { idx: usize, len: usize },
+    #[error("index {idx} is out-of-bounds for Memory of length {len}")]
+    OutOfBoundsMemory { idx: usize, len: usize },
     #[error("index {idx} is invalid for array with shape {sz}")]
     InvalidIndex { idx: Dimensions, sz: Dimensions },
+    #[error("axis {axis} is invalid for array of rank {rank}")]
+    InvalidAxis { axis: usize, rank: usize },
+    #[error("index {idx} is out-of-bounds for axis {axis} of length {len}")]
+    OutOfBoundsAxisIndex { idx: usize, axis: usize, len: usize },
+    #[error("range {start}..{end} is out-of-bounds for axis {axis} of length {len}")]
+    OutOfBoundsRange {
+        start: usize,
+        end: usize,
+        axis: usize,
+        len: usize,
+    },
     #[error("arrays can only be accessed with n-dimensional indices")]
     ArrayNeedsNumericalIndex,
     #[error("fields cannot be accessed with n-dimensional indices")]
@@ -149,6 +201,20 @@ pub enum InstantiationError {
     NamedTupleSizeMismatch { n_names: usize, n_values: usize },
     #[error("expected a shape for {vec_size} elements, got a shape for {dim_size} elements")]
     ArraySizeMismatch { dim_size: usize, vec_size: usize },
+    #[error("cannot move an ndarray that is not contiguous in standard or Fortran order")]
+    NonContiguousNdArray,
+}
+
+/// Errors that can occur while serializing or deserializing Julia data.
+#[cfg(feature = "serde")]
+#[derive(Debug, Error)]
+pub enum SerdeError {
+    #[error("{msg}")]
+    Custom { msg: String },
+    #[error("cannot deserialize data of type {value_type}")]
+    Unsupported { value_type: String },
+    #[error("expected a Symbol, String or NamedTuple with a single field, got {value_type}")]
+    NotAnEnum { value_type: String },
 }
 
 /// Julia exception converted to a string.
@@ -165,6 +231,317 @@ impl Exception {
     }
 }
 
+/// Julia code that can't be parsed.
+///
+/// The parser doesn't report where the error occurred, the position is the line and column,
+/// both starting at 1, of the first character of the top-level expression that failed to parse.
+#[derive(Debug, Error)]
+#[error("{msg} (in the expression starting at line {expr_line}, column {expr_column})")]
+pub struct ParseError {
+    msg: String,
+    expr_line: usize,
+    expr_column: usize,
+    incomplete: bool,
+}
+
+impl ParseError {
+    #[cfg(feature = "internal-types")]
+    pub(crate) fn new(msg: String, expr_line: usize, expr_column: usize, incomplete: bool) -> Self {
+        ParseError {
+            msg,
+            expr_line,
+            expr_column,
+            incomplete,
+        }
+    }
+
+    /// Returns a reference to the error message.
+    pub fn get_message(&self) -> &str {
+        &self.msg
+    }
+
+    /// Returns the line where the expression that failed to parse starts.
+    pub fn expr_line(&self) -> usize {
+        self.expr_line
+    }
+
+    /// Returns the column where the expression that failed to parse starts.
+    pub fn expr_column(&self) -> usize {
+        self.expr_column
+    }
+
+    /// Returns `true` if the code ended before the expression was complete, e.g. because a
+    /// block is never closed with `end`.
+    pub fn is_incomplete(&self) -> bool {
+        self.incomplete
+    }
+}
+
+/// A frame of a Julia backtrace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BacktraceFrame {
+    func: String,
+    file: String,
+    line: usize,
+}
+
+impl BacktraceFrame {
+    /// Returns the name of the function.
+    pub fn func(&self) -> &str {
+        &self.func
+    }
+
+    /// Returns the file the function is defined in.
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// Returns the line number.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl fmt::Display for BacktraceFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}:{}", self.func, self.file, self.line)
+    }
+}
+
+/// Julia exception with its type, error message, and backtrace.
+///
+/// Exceptions converted with [`IntoJlrsResult::into_jlrs_result`] have no backtrace, it's no
+/// longer available when the exception is converted. Functions called with
+/// [`Call::call_traced`] capture the backtrace of the exception they throw, it can also be
+/// captured with [`JuliaException::capture`] in the exception handler of
+/// [`catch_exceptions_or_rethrow`] or [`catch_exceptions_of`]. The backtrace is resolved to
+/// frames when [`JuliaException::backtrace`] is called. Exceptions thrown by a task, i.e. a `TaskFailedException`, and `CapturedException`s
+/// are unwrapped: the type of a `JuliaException` is the type of the exception that was
+/// originally thrown. The error message is always the message of the exception that has been
+/// caught.
+///
+/// The exception itself is not stored, a [`CaughtException`] returned by [`Call::call_traced`]
+/// provides a handle to it.
+///
+/// [`Call::call_traced`]: crate::call::Call::call_traced
+/// [`IntoJlrsResult::into_jlrs_result`]: crate::convert::into_jlrs_result::IntoJlrsResult::into_jlrs_result
+/// [`catch_exceptions_or_rethrow`]: crate::catch::catch_exceptions_or_rethrow
+/// [`catch_exceptions_of`]: crate::catch::catch_exceptions_of
+#[derive(Debug, Error)]
+#[error("{msg}")]
+pub struct JuliaException {
+    type_name: String,
+    msg: String,
+    backtrace: Vec<usize>,
+}
+
+impl JuliaException {
+    /// Convert `exception` to a `JuliaException` without a backtrace.
+    ///
+    /// The exception is converted to its error message by calling `Base.showerror`.
+    pub fn new(exception: Value) -> Self {
+        let msg = exception.error_string_or(CANNOT_DISPLAY_VALUE);
+        // Safety: the exception is rooted, the unwrapped exception is reachable from it.
+        let type_name = unsafe { Self::unwrap_exception(exception) }
+            .datatype_name()
+            .unwrap_or(CANNOT_DISPLAY_TYPE)
+            .to_string();
+
+        JuliaException {
+            type_name,
+            msg,
+            backtrace: Vec::new(),
+        }
+    }
+
+    /// Convert `exception` to a `JuliaException` and capture its backtrace.
+    ///
+    /// This function must be called from the exception handler of [`catch_exceptions_or_rethrow`]
+    /// or [`catch_exceptions_of`] with the exception that is being handled, the backtrace is
+    /// only available while the catch block is active. Otherwise the backtrace is empty or
+    /// belongs to another exception. The backtrace is always empty if Julia 1.6 is used on
+    /// Windows.
+    ///
+    /// [`catch_exceptions_or_rethrow`]: crate::catch::catch_exceptions_or_rethrow
+    /// [`catch_exceptions_of`]: crate::catch::catch_exceptions_of
+    pub fn capture(exception: Value) -> Self {
+        let mut exc = Self::new(exception);
+        exc.backtrace = Self::instruction_pointers();
+        exc
+    }
+
+    /// Returns the name of the type of the exception, e.g. `"DivideError"`.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// Returns the error message.
+    pub fn get_message(&self) -> &str {
+        &self.msg
+    }
+
+    /// Resolve the captured backtrace, starting with the frame where the exception was thrown.
+    ///
+    /// Frames of C functions are skipped, inlined functions are included. The result is empty if
+    /// no backtrace has been captured.
+    pub fn backtrace<'target, Tgt: Target<'target>>(
+        &self,
+        target: &Tgt,
+    ) -> JlrsResult<Vec<BacktraceFrame>> {
+        let mut backtrace = Vec::with_capacity(self.backtrace.len());
+
+        for &ip in self.backtrace.iter() {
+            // Safety: the instruction pointer has been captured from a backtrace, looking up
+            // its code address returns a new `svec` of `svec(func, file, line, linfo, from_c,
+            // inlined)`s which is rooted immediately.
+            unsafe {
+                target.local_scope::<_, _, 1>(|mut frame| {
+                    let ptr = jlrs_lookup_code_address(ip as *mut c_void);
+                    let ptr = NonNull::new_unchecked(ptr.cast());
+                    let frames = SimpleVector::wrap_non_null(ptr, Private).root(&mut frame);
+
+                    for fr in frames.value_slice_unchecked() {
+                        let fr = fr.cast::<SimpleVector>()?;
+                        let fr = fr.value_slice_unchecked();
+                        if fr[4].unbox::<Bool>()?.as_bool() {
+                            continue;
+                        }
+
+                        let func = fr[0].cast::<Symbol>()?.as_string()?;
+                        let file = fr[1].cast::<Symbol>()?.as_string()?;
+                        let line = fr[2].unbox::<isize>()? as usize;
+                        backtrace.push(BacktraceFrame { func, file, line });
+                    }
+
+                    Ok(())
+                })?;
+            }
+        }
+
+        Ok(backtrace)
+    }
+
+    #[julia_version(windows_lts = false)]
+    fn instruction_pointers() -> Vec<usize> {
+        // Safety: the backtrace is read from the exception stack of the current task, the buffer
+        // can hold `n` instruction pointers.
+        unsafe {
+            let n = jlrs_catch_backtrace(null_mut(), 0);
+            let mut ips = Vec::with_capacity(n);
+            let n = jlrs_catch_backtrace(ips.as_mut_ptr(), n).min(n);
+            ips.set_len(n);
+            ips
+        }
+    }
+
+    #[julia_version(windows_lts = true)]
+    fn instruction_pointers() -> Vec<usize> {
+        Vec::new()
+    }
+
+    // Returns the exception that was originally thrown if `exception` is a `TaskFailedException`
+    // or `CapturedException`, otherwise `exception` itself.
+    unsafe fn unwrap_exception<'scope, 'data>(
+        exception: Value<'scope, 'data>,
+    ) -> Value<'scope, 'data> {
+        let unrooted = exception.unrooted_target();
+        let captured_exception = inline_static_ref!(
+            CAPTURED_EXCEPTION,
+            Value,
+            "Base.CapturedException",
+            &unrooted
+        );
+        let task_failed_exception = inline_static_ref!(
+            TASK_FAILED_EXCEPTION,
+            Value,
+            "Base.TaskFailedException",
+            &unrooted
+        );
+
+        let inner = if exception.isa(captured_exception) {
+            Self::field(exception, "ex")
+        } else if exception.isa(task_failed_exception) {
+            Self::field(exception, "task").and_then(|task| Self::field(task, "result"))
+        } else {
+            return exception;
+        };
+
+        match inner {
+            Ok(inner) => inner,
+            Err(_) => exception,
+        }
+    }
+
+    unsafe fn field<'scope>(
+        value: Value<'scope, '_>,
+        name: &str,
+    ) -> JlrsResult<Value<'scope, 'static>> {
+        match value.get_field_ref(name)? {
+            Some(v) => Ok(v.as_value().assume_owned()),
+            None => Err(AccessError::UndefRef)?,
+        }
+    }
+}
+
+/// An exception caught by [`Call::call_traced`].
+///
+/// The exception is rooted in the target the function was called with and can be accessed with
+/// [`CaughtException::exception`], its type, error message, and captured backtrace are
+/// available as a [`JuliaException`]. A `CaughtException` can be converted to a
+/// [`JlrsError::JuliaException`] with the `?` operator, the handle to the exception is dropped.
+///
+/// [`Call::call_traced`]: crate::call::Call::call_traced
+pub struct CaughtException<'target, T: TargetType<'target>> {
+    exception: ValueData<'target, 'static, T>,
+    info: JuliaException,
+}
+
+impl<'target, T: TargetType<'target>> CaughtException<'target, T> {
+    #[inline]
+    pub(crate) fn new(exception: ValueData<'target, 'static, T>, info: JuliaException) -> Self {
+        CaughtException { exception, info }
+    }
+
+    /// Returns the exception.
+    #[inline]
+    pub fn exception(&self) -> &ValueData<'target, 'static, T> {
+        &self.exception
+    }
+
+    /// Returns the type, error message, and backtrace of the exception.
+    #[inline]
+    pub fn info(&self) -> &JuliaException {
+        &self.info
+    }
+
+    /// Split `self` into the exception and its `JuliaException`.
+    #[inline]
+    pub fn into_parts(self) -> (ValueData<'target, 'static, T>, JuliaException) {
+        (self.exception, self.info)
+    }
+}
+
+impl<'target, T: TargetType<'target>> fmt::Debug for CaughtException<'target, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CaughtException")
+            .field("info", &self.info)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'target, T: TargetType<'target>> From<CaughtException<'target, T>> for Box<JlrsError> {
+    #[inline]
+    fn from(e: CaughtException<'target, T>) -> Self {
+        Box::new(JlrsError::JuliaException(e.info))
+    }
+}
+
+/// The result of [`Call::call_traced`], the exception is returned as a [`CaughtException`].
+///
+/// [`Call::call_traced`]: crate::call::Call::call_traced
+pub type TracedResult<'target, 'data, T> =
+    Result<ValueData<'target, 'data, T>, CaughtException<'target, T>>;
+
 /// All different errors.
 #[derive(Debug, Error)]
 pub enum JlrsError {
@@ -172,6 +549,8 @@ pub enum JlrsError {
     Other(Box<dyn StdErr + 'static + Send + Sync>),
     #[error("Exception: {0}")]
     Exception(Exception),
+    #[error("Julia exception: {0}")]
+    JuliaException(JuliaException),
     #[error("Runtime error: {0}")]
     RuntimeError(RuntimeError),
     #[error("Type error: {0}")]
@@ -184,6 +563,15 @@ pub enum JlrsError {
     InstantiationError(InstantiationError),
     #[error("Array layout error: {0}")]
     ArrayLayoutError(ArrayLayoutError),
+    #[error("Parse error: {0}")]
+    ParseError(ParseError),
+    #[error("Timeout: the task did not complete within {0:?}")]
+    Timeout(Duration),
+    #[error("Pkg error: {0}")]
+    PkgError(PkgError),
+    #[cfg(feature = "serde")]
+    #[error("Serde error: {0}")]
+    SerdeError(SerdeError),
 }
 
 impl JlrsError {
@@ -230,9 +618,14 @@ macro_rules! impl_from {
     };
 }
 
+impl_from!(JuliaException);
 impl_from!(RuntimeError);
 impl_from!(TypeError);
 impl_from!(IOError);
 impl_from!(AccessError);
 impl_from!(InstantiationError);
 impl_from!(ArrayLayoutError);
+impl_from!(ParseError);
+impl_from!(PkgError);
+#[cfg(feature = "serde")]
+impl_from!(SerdeError);
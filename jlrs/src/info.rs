@@ -3,9 +3,9 @@
 use std::{ffi::CStr, ptr::NonNull};
 
 use jl_sys::{
-    jl_cpu_threads, jl_get_ARCH, jl_get_UNAME, jl_getallocationgranularity, jl_getpagesize,
-    jl_git_branch, jl_git_commit, jl_is_debugbuild, jl_n_threads, jl_ver_is_release, jl_ver_major,
-    jl_ver_minor, jl_ver_patch, jl_ver_string,
+    jl_cpu_threads, jl_get_ARCH, jl_get_UNAME, jl_get_world_counter, jl_getallocationgranularity,
+    jl_getpagesize, jl_git_branch, jl_git_commit, jl_is_debugbuild, jl_n_threads,
+    jl_ver_is_release, jl_ver_major, jl_ver_minor, jl_ver_patch, jl_ver_string,
 };
 use jlrs_macros::julia_version;
 
@@ -135,6 +135,18 @@ impl Info {
     pub fn version_string() -> &'static str {
         unsafe { CStr::from_ptr(jl_ver_string()).to_str().unwrap() }
     }
+
+    /// Returns the current world counter.
+    ///
+    /// The world counter is incremented every time a method is defined or deleted. Code that
+    /// runs in an older world age can't see methods that have been defined later, such code can
+    /// call functions in the latest world age with [`Call::call_latest`].
+    ///
+    /// [`Call::call_latest`]: crate::call::Call::call_latest
+    #[inline]
+    pub fn world_counter() -> usize {
+        unsafe { jl_get_world_counter() }
+    }
 }
 
 /// Alias for a result that contains either a valid UTF8-encoded string slice, or the raw byte
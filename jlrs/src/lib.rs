@@ -95,6 +95,7 @@
 //!  - `julia-1-8`
 //!  - `julia-1-9`
 //!  - `julia-1-10`
+//!  - `julia-1-11`
 //!
 //! Exactly one version feature must be enabled. If no version is enabled, or multiple are, jl-sys
 //! will fail to compile.
@@ -109,6 +110,7 @@
 //! julia-1-8 = ["jlrs/julia-1-8"]
 //! julia-1-9 = ["jlrs/julia-1-9"]
 //! julia-1-10 = ["jlrs/julia-1-10"]
+//! julia-1-11 = ["jlrs/julia-1-11"]
 //! ```
 //!
 //! In this case you must provide this feature when you build or run your crate:
@@ -163,6 +165,11 @@
 //!
 //!   Access the content of a Julia array as an `ArrayView` or `ArrayViewMut` from ndarray.
 //!
+//! - `serde`
+//!
+//!   Convert Rust data that implements `Serialize` to Julia data, and Julia data to Rust data
+//!   that implements `Deserialize`.
+//!
 //! - `f16`
 //!
 //!   Adds support for working with Julia's `Float16` type from Rust using half's `f16` type.
@@ -839,6 +846,7 @@ pub mod info;
 pub mod memory;
 #[cfg(feature = "prelude")]
 pub mod prelude;
+pub mod pkg;
 pub(crate) mod private;
 #[cfg(feature = "pyplot")]
 pub mod pyplot;
@@ -0,0 +1,458 @@
+//! Manage package environments with Pkg.
+//!
+//! By default, JlrsCore is installed in whatever environment is active when the runtime is
+//! initialized and packages are installed by evaluating Julia code. The functions in this module
+//! let you manage an environment without generating code: you can activate a project directory,
+//! install the exact versions recorded in its manifest, add or develop packages, and query which
+//! versions are installed.
+//!
+//! Applications that ship with a locked environment should enable offline mode, either with
+//! [`RuntimeBuilder::pkg_offline`] or [`Pkg::offline`]. In offline mode the package manager
+//! avoids accessing the network where it can, e.g. it resolves versions with the registries
+//! that are already installed. This is best-effort: it doesn't prevent package code, like build
+//! scripts, from accessing the network.
+//!
+//! Exceptions thrown by Pkg are converted to a [`PkgError`]. If the requirements of the
+//! environment can't be satisfied, e.g. because the requested version isn't available offline,
+//! `PkgError::Unsatisfiable` is returned.
+//!
+//! [`RuntimeBuilder::pkg_offline`]: crate::runtime::builder::RuntimeBuilder::pkg_offline
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    call::{Call, ProvideKeywords},
+    convert::into_jlrs_result::IntoJlrsResult,
+    data::{
+        layout::{bool::Bool, nothing::Nothing},
+        managed::{
+            function::Function, module::Module, string::JuliaString, symbol::Symbol, value::Value,
+            Managed,
+        },
+    },
+    error::{JlrsResult, JuliaException, PkgError, CANNOT_DISPLAY_VALUE},
+    memory::target::{frame::LocalGcFrame, Target},
+};
+
+/// A package that should be added or developed.
+///
+/// A package is identified by its name, UUID, URL, or path. If a version is set, it's
+/// interpreted as a version specifier: `"1.2.3"` installs exactly that version, `"1.2"` the
+/// most recent compatible version.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PackageSpec {
+    name: Option<String>,
+    uuid: Option<String>,
+    version: Option<String>,
+    url: Option<String>,
+    rev: Option<String>,
+    path: Option<PathBuf>,
+}
+
+impl PackageSpec {
+    /// Create a new spec for the package `name`.
+    #[inline]
+    pub fn new<N: Into<String>>(name: N) -> Self {
+        PackageSpec {
+            name: Some(name.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Create a new spec for the package at `path`.
+    #[inline]
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        PackageSpec {
+            path: Some(path.as_ref().to_path_buf()),
+            ..Default::default()
+        }
+    }
+
+    /// Create a new spec for the package in the git repository at `url`.
+    #[inline]
+    pub fn from_url<U: Into<String>>(url: U) -> Self {
+        PackageSpec {
+            url: Some(url.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Set the UUID of the package.
+    #[inline]
+    pub fn uuid<U: Into<String>>(mut self, uuid: U) -> Self {
+        self.uuid = Some(uuid.into());
+        self
+    }
+
+    /// Set the version of the package.
+    #[inline]
+    pub fn version<V: Into<String>>(mut self, version: V) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Set the git revision of the package, e.g. a branch or commit.
+    #[inline]
+    pub fn rev<R: Into<String>>(mut self, rev: R) -> Self {
+        self.rev = Some(rev.into());
+        self
+    }
+
+    /// Returns the name of the package, if it has been set.
+    #[inline]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the version of the package, if it has been set.
+    #[inline]
+    pub fn get_version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    // Convert this spec to a `Pkg.PackageSpec`.
+    unsafe fn to_julia<'target, const M: usize>(
+        &self,
+        frame: &mut LocalGcFrame<'target, M>,
+        pkg: Module,
+    ) -> JlrsResult<Value<'target, 'static>> {
+        let name = Self::string_or_nothing(frame, self.name.as_deref());
+        let uuid = Self::string_or_nothing(frame, self.uuid.as_deref());
+        // `nothing` isn't accepted as a version by all versions of Pkg, `*` matches any version.
+        let version = JuliaString::new(&mut *frame, self.version.as_deref().unwrap_or("*"));
+        let url = Self::string_or_nothing(frame, self.url.as_deref());
+        let rev = Self::string_or_nothing(frame, self.rev.as_deref());
+        let path = self.path.as_ref().map(|p| p.to_string_lossy());
+        let path = Self::string_or_nothing(frame, path.as_deref());
+
+        let pairs = [
+            (Symbol::new(&*frame, "name"), name),
+            (Symbol::new(&*frame, "uuid"), uuid),
+            (Symbol::new(&*frame, "version"), version.as_value()),
+            (Symbol::new(&*frame, "url"), url),
+            (Symbol::new(&*frame, "rev"), rev),
+            (Symbol::new(&*frame, "path"), path),
+        ];
+        let kws = Value::new_named_tuple(&mut *frame, &pairs);
+
+        pkg.global(&*frame, "PackageSpec")?
+            .as_value()
+            .provide_keywords(kws)?
+            .call0(frame)
+            .map_err(|e| Pkg::error(e))
+    }
+
+    unsafe fn string_or_nothing<'target, const M: usize>(
+        frame: &mut LocalGcFrame<'target, M>,
+        s: Option<&str>,
+    ) -> Value<'target, 'static> {
+        match s {
+            Some(s) => JuliaString::new(frame, s).as_value(),
+            None => Value::nothing(&*frame),
+        }
+    }
+}
+
+/// An installed package.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PackageInfo {
+    name: String,
+    version: Option<String>,
+    is_direct_dep: bool,
+}
+
+impl PackageInfo {
+    /// Returns the name of the package.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the installed version of the package. Packages that don't have a version, e.g.
+    /// some standard libraries, return `None`.
+    #[inline]
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Returns `true` if the package is a direct dependency of the active project.
+    #[inline]
+    pub fn is_direct_dep(&self) -> bool {
+        self.is_direct_dep
+    }
+}
+
+/// Functions to manage the active package environment.
+///
+/// All functions load Pkg if it hasn't been loaded yet and call into it, so they're unsafe:
+/// loading a package runs its initialization code, and operations that modify the environment
+/// can run arbitrary Julia code, e.g. the build scripts of packages.
+pub struct Pkg;
+
+impl Pkg {
+    /// Activate the project at `path`.
+    ///
+    /// Unlike `Pkg.activate`, this function doesn't create a new project if `path` doesn't exist
+    /// but returns `PkgError::ProjectNotFound`.
+    ///
+    /// Safety: Pkg is loaded if it hasn't been loaded yet. Code that has already been loaded
+    /// isn't reloaded, packages that are loaded afterwards are resolved in the new environment.
+    pub unsafe fn activate<'target, P, T>(target: T, path: P) -> JlrsResult<()>
+    where
+        P: AsRef<Path>,
+        T: Target<'target>,
+    {
+        let path = path.as_ref();
+        if !path.exists() {
+            Err(PkgError::ProjectNotFound {
+                path: path.to_string_lossy().to_string(),
+            })?;
+        }
+
+        target.local_scope::<_, _, 3>(|mut frame| {
+            let pkg = Self::module(&mut frame)?;
+            let path = JuliaString::new(&mut frame, path.to_string_lossy()).as_value();
+            Self::function(&frame, pkg, "activate")?
+                .call1(&mut frame, path)
+                .map_err(|e| Self::error(e))?;
+            Ok(())
+        })
+    }
+
+    /// Install the packages in the manifest of the active project.
+    ///
+    /// If the active project has a manifest, the exact versions recorded in it are installed.
+    ///
+    /// Safety: installing packages runs their build scripts, which can run arbitrary code.
+    pub unsafe fn instantiate<'target, T>(target: T) -> JlrsResult<()>
+    where
+        T: Target<'target>,
+    {
+        target.local_scope::<_, _, 2>(|mut frame| {
+            let pkg = Self::module(&mut frame)?;
+            Self::function(&frame, pkg, "instantiate")?
+                .call0(&mut frame)
+                .map_err(|e| Self::error(e))?;
+            Ok(())
+        })
+    }
+
+    /// Add a package to the active project.
+    ///
+    /// Safety: the package and its new dependencies are installed, which runs their build
+    /// scripts.
+    pub unsafe fn add<'target, T>(target: T, spec: &PackageSpec) -> JlrsResult<()>
+    where
+        T: Target<'target>,
+    {
+        Self::call_with_spec(target, "add", spec)
+    }
+
+    /// Add a package to the active project in development mode, i.e. the package is tracked by
+    /// path and isn't installed in the depot.
+    ///
+    /// Safety: the build script of the package is run, which can run arbitrary code.
+    pub unsafe fn develop<'target, T>(target: T, spec: &PackageSpec) -> JlrsResult<()>
+    where
+        T: Target<'target>,
+    {
+        Self::call_with_spec(target, "develop", spec)
+    }
+
+    /// Pin a package to its installed version, or to the version in `spec` if it has been set.
+    ///
+    /// Safety: if another version is pinned it's installed, which runs its build script.
+    pub unsafe fn pin<'target, T>(target: T, spec: &PackageSpec) -> JlrsResult<()>
+    where
+        T: Target<'target>,
+    {
+        Self::call_with_spec(target, "pin", spec)
+    }
+
+    /// Remove a package from the active project.
+    ///
+    /// Safety: Pkg is loaded if it hasn't been loaded yet. A package that has already been
+    /// loaded remains loaded.
+    pub unsafe fn rm<'target, T>(target: T, spec: &PackageSpec) -> JlrsResult<()>
+    where
+        T: Target<'target>,
+    {
+        Self::call_with_spec(target, "rm", spec)
+    }
+
+    /// Enable or disable offline mode.
+    ///
+    /// In offline mode the package manager avoids accessing the network where it can,
+    /// operations that can't be completed without downloading something fail instead. This is
+    /// best-effort, package code like build scripts can still access the network.
+    ///
+    /// Safety: Pkg is loaded if it hasn't been loaded yet, which runs its initialization code.
+    pub unsafe fn offline<'target, T>(target: T, enable: bool) -> JlrsResult<()>
+    where
+        T: Target<'target>,
+    {
+        target.local_scope::<_, _, 3>(|mut frame| {
+            let pkg = Self::module(&mut frame)?;
+            let enable = Value::new(&mut frame, enable);
+            Self::function(&frame, pkg, "offline")?
+                .call1(&mut frame, enable)
+                .map_err(|e| Self::error(e))?;
+            Ok(())
+        })
+    }
+
+    /// Returns all packages that are installed in the active environment, including indirect
+    /// dependencies.
+    ///
+    /// Safety: Pkg is loaded if it hasn't been loaded yet, which runs its initialization code.
+    /// The environment is only read.
+    pub unsafe fn installed<'target, T>(target: T) -> JlrsResult<Vec<PackageInfo>>
+    where
+        T: Target<'target>,
+    {
+        target.local_scope::<_, _, 5>(|mut frame| {
+            let pkg = Self::module(&mut frame)?;
+            let deps = Self::function(&frame, pkg, "dependencies")?
+                .call0(&mut frame)
+                .map_err(|e| Self::error(e))?;
+
+            let base = Module::base(&frame);
+            let values = Self::function(&frame, base, "values")?
+                .call1(&mut frame, deps)
+                .into_jlrs_result()?;
+            let infos = Self::function(&frame, base, "collect")?
+                .call1(&mut frame, values)
+                .into_jlrs_result()?;
+            let n = Self::function(&frame, base, "length")?
+                .call1(&mut frame, infos)
+                .into_jlrs_result()?
+                .unbox::<isize>()? as usize;
+
+            let getindex = Self::function(&frame, base, "getindex")?;
+            let string = Self::function(&frame, base, "string")?;
+
+            let mut installed = Vec::with_capacity(n);
+            for i in 1..=n {
+                let info = frame.local_scope::<_, _, 3>(|mut frame| {
+                    let idx = Value::new(&mut frame, i as isize);
+                    let info = getindex.call2(&mut frame, infos, idx).into_jlrs_result()?;
+
+                    let name = Self::field(info, "name")?
+                        .cast::<JuliaString>()?
+                        .as_str()?
+                        .to_string();
+
+                    let version = Self::field(info, "version")?;
+                    let version = if version.is::<Nothing>() {
+                        None
+                    } else {
+                        let version = string.call1(&mut frame, version).into_jlrs_result()?;
+                        Some(version.cast::<JuliaString>()?.as_str()?.to_string())
+                    };
+
+                    let is_direct_dep = info
+                        .field_accessor()
+                        .field("is_direct_dep")?
+                        .access::<Bool>()?
+                        .as_bool();
+
+                    Ok(PackageInfo {
+                        name,
+                        version,
+                        is_direct_dep,
+                    })
+                })?;
+
+                installed.push(info);
+            }
+
+            Ok(installed)
+        })
+    }
+
+    /// Returns the installed version of the package `name`.
+    ///
+    /// If the package isn't installed in the active environment `PkgError::NotInstalled` is
+    /// returned. If it's installed but has no version, e.g. some standard libraries, `None` is
+    /// returned.
+    ///
+    /// Safety: the same as [`Pkg::installed`].
+    pub unsafe fn installed_version<'target, T>(target: T, name: &str) -> JlrsResult<Option<String>>
+    where
+        T: Target<'target>,
+    {
+        match Self::installed(target)?
+            .into_iter()
+            .find(|info| info.name == name)
+        {
+            Some(info) => Ok(info.version),
+            None => Err(PkgError::NotInstalled {
+                name: name.to_string(),
+            })?,
+        }
+    }
+
+    unsafe fn call_with_spec<'target, T>(
+        target: T,
+        func: &str,
+        spec: &PackageSpec,
+    ) -> JlrsResult<()>
+    where
+        T: Target<'target>,
+    {
+        target.local_scope::<_, _, 10>(|mut frame| {
+            let pkg = Self::module(&mut frame)?;
+            let spec = spec.to_julia(&mut frame, pkg)?;
+            Self::function(&frame, pkg, func)?
+                .call1(&mut frame, spec)
+                .map_err(|e| Self::error(e))?;
+            Ok(())
+        })
+    }
+
+    unsafe fn module<'target, const M: usize>(
+        frame: &mut LocalGcFrame<'target, M>,
+    ) -> JlrsResult<Module<'target>> {
+        let pkg = Module::main(&*frame)
+            .require(frame, "Pkg")
+            .into_jlrs_result()?
+            .cast::<Module>()?;
+        Ok(pkg)
+    }
+
+    unsafe fn function<'target, T>(
+        target: &T,
+        module: Module,
+        name: &str,
+    ) -> JlrsResult<Function<'target, 'static>>
+    where
+        T: Target<'target>,
+    {
+        Ok(module.function(target, name)?.as_managed())
+    }
+
+    unsafe fn field<'scope>(
+        value: Value<'scope, '_>,
+        name: &str,
+    ) -> JlrsResult<Value<'scope, 'static>> {
+        match value.get_field_ref(name)? {
+            Some(v) => Ok(v.as_value().assume_owned()),
+            None => Err(crate::error::AccessError::UndefRef)?,
+        }
+    }
+
+    // Convert an exception thrown by Pkg to an error.
+    fn error(exception: Value) -> Box<crate::error::JlrsError> {
+        match exception.datatype_name() {
+            Ok("PkgError") => {
+                let msg = exception.error_string_or(CANNOT_DISPLAY_VALUE);
+                PkgError::Failed { msg }.into()
+            }
+            Ok("ResolverError") => {
+                let msg = exception.error_string_or(CANNOT_DISPLAY_VALUE);
+                PkgError::Unsatisfiable { msg }.into()
+            }
+            _ => JuliaException::new(exception).into(),
+        }
+    }
+}
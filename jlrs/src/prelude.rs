@@ -49,6 +49,7 @@ pub use crate::{
         },
     },
     error::JlrsResult,
+    kwargs,
     memory::target::{Target, TargetType},
     named_tuple,
 };
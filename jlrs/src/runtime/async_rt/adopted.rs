@@ -4,7 +4,7 @@ use jl_sys::{jl_adopt_thread, jl_gc_safepoint, jlrs_gc_safe_enter, jlrs_gc_safe_
 
 use super::{queue::Receiver, AsyncRuntime, Message, MessageInner};
 use crate::{
-    async_util::task::sleep,
+    async_util::{future::set_runtime_timer, task::sleep},
     error::JlrsResult,
     memory::{get_tls, stack_frame::StackFrame, target::unrooted::Unrooted},
 };
@@ -37,6 +37,7 @@ async unsafe fn run_inner<R: AsyncRuntime, const N: usize>(
     base_frame: &mut StackFrame<N>,
 ) -> JlrsResult<()> {
     let _ = jl_adopt_thread();
+    set_runtime_timer::<R>();
 
     let base_frame: &'static mut StackFrame<N> = std::mem::transmute(base_frame);
     let mut pinned = base_frame.pin();
@@ -71,10 +72,15 @@ async unsafe fn run_inner<R: AsyncRuntime, const N: usize>(
 
         let msg = if n_free == N {
             // No tasks are running so we block until there is work to do. Enter a GC-safe state
-            // to indicate garbage can be collected while we wait.
+            // to indicate garbage can be collected while we wait. The timeout is used to notice
+            // if the queue has been closed because the runtime is shutting down.
             let ptls = get_tls();
             let state = jlrs_gc_safe_enter(ptls);
-            let msg = receiver.recv_worker().await;
+            let msg = loop {
+                if let Some(msg) = R::timeout(recv_timeout, receiver.recv_worker()).await {
+                    break msg;
+                }
+            };
             jlrs_gc_safe_leave(ptls, state);
             Some(msg)
         } else {
@@ -130,6 +136,9 @@ async unsafe fn run_inner<R: AsyncRuntime, const N: usize>(
                     let stack = base_frame.sync_stack();
                     task.call(stack);
                 }
+                MessageInner::Shutdown(_) => {
+                    unreachable!("shutdown messages are only sent to the main thread")
+                }
             },
             _ => break,
         }
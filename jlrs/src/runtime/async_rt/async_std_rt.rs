@@ -18,7 +18,7 @@ use crate::{
         Channel, ChannelReceiver, ChannelSender, OneshotSender, SendError, TrySendError,
     },
     error::{JlrsError, JlrsResult},
-    runtime::async_rt::{AsyncRuntime, Message},
+    runtime::async_rt::AsyncRuntime,
 };
 
 /// Struct for which [`AsyncRuntime`] is implemented using async-std.
@@ -62,9 +62,9 @@ impl AsyncRuntime for AsyncStd {
     }
 
     #[inline]
-    async fn timeout<F>(duration: Duration, future: F) -> Option<JlrsResult<Message>>
+    async fn timeout<F>(duration: Duration, future: F) -> Option<F::Output>
     where
-        F: Future<Output = JlrsResult<Message>>,
+        F: Future,
     {
         async_std::future::timeout(duration, future).await.ok()
     }
@@ -3,7 +3,11 @@
 use std::{fmt::Debug, marker::PhantomData};
 
 use crate::{
-    async_util::affinity::{Affinity, ToAny, ToMain, ToWorker},
+    async_util::{
+        affinity::{Affinity, ToAny, ToMain, ToWorker},
+        channel::TrySendError,
+    },
+    error::JlrsResult,
     runtime::async_rt::{queue::Sender, Message},
 };
 
@@ -29,33 +33,42 @@ impl<'a, D: Affinity> Dispatch<'a, D> {
             _dispatch: PhantomData,
         }
     }
+
+    // Returns the dispatcher in the error if the task couldn't be sent.
+    #[inline]
+    fn try_send_error(
+        sender: &'a Sender<Message>,
+        err: TrySendError<Message>,
+    ) -> TrySendError<Self> {
+        match err {
+            TrySendError::Full(msg) => TrySendError::Full(Dispatch::new(sender, msg)),
+            TrySendError::Closed(msg) => TrySendError::Closed(Dispatch::new(sender, msg)),
+        }
+    }
 }
 
 impl<'a, D: ToAny> Dispatch<'a, D> {
     /// Dispatch the task to any thread.
     ///
     /// The dispatched task can be handled by either the main thread or any of the worker threads.
-    /// This method doesn't resolve until the task has been successfully dispatched.
+    /// This method doesn't resolve until the task has been successfully dispatched. If the
+    /// runtime has been shut down, `RuntimeError::ChannelClosed` is returned.
     #[inline]
-    pub async fn dispatch_any(self) {
+    pub async fn dispatch_any(self) -> JlrsResult<()> {
         self.sender.send(self.msg).await
     }
 
     /// Try to dispatch the task to any thread.
     ///
     /// The dispatched task can be handled by either the main thread or any of the worker threads.
-    /// If the backing queue is full, the dispatcher is returned to allow retrying.
+    /// If the backing queue is full, the dispatcher is returned in `TrySendError::Full` to allow
+    /// retrying. If the runtime has been shut down, it's returned in `TrySendError::Closed`.
     #[inline]
-    pub fn try_dispatch_any(self) -> Result<(), Self> {
-        if let Some(msg) = self.sender.try_send(self.msg) {
-            Err(Dispatch {
-                msg,
-                sender: self.sender,
-                _dispatch: PhantomData,
-            })
-        } else {
-            Ok(())
-        }
+    pub fn try_dispatch_any(self) -> Result<(), TrySendError<Self>> {
+        let sender = self.sender;
+        sender
+            .try_send(self.msg)
+            .map_err(|e| Self::try_send_error(sender, e))
     }
 }
 
@@ -63,27 +76,24 @@ impl<'a, D: ToMain> Dispatch<'a, D> {
     /// Dispatch the task to the main thread.
     ///
     /// The dispatched task is guaranteed to be handled by the main thread. This method doesn't
-    /// resolve until the task has been successfully dispatched.
+    /// resolve until the task has been successfully dispatched. If the runtime has been shut
+    /// down, `RuntimeError::ChannelClosed` is returned.
     #[inline]
-    pub async fn dispatch_main(self) {
+    pub async fn dispatch_main(self) -> JlrsResult<()> {
         self.sender.send_main(self.msg).await
     }
 
     /// Try to dispatch the task to the main thread.
     ///
     /// The dispatched task is guaranteed to be handled by the main thread. If the backing queue
-    /// is full, the dispatcher is returned to allow retrying.
+    /// is full, the dispatcher is returned in `TrySendError::Full` to allow retrying. If the
+    /// runtime has been shut down, it's returned in `TrySendError::Closed`.
     #[inline]
-    pub fn try_dispatch_main(self) -> Result<(), Self> {
-        if let Some(msg) = self.sender.try_send_main(self.msg) {
-            Err(Dispatch {
-                msg,
-                sender: self.sender,
-                _dispatch: PhantomData,
-            })
-        } else {
-            Ok(())
-        }
+    pub fn try_dispatch_main(self) -> Result<(), TrySendError<Self>> {
+        let sender = self.sender;
+        sender
+            .try_send_main(self.msg)
+            .map_err(|e| Self::try_send_error(sender, e))
     }
 }
 
@@ -92,27 +102,24 @@ impl<'a, D: ToWorker> Dispatch<'a, D> {
     ///
     /// The dispatched task is guaranteed to be handled by a worker thread if they're used,
     /// otherwise it's handled by the main thread. This method doesn't resolve until the task has
-    /// been successfully dispatched.
+    /// been successfully dispatched. If the runtime has been shut down,
+    /// `RuntimeError::ChannelClosed` is returned.
     #[inline]
-    pub async fn dispatch_worker(self) {
+    pub async fn dispatch_worker(self) -> JlrsResult<()> {
         self.sender.send_worker(self.msg).await
     }
 
     /// Try to dispatch the task to a worker thread.
     ///
     /// The dispatched task is guaranteed to be handled by a worker thread if they're used,
-    /// otherwise it's handled by the main thread.  If the backing queue is full, the dispatcher
-    /// is returned to allow retrying.
+    /// otherwise it's handled by the main thread. If the backing queue is full, the dispatcher is
+    /// returned in `TrySendError::Full` to allow retrying. If the runtime has been shut down,
+    /// it's returned in `TrySendError::Closed`.
     #[inline]
-    pub fn try_dispatch_worker(self) -> Result<(), Self> {
-        if let Some(msg) = self.sender.try_send_worker(self.msg) {
-            Err(Dispatch {
-                msg,
-                sender: self.sender,
-                _dispatch: PhantomData,
-            })
-        } else {
-            Ok(())
-        }
+    pub fn try_dispatch_worker(self) -> Result<(), TrySendError<Self>> {
+        let sender = self.sender;
+        sender
+            .try_send_worker(self.msg)
+            .map_err(|e| Self::try_send_error(sender, e))
     }
 }
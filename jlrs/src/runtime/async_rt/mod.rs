@@ -19,8 +19,11 @@
 //! complete. While the scheduled Julia function hasn't returned the async runtime can handle other
 //! tasks scheduled on that thread. Blocking tasks can be expressed as closures, the other two
 //! require implementing the [`AsyncTask`] and [`PersistentTask`] traits respectively.
+//!
+//! The runtime shuts down when all handles have been dropped, or when it's shut down explicitly
+//! with [`AsyncJulia::shutdown`].
 
-#[cfg(any(feature = "julia-1-10", feature = "julia-1-9"))]
+#[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
 pub mod adopted;
 #[cfg(feature = "async-std-rt")]
 pub mod async_std_rt;
@@ -60,28 +63,37 @@ use self::{
 use crate::{
     async_util::{
         affinity::{Affinity, DispatchAny, DispatchMain},
+        cancel::CancelHandle,
         channel::{Channel, ChannelSender, OneshotSender, TrySendError},
         envelope::{
-            BlockingTask, BlockingTaskEnvelope, CallPersistentTask, IncludeTask,
+            BlockingTask, BlockingTaskEnvelope, CallPersistentTask, CancellableTask, IncludeTask,
             IncludeTaskEnvelope, InnerPersistentMessage, PendingTask, PendingTaskEnvelope,
             Persistent, PersistentComms, RegisterPersistent, RegisterTask, SetErrorColorTask,
-            SetErrorColorTaskEnvelope, Task,
+            SetErrorColorTaskEnvelope, ShutdownTask, ShutdownTaskEnvelope, Task,
         },
-        future::wake_task,
+        future::{set_runtime_timer, wake_task},
         task::{sleep, AsyncTask, PersistentTask},
     },
     convert::into_result::IntoResult,
     data::managed::{module::Module, value::Value},
     error::{IOError, JlrsError, JlrsResult, RuntimeError},
+    gc_safe::GcSafeOnceLock,
     init_jlrs,
     memory::{
         context::stack::Stack,
         stack_frame::StackFrame,
         target::{frame::GcFrame, unrooted::Unrooted},
     },
-    runtime::{builder::AsyncRuntimeBuilder, INIT},
+    runtime::{
+        builder::{AsyncRuntimeBuilder, StartupReport},
+        INIT,
+    },
 };
 
+// Julia can only be initialized once, the report is set after the startup file and precompile
+// statements have been evaluated.
+static STARTUP_REPORT: GcSafeOnceLock<StartupReport> = GcSafeOnceLock::new();
+
 /// Functionality that is necessary to use an async runtime with jlrs.
 ///
 /// If you want to use async-std or tokio you can use one of the implementations provided by
@@ -134,15 +146,16 @@ pub trait AsyncRuntime: Send + Sync + 'static {
 
     /// Wait on `future` until it resolves or `duration` has elapsed. If the future times out it
     /// must return `None`.
-    async fn timeout<F>(duration: Duration, future: F) -> Option<JlrsResult<Message>>
+    async fn timeout<F>(duration: Duration, future: F) -> Option<F::Output>
     where
-        F: Future<Output = JlrsResult<Message>>;
+        F: Future;
 }
 
 /// A handle to the async runtime.
 ///
 /// This handle can be used to include files and send new tasks to the runtime. The runtime shuts
-/// down when the last handle is dropped and all active tasks have completed.
+/// down when the last handle is dropped and all active tasks have completed, or when
+/// [`AsyncJulia::shutdown`] is called.
 pub struct AsyncJulia<R> {
     sender: Sender<Message>,
     _runtime: PhantomData<R>,
@@ -238,6 +251,30 @@ where
         Dispatch::new(&self.sender, msg)
     }
 
+    /// Send a new async task to the runtime that can be cancelled.
+    ///
+    /// This method is equivalent to [`AsyncJulia::task`], but also returns a [`CancelHandle`]
+    /// that can be used to cancel the task. If the task is cancelled, the result sender receives
+    /// `RuntimeError::Cancelled`. See the [`cancel`] module for more information.
+    ///
+    /// [`cancel`]: crate::async_util::cancel
+    pub fn cancellable_task<A, O>(
+        &self,
+        task: A,
+        res_sender: O,
+    ) -> (Dispatch<A::Affinity>, CancelHandle)
+    where
+        A: AsyncTask,
+        O: OneshotSender<JlrsResult<A::Output>>,
+    {
+        let handle = CancelHandle::new();
+        let pending_task =
+            PendingTask::<_, _, CancellableTask>::new(task, res_sender, handle.clone());
+        let boxed = Box::new(pending_task);
+        let msg = MessageInner::Task(boxed).wrap();
+        (Dispatch::new(&self.sender, msg), handle)
+    }
+
     /// Register an async task.
     ///
     /// This method waits if there's no room in the channel. It takes one argument, the sending
@@ -389,12 +426,53 @@ where
         Dispatch::new(&self.sender, msg)
     }
 
+    /// Request the failures that occurred while the runtime was started.
+    ///
+    /// This method waits if there's no room in the channel. It takes the sending half of a
+    /// channel which is used to send the [`StartupReport`] back. The report contains the
+    /// exception thrown by the startup file and the precompile statements that failed.
+    pub fn startup_report<O>(&self, res_sender: O) -> Dispatch<DispatchMain>
+    where
+        O: OneshotSender<JlrsResult<StartupReport>>,
+    {
+        // Tasks are only handled after the report has been set.
+        self.blocking_task_with_affinity(
+            |_| Ok(STARTUP_REPORT.get().cloned().unwrap_or_default()),
+            res_sender,
+        )
+    }
+
+    /// Shut down the runtime as a blocking task.
+    ///
+    /// This method waits if there's no room in the channel. It takes two arguments, the exit
+    /// code that is passed to Julia's `atexit` hooks and the sending half of a channel which is
+    /// used to send a [`ShutdownReport`] back after the runtime has shut down.
+    ///
+    /// After this task has been received, new tasks are rejected: dispatching them returns
+    /// `RuntimeError::ChannelClosed`, or `TrySendError::Closed` if they're dispatched with one
+    /// of the `try_dispatch` methods. Tasks that are already in the queue or running are
+    /// completed first. Then the runtime waits for the worker threads to finish, runs the
+    /// `atexit` hooks, and sends the report. If multiple shutdown tasks are sent, only the first
+    /// one is handled.
+    pub fn shutdown<O>(&self, exit_code: i32, res_sender: O) -> Dispatch<DispatchMain>
+    where
+        O: OneshotSender<JlrsResult<ShutdownReport>>,
+    {
+        let pending_task = ShutdownTask::new(exit_code, res_sender);
+        let msg = MessageInner::Shutdown(Box::new(pending_task)).wrap();
+        Dispatch::new(&self.sender, msg)
+    }
+
     pub(crate) unsafe fn init<const N: usize>(
         builder: AsyncRuntimeBuilder<R>,
     ) -> JlrsResult<(Self, std::thread::JoinHandle<JlrsResult<()>>)> {
+        builder.builder.validate()?;
+        let precompile_statements = builder.builder.read_precompile_statements()?;
+
         let has_workers = builder.has_workers();
         let (sender, receiver) = channel(builder.channel_capacity.get(), has_workers);
-        let handle = R::spawn_thread(move || Self::run_async::<N>(builder, receiver));
+        let handle =
+            R::spawn_thread(move || Self::run_async::<N>(builder, receiver, precompile_statements));
 
         let julia = AsyncJulia {
             sender,
@@ -408,9 +486,14 @@ where
     pub(crate) unsafe fn init_async<const N: usize>(
         builder: AsyncRuntimeBuilder<R>,
     ) -> JlrsResult<(Self, R::RuntimeHandle)> {
+        builder.builder.validate()?;
+        let precompile_statements = builder.builder.read_precompile_statements()?;
+
         let has_workers = builder.has_workers();
         let (sender, receiver) = channel(builder.channel_capacity.get(), has_workers);
-        let handle = R::spawn_blocking(move || Self::run_async::<N>(builder, receiver));
+        let handle = R::spawn_blocking(move || {
+            Self::run_async::<N>(builder, receiver, precompile_statements)
+        });
 
         let julia = AsyncJulia {
             sender,
@@ -423,12 +506,18 @@ where
     fn run_async<const N: usize>(
         builder: AsyncRuntimeBuilder<R>,
         receiver: Receiver<Message>,
+        precompile_statements: Vec<String>,
     ) -> JlrsResult<()> {
         unsafe {
             if jl_is_initialized() != 0 || INIT.swap(true, Ordering::Relaxed) {
                 Err(RuntimeError::AlreadyInitialized)?;
             }
-            #[cfg(not(any(feature = "julia-1-10", feature = "julia-1-9")))]
+
+            #[cfg(not(any(
+                feature = "julia-1-11",
+                feature = "julia-1-10",
+                feature = "julia-1-9"
+            )))]
             {
                 if builder.n_threads == 0 {
                     jl_options.nthreads = -1;
@@ -437,7 +526,7 @@ where
                 }
             }
 
-            #[cfg(any(feature = "julia-1-10", feature = "julia-1-9"))]
+            #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
             {
                 if builder.n_threadsi != 0 {
                     if builder.n_threads == 0 {
@@ -467,22 +556,12 @@ where
                 }
             }
 
+            builder.builder.set_options();
+
             if let Some((ref julia_bindir, ref image_path)) = builder.builder.image {
                 let julia_bindir_str = julia_bindir.to_string_lossy().to_string();
                 let image_path_str = image_path.to_string_lossy().to_string();
 
-                if !julia_bindir.exists() {
-                    return Err(IOError::NotFound {
-                        path: julia_bindir_str,
-                    })?;
-                }
-
-                if !image_path.exists() {
-                    return Err(IOError::NotFound {
-                        path: image_path_str,
-                    })?;
-                }
-
                 let bindir = std::ffi::CString::new(julia_bindir_str).unwrap();
                 let im_rel_path = std::ffi::CString::new(image_path_str).unwrap();
 
@@ -490,6 +569,9 @@ where
             } else {
                 jl_init();
             }
+
+            let report = builder.builder.run_startup(&precompile_statements);
+            STARTUP_REPORT.set(report).ok();
         }
 
         let mut base_frame = StackFrame::<N>::new_n();
@@ -504,6 +586,7 @@ where
         receiver: Receiver<Message>,
         base_frame: &'ctx mut StackFrame<N>,
     ) -> Result<(), Box<JlrsError>> {
+        set_runtime_timer::<R>();
         let base_frame: &'static mut StackFrame<N> = std::mem::transmute(base_frame);
         let mut pinned = base_frame.pin();
 
@@ -533,17 +616,19 @@ where
 
         let recv_timeout = builder.recv_timeout;
 
-        #[cfg(any(feature = "julia-1-10", feature = "julia-1-9"))]
+        #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
         let mut workers = Vec::with_capacity(builder.n_workers);
-        #[cfg(any(feature = "julia-1-10", feature = "julia-1-9"))]
+        #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
         for i in 0..builder.n_workers {
             let worker = init_worker::<R, N>(i, recv_timeout, receiver.clone());
             workers.push(worker)
         }
 
-        #[cfg(any(feature = "julia-1-10", feature = "julia-1-9"))]
+        #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
         jl_enter_threaded_region();
 
+        let mut shutdown: Option<(Box<dyn ShutdownTaskEnvelope>, ShutdownReport)> = None;
+
         loop {
             if free_stacks.borrow().len() == 0 {
                 jl_process_events();
@@ -604,6 +689,23 @@ where
                         let stack = base_frame.sync_stack();
                         task.call(stack);
                     }
+                    MessageInner::Shutdown(task) => {
+                        if shutdown.is_none() {
+                            let n_running = running_tasks
+                                .borrow()
+                                .iter()
+                                .filter(|t| t.is_some())
+                                .count();
+                            let report = ShutdownReport {
+                                pending_tasks: n_running + receiver.len(),
+                            };
+
+                            // Stop accepting new tasks, the loop ends after the remaining tasks
+                            // have been received.
+                            receiver.close();
+                            shutdown = Some((task, report));
+                        }
+                    }
                 },
                 Some(Err(_)) => break,
             }
@@ -621,7 +723,7 @@ where
             }
         }
 
-        #[cfg(any(feature = "julia-1-10", feature = "julia-1-9"))]
+        #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
         for worker in workers.into_iter() {
             loop {
                 if worker.is_finished() {
@@ -634,14 +736,46 @@ where
             }
         }
 
-        #[cfg(any(feature = "julia-1-10", feature = "julia-1-9"))]
+        #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
         jl_exit_threaded_region();
 
-        jl_atexit_hook(0);
+        match shutdown {
+            Some((task, report)) => {
+                jl_atexit_hook(task.exit_code());
+                task.respond(report);
+            }
+            None => jl_atexit_hook(0),
+        }
+
         Ok(())
     }
 }
 
+/// Information about the shutdown of the async runtime, it's sent back by
+/// [`AsyncJulia::shutdown`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShutdownReport {
+    pending_tasks: usize,
+}
+
+impl ShutdownReport {
+    /// Returns the number of tasks that were still pending when the runtime received the
+    /// shutdown task, i.e. tasks that were waiting in the queue or running on the main thread.
+    ///
+    /// Tasks that were running on a worker thread are not counted.
+    #[inline]
+    pub fn pending_tasks(&self) -> usize {
+        self.pending_tasks
+    }
+
+    /// Returns `true` if any tasks were still pending when the runtime received the shutdown
+    /// task.
+    #[inline]
+    pub fn had_pending_tasks(&self) -> bool {
+        self.pending_tasks > 0
+    }
+}
+
 /// The message type used by the async runtime for communication.
 pub struct Message {
     inner: MessageInner,
@@ -653,6 +787,7 @@ pub(crate) enum MessageInner {
     PostBlockingTask(Box<dyn BlockingTaskEnvelope>),
     Include(Box<dyn IncludeTaskEnvelope>),
     ErrorColor(Box<dyn SetErrorColorTaskEnvelope>),
+    Shutdown(Box<dyn ShutdownTaskEnvelope>),
 }
 
 impl fmt::Debug for Message {
@@ -1,5 +1,5 @@
 use std::sync::{
-    atomic::{AtomicUsize, Ordering},
+    atomic::{AtomicBool, AtomicUsize, Ordering},
     Arc,
 };
 
@@ -8,7 +8,10 @@ use futures::Future;
 use futures_concurrency::future::Race;
 use jlrs_macros::julia_version;
 
-use crate::error::{JlrsResult, RuntimeError};
+use crate::{
+    async_util::channel::TrySendError,
+    error::{JlrsResult, RuntimeError},
+};
 
 struct Queues<T> {
     main_queue: Queue<T>,
@@ -16,6 +19,12 @@ struct Queues<T> {
     worker_queue: Option<Queue<T>>,
     // there's no method that closes the queue, so the number of senders must be tracked.
     n_senders: AtomicUsize,
+    // set when the runtime shuts down, new items are rejected and the receivers stop waiting
+    // after the remaining items have been received.
+    closed: AtomicBool,
+    // the number of items that are being pushed. The receivers keep waiting for these items
+    // after the queues have been closed.
+    n_sending: AtomicUsize,
 }
 
 impl<T> Queues<T> {
@@ -32,8 +41,41 @@ impl<T> Queues<T> {
             any_queue,
             worker_queue,
             n_senders: AtomicUsize::new(1),
+            closed: AtomicBool::new(false),
+            n_sending: AtomicUsize::new(0),
+        })
+    }
+
+    // Returns `None` if the queues have been closed. Otherwise, the receivers don't consider the
+    // queues closed until the guard has been dropped.
+    #[inline]
+    fn start_send(&self) -> Option<SendGuard> {
+        self.n_sending.fetch_add(1, Ordering::SeqCst);
+        if self.closed.load(Ordering::SeqCst) {
+            self.n_sending.fetch_sub(1, Ordering::SeqCst);
+            return None;
+        }
+
+        Some(SendGuard {
+            n_sending: &self.n_sending,
         })
     }
+
+    #[inline]
+    fn is_closed(&self) -> bool {
+        (self.closed.load(Ordering::SeqCst) && self.n_sending.load(Ordering::SeqCst) == 0)
+            || self.n_senders.load(Ordering::Acquire) == 0
+    }
+}
+
+struct SendGuard<'a> {
+    n_sending: &'a AtomicUsize,
+}
+
+impl Drop for SendGuard<'_> {
+    fn drop(&mut self) {
+        self.n_sending.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
 pub(crate) struct Sender<T> {
@@ -59,22 +101,36 @@ impl<T> Clone for Sender<T> {
 
 impl<T: Send> Sender<T> {
     #[inline]
-    pub(crate) async fn send(&self, item: T) {
+    pub(crate) async fn send(&self, item: T) -> JlrsResult<()> {
+        let _guard = match self.queues.start_send() {
+            Some(guard) => guard,
+            None => Err(RuntimeError::ChannelClosed)?,
+        };
+
         if let Some(ref q) = self.queues.any_queue {
             q.push(item).await
         } else {
-            self.send_main(item).await
+            self.queues.main_queue.push(item).await
         }
+
+        Ok(())
     }
 
     #[inline]
-    pub(crate) fn try_send(&self, item: T) -> Option<T> {
+    pub(crate) fn try_send(&self, item: T) -> Result<(), TrySendError<T>> {
+        let _guard = match self.queues.start_send() {
+            Some(guard) => guard,
+            None => return Err(TrySendError::Closed(item)),
+        };
+
         if let Some(ref q) = self.queues.any_queue {
-            q.try_push(item).err()?;
+            q.try_push(item).map_err(TrySendError::Full)
         } else {
-            self.try_send_main(item)?;
+            self.queues
+                .main_queue
+                .try_push(item)
+                .map_err(TrySendError::Full)
         }
-        None
     }
 
     #[inline]
@@ -86,14 +142,27 @@ impl<T: Send> Sender<T> {
     }
 
     #[inline]
-    pub(crate) async fn send_main(&self, item: T) {
-        self.queues.main_queue.push(item).await
+    pub(crate) async fn send_main(&self, item: T) -> JlrsResult<()> {
+        let _guard = match self.queues.start_send() {
+            Some(guard) => guard,
+            None => Err(RuntimeError::ChannelClosed)?,
+        };
+
+        self.queues.main_queue.push(item).await;
+        Ok(())
     }
 
     #[inline]
-    pub(crate) fn try_send_main(&self, item: T) -> Option<T> {
-        self.queues.main_queue.try_push(item).err()?;
-        None
+    pub(crate) fn try_send_main(&self, item: T) -> Result<(), TrySendError<T>> {
+        let _guard = match self.queues.start_send() {
+            Some(guard) => guard,
+            None => return Err(TrySendError::Closed(item)),
+        };
+
+        self.queues
+            .main_queue
+            .try_push(item)
+            .map_err(TrySendError::Full)
     }
 
     #[inline]
@@ -105,22 +174,36 @@ impl<T: Send> Sender<T> {
     }
 
     #[inline]
-    pub(crate) async fn send_worker(&self, item: T) {
+    pub(crate) async fn send_worker(&self, item: T) -> JlrsResult<()> {
+        let _guard = match self.queues.start_send() {
+            Some(guard) => guard,
+            None => Err(RuntimeError::ChannelClosed)?,
+        };
+
         if let Some(ref q) = self.queues.worker_queue {
             q.push(item).await
         } else {
-            self.send_main(item).await
+            self.queues.main_queue.push(item).await
         }
+
+        Ok(())
     }
 
     #[inline]
-    pub(crate) fn try_send_worker(&self, item: T) -> Option<T> {
+    pub(crate) fn try_send_worker(&self, item: T) -> Result<(), TrySendError<T>> {
+        let _guard = match self.queues.start_send() {
+            Some(guard) => guard,
+            None => return Err(TrySendError::Closed(item)),
+        };
+
         if let Some(ref q) = self.queues.worker_queue {
-            q.try_push(item).err()?;
+            q.try_push(item).map_err(TrySendError::Full)
         } else {
-            self.try_send_main(item)?;
+            self.queues
+                .main_queue
+                .try_push(item)
+                .map_err(TrySendError::Full)
         }
-        None
     }
 
     #[inline]
@@ -151,8 +234,27 @@ impl<T> Clone for Receiver<T> {
 }
 
 impl<T: Send> Receiver<T> {
+    // Items that are sent after the queue has been closed are rejected, the items that are
+    // already in the queue or are being pushed can still be received.
+    #[inline]
+    pub(crate) fn close(&self) {
+        self.queue.closed.store(true, Ordering::SeqCst);
+    }
+
+    // The total number of items in all queues.
+    pub(crate) fn len(&self) -> usize {
+        let mut len = self.queue.main_queue.len();
+        if let Some(ref q) = self.queue.any_queue {
+            len += q.len();
+        }
+        if let Some(ref q) = self.queue.worker_queue {
+            len += q.len();
+        }
+        len
+    }
+
     pub(crate) async fn recv_main(&self) -> JlrsResult<T> {
-        if self.queue.n_senders.load(Ordering::Acquire) == 0 {
+        if self.queue.is_closed() {
             return match self.try_recv_main() {
                 Some(t) => Ok(t),
                 None => Err(RuntimeError::ChannelClosed)?,
@@ -181,7 +283,7 @@ impl<T: Send> Receiver<T> {
 
     #[julia_version(since = "1.9")]
     pub(crate) async fn recv_worker(&self) -> JlrsResult<T> {
-        if self.queue.n_senders.load(Ordering::Acquire) == 0 {
+        if self.queue.is_closed() {
             return match self.try_recv_worker() {
                 Some(t) => Ok(t),
                 None => Err(RuntimeError::ChannelClosed)?,
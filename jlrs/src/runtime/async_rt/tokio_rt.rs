@@ -28,7 +28,7 @@ use crate::{
         Channel, ChannelReceiver, ChannelSender, OneshotSender, SendError, TrySendError,
     },
     error::{JlrsError, JlrsResult},
-    runtime::async_rt::{AsyncRuntime, Message},
+    runtime::async_rt::AsyncRuntime,
 };
 
 /// Struct for which [`AsyncRuntime`] is implemented using tokio.
@@ -85,9 +85,9 @@ impl AsyncRuntime for Tokio {
     }
 
     #[inline]
-    async fn timeout<F>(duration: Duration, future: F) -> Option<JlrsResult<Message>>
+    async fn timeout<F>(duration: Duration, future: F) -> Option<F::Output>
     where
-        F: Future<Output = JlrsResult<Message>>,
+        F: Future,
     {
         tokio::time::timeout(duration, future).await.ok()
     }
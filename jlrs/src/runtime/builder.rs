@@ -1,22 +1,37 @@
 //! Build a runtime.
 //!
 //! Before Julia can be used it must be initialized. The builders provided by this module must be
-//! used to initialize Julia and set custom parameters. The [`RuntimeBuilder`] only lets you
-//! provide a custom system image, [`AsyncRuntimeBuilder`] provides additional methods to set the
-//! number of threads available to Julia among others.
+//! used to initialize Julia and set custom parameters. The [`RuntimeBuilder`] lets you provide a
+//! custom system image, precompile statements, and startup options like the optimization level
+//! and active project. [`AsyncRuntimeBuilder`] provides additional methods to set the number of
+//! threads available to Julia among others.
 
 #[cfg(feature = "async-rt")]
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 
-#[cfg(feature = "async-rt")]
 use jlrs_macros::julia_version;
 
 #[cfg(feature = "sync-rt")]
 use super::sync_rt::PendingJulia;
-#[cfg(any(feature = "sync-rt", feature = "async-rt"))]
-use crate::error::JlrsResult;
 use crate::InstallJlrsCore;
+#[cfg(any(feature = "sync-rt", feature = "async-rt"))]
+use crate::{
+    data::managed::{value::Value, Managed},
+    error::{IOError, JlrsError, JlrsResult, RuntimeError, CANNOT_DISPLAY_VALUE},
+    memory::target::unrooted::Unrooted,
+};
+
+/// Bounds checking mode, equivalent to Julia's `--check-bounds` option.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CheckBounds {
+    /// Respect `@inbounds` annotations.
+    Default,
+    /// Always check bounds, `@inbounds` annotations are ignored.
+    On,
+    /// Never check bounds.
+    Off,
+}
 
 /// Build a sync runtime.
 ///
@@ -27,6 +42,18 @@ use crate::InstallJlrsCore;
 pub struct RuntimeBuilder {
     pub(crate) image: Option<(PathBuf, PathBuf)>,
     pub(crate) install_jlrs_core: InstallJlrsCore,
+    pub(crate) precompile_statements: Vec<String>,
+    pub(crate) precompile_files: Vec<PathBuf>,
+    pub(crate) optimization_level: Option<u8>,
+    pub(crate) check_bounds: Option<CheckBounds>,
+    pub(crate) project: Option<PathBuf>,
+    pub(crate) depot_path: Vec<PathBuf>,
+    pub(crate) compiled_modules: Option<bool>,
+    #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
+    pub(crate) heap_size_hint: Option<u64>,
+    pub(crate) startup_file: Option<bool>,
+    pub(crate) handle_signals: Option<bool>,
+    pub(crate) pkg_offline: Option<bool>,
 }
 
 cfg_if::cfg_if! {
@@ -46,9 +73,9 @@ cfg_if::cfg_if! {
             pub(crate) n_threads: usize,
             pub(crate) channel_capacity: NonZeroUsize,
             pub(crate) recv_timeout: Duration,
-            #[cfg(any(feature = "julia-1-10", feature = "julia-1-9"))]
+            #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
             pub(crate) n_threadsi: usize,
-            #[cfg(any(feature = "julia-1-10", feature = "julia-1-9"))]
+            #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
             pub(crate) n_workers: usize,
             _runtime: PhantomData<R>,
         }
@@ -120,10 +147,10 @@ cfg_if::cfg_if! {
             /// compatible Julia binary (eg `${JULIA_DIR}/bin`), the second is the path to a
             /// system image.
             ///
-            /// A custom system image can be created with [`PackageCompiler`].
+            /// A custom system image can be created with [`PackageCompiler`]. If either path
+            /// doesn't exist, an error is returned when the runtime is started.
             ///
             /// [`PackageCompiler`]: https://julialang.github.io/PackageCompiler.jl
-            // TODO: Check if these paths exist.
             #[inline]
             pub fn image<P, Q>(mut self, julia_bindir: P, image_path: Q) -> Self
             where
@@ -147,6 +174,124 @@ cfg_if::cfg_if! {
                 self
             }
 
+            /// Evaluate precompile statements after Julia has been initialized.
+            ///
+            /// See [`RuntimeBuilder::precompile_statements`] for more information.
+            #[inline]
+            pub fn precompile_statements<I, S>(mut self, statements: I) -> Self
+            where
+                I: IntoIterator<Item = S>,
+                S: Into<String>,
+            {
+                self.builder = self.builder.precompile_statements(statements);
+                self
+            }
+
+            /// Evaluate the precompile statements in the file at `path` after Julia has been
+            /// initialized.
+            ///
+            /// See [`RuntimeBuilder::precompile_file`] for more information.
+            #[inline]
+            pub fn precompile_file<P>(mut self, path: P) -> Self
+            where
+                P: AsRef<Path>,
+            {
+                self.builder = self.builder.precompile_file(path);
+                self
+            }
+
+            /// Set the optimization level, equivalent to Julia's `-O` option.
+            ///
+            /// See [`RuntimeBuilder::optimization_level`] for more information.
+            #[inline]
+            pub fn optimization_level(mut self, level: u8) -> Self {
+                self.builder = self.builder.optimization_level(level);
+                self
+            }
+
+            /// Set the bounds checking mode, equivalent to Julia's `--check-bounds` option.
+            ///
+            /// See [`RuntimeBuilder::check_bounds`] for more information.
+            #[inline]
+            pub fn check_bounds(mut self, mode: CheckBounds) -> Self {
+                self.builder = self.builder.check_bounds(mode);
+                self
+            }
+
+            /// Set the active project, equivalent to Julia's `--project` option.
+            ///
+            /// See [`RuntimeBuilder::project`] for more information.
+            #[inline]
+            pub fn project<P>(mut self, project: P) -> Self
+            where
+                P: AsRef<Path>,
+            {
+                self.builder = self.builder.project(project);
+                self
+            }
+
+            /// Set the depot path.
+            ///
+            /// See [`RuntimeBuilder::depot_path`] for more information.
+            #[inline]
+            pub fn depot_path<I, P>(mut self, paths: I) -> Self
+            where
+                I: IntoIterator<Item = P>,
+                P: AsRef<Path>,
+            {
+                self.builder = self.builder.depot_path(paths);
+                self
+            }
+
+            /// Enable or disable using compiled modules, equivalent to Julia's
+            /// `--compiled-modules` option.
+            ///
+            /// See [`RuntimeBuilder::compiled_modules`] for more information.
+            #[inline]
+            pub fn compiled_modules(mut self, enable: bool) -> Self {
+                self.builder = self.builder.compiled_modules(enable);
+                self
+            }
+
+            #[julia_version(since = "1.9")]
+            /// Set the heap size hint in bytes, equivalent to Julia's `--heap-size-hint` option.
+            ///
+            /// See [`RuntimeBuilder::heap_size_hint`] for more information.
+            #[inline]
+            pub fn heap_size_hint(mut self, size: u64) -> Self {
+                self.builder = self.builder.heap_size_hint(size);
+                self
+            }
+
+            /// Enable or disable loading the startup file, equivalent to Julia's
+            /// `--startup-file` option.
+            ///
+            /// See [`RuntimeBuilder::startup_file`] for more information.
+            #[inline]
+            pub fn startup_file(mut self, enable: bool) -> Self {
+                self.builder = self.builder.startup_file(enable);
+                self
+            }
+
+            /// Enable or disable Julia's signal handlers, equivalent to Julia's
+            /// `--handle-signals` option.
+            ///
+            /// See [`RuntimeBuilder::handle_signals`] for more information.
+            #[inline]
+            pub fn handle_signals(mut self, enable: bool) -> Self {
+                self.builder = self.builder.handle_signals(enable);
+                self
+            }
+
+            /// Enable or disable the offline mode of the package manager.
+            ///
+            /// See [`RuntimeBuilder::pkg_offline`] for more information.
+            #[inline]
+            pub fn pkg_offline(mut self, enable: bool) -> Self {
+                self.builder = self.builder.pkg_offline(enable);
+                self
+            }
+
             /// Initialize Julia on another thread.
             ///
             /// You must set the maximum number of concurrent tasks with the `N` const generic.
@@ -165,12 +310,12 @@ cfg_if::cfg_if! {
 
             #[inline]
             pub(crate) fn has_workers(&self) -> bool {
-                #[cfg(any(feature = "julia-1-10", feature = "julia-1-9"))]
+                #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
                 {
                     self.n_workers > 0
                 }
 
-                #[cfg(not(any(feature = "julia-1-10", feature = "julia-1-9")))]
+                #[cfg(not(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9")))]
                 {
                     false
                 }
@@ -186,6 +331,18 @@ impl RuntimeBuilder {
         RuntimeBuilder {
             image: None,
             install_jlrs_core: InstallJlrsCore::Default,
+            precompile_statements: Vec::new(),
+            precompile_files: Vec::new(),
+            optimization_level: None,
+            check_bounds: None,
+            project: None,
+            depot_path: Vec::new(),
+            compiled_modules: None,
+            #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
+            heap_size_hint: None,
+            startup_file: None,
+            handle_signals: None,
+            pkg_offline: None,
         }
     }
 
@@ -241,9 +398,9 @@ impl RuntimeBuilder {
             n_threads: 0,
             channel_capacity: unsafe { NonZeroUsize::new_unchecked(16) },
             recv_timeout: Duration::from_millis(1),
-            #[cfg(any(feature = "julia-1-10", feature = "julia-1-9"))]
+            #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
             n_threadsi: 0,
-            #[cfg(any(feature = "julia-1-10", feature = "julia-1-9"))]
+            #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
             n_workers: 0,
             _runtime: PhantomData,
         }
@@ -255,10 +412,10 @@ impl RuntimeBuilder {
     /// `image_path`. The first is the absolute path to a directory that contains a compatible
     /// Julia binary (eg `${JULIA_DIR}/bin`), the second is the path to a system image.
     ///
-    /// A custom system image can be created with [`PackageCompiler`].
+    /// A custom system image can be created with [`PackageCompiler`]. If either path doesn't
+    /// exist, an error is returned when the runtime is started.
     ///
     /// [`PackageCompiler`]: https://julialang.github.io/PackageCompiler.jl
-    // TODO: Check if these paths exist.
     #[inline]
     pub fn image<P, Q>(mut self, julia_bindir: P, image_path: Q) -> Self
     where
@@ -281,4 +438,352 @@ impl RuntimeBuilder {
         self.install_jlrs_core = install;
         self
     }
+
+    /// Evaluate precompile statements after Julia has been initialized.
+    ///
+    /// Calling a function for the first time is expensive because it must be compiled first. A
+    /// precompile statement like `precompile(Tuple{typeof(Base.sum), Vector{Float64}})` compiles
+    /// a method ahead of time. The statements are evaluated in the `Main` module before the
+    /// runtime is started, statements that throw an exception are skipped. Skipped statements
+    /// are listed in the [`StartupReport`]. This method can be called multiple times, the
+    /// statements are evaluated in order.
+    #[inline]
+    pub fn precompile_statements<I, S>(mut self, statements: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.precompile_statements
+            .extend(statements.into_iter().map(Into::into));
+        self
+    }
+
+    /// Evaluate the precompile statements in the file at `path` after Julia has been
+    /// initialized.
+    ///
+    /// The file must contain one statement per line, e.g. the output of
+    /// `julia --trace-compile=path`. The statements in these files are evaluated before the
+    /// statements provided with [`RuntimeBuilder::precompile_statements`]. If the file doesn't
+    /// exist, an error is returned when the runtime is started.
+    #[inline]
+    pub fn precompile_file<P>(mut self, path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        self.precompile_files.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set the optimization level, equivalent to Julia's `-O` option.
+    ///
+    /// The level must be between 0 and 3, an error is returned when the runtime is started if
+    /// it's not. If this option isn't set, the default level 2 is used.
+    #[inline]
+    pub fn optimization_level(mut self, level: u8) -> Self {
+        self.optimization_level = Some(level);
+        self
+    }
+
+    /// Set the bounds checking mode, equivalent to Julia's `--check-bounds` option.
+    #[inline]
+    pub fn check_bounds(mut self, mode: CheckBounds) -> Self {
+        self.check_bounds = Some(mode);
+        self
+    }
+
+    /// Set the active project, equivalent to Julia's `--project` option.
+    ///
+    /// The project is either the path to a project directory or file, or a special value like
+    /// `@.`. If a path is provided that doesn't exist, an error is returned when the runtime is
+    /// started.
+    #[inline]
+    pub fn project<P>(mut self, project: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        self.project = Some(project.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set the depot path.
+    ///
+    /// The depot path is where Julia looks for installed packages, compiled modules, and
+    /// registries among other things. It's set by setting the `JULIA_DEPOT_PATH` environment
+    /// variable before Julia is initialized, the directories don't need to exist.
+    #[inline]
+    pub fn depot_path<I, P>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        self.depot_path = paths
+            .into_iter()
+            .map(|path| path.as_ref().to_path_buf())
+            .collect();
+        self
+    }
+
+    /// Enable or disable using compiled modules, equivalent to Julia's `--compiled-modules`
+    /// option.
+    ///
+    /// If compiled modules are disabled, packages are not precompiled and loaded from their
+    /// cached compiled versions. This is enabled by default.
+    #[inline]
+    pub fn compiled_modules(mut self, enable: bool) -> Self {
+        self.compiled_modules = Some(enable);
+        self
+    }
+
+    #[julia_version(since = "1.9")]
+    /// Set the heap size hint in bytes, equivalent to Julia's `--heap-size-hint` option.
+    ///
+    /// The garbage collector runs more aggressively when the size of the heap approaches this
+    /// limit.
+    #[inline]
+    pub fn heap_size_hint(mut self, size: u64) -> Self {
+        self.heap_size_hint = Some(size);
+        self
+    }
+
+    /// Enable or disable loading the startup file, equivalent to Julia's `--startup-file`
+    /// option.
+    ///
+    /// Unlike the Julia executable, embedded Julia doesn't load the startup file
+    /// `~/.julia/config/startup.jl` by default. If this option is enabled, the startup file is
+    /// evaluated after Julia has been initialized. If it throws an exception, the runtime is
+    /// started anyway and the error message is recorded in the [`StartupReport`].
+    #[inline]
+    pub fn startup_file(mut self, enable: bool) -> Self {
+        self.startup_file = Some(enable);
+        self
+    }
+
+    /// Enable or disable Julia's signal handlers, equivalent to Julia's `--handle-signals`
+    /// option.
+    ///
+    /// Julia installs its own signal handlers by default, these can conflict with the signal
+    /// handlers of the embedding application.
+    #[inline]
+    pub fn handle_signals(mut self, enable: bool) -> Self {
+        self.handle_signals = Some(enable);
+        self
+    }
+
+    /// Enable or disable the offline mode of the package manager.
+    ///
+    /// In offline mode the package manager avoids accessing the network where it can,
+    /// operations that can't be completed without downloading something fail instead. This
+    /// includes installing JlrsCore when the runtime is initialized, so it must already be
+    /// available in the active environment. Offline mode is best-effort, package code like build
+    /// scripts can still access the network. It's set by
+    /// setting the `JULIA_PKG_OFFLINE` environment variable before Julia is initialized, it can
+    /// be changed later with [`Pkg::offline`].
+    ///
+    /// [`Pkg::offline`]: crate::pkg::Pkg::offline
+    #[inline]
+    pub fn pkg_offline(mut self, enable: bool) -> Self {
+        self.pkg_offline = Some(enable);
+        self
+    }
+
+    // Check if the provided options are valid before Julia is initialized.
+    #[cfg(any(feature = "sync-rt", feature = "async-rt"))]
+    pub(crate) fn validate(&self) -> JlrsResult<()> {
+        if let Some((ref julia_bindir, ref image_path)) = self.image {
+            if !julia_bindir.exists() {
+                Err(IOError::NotFound {
+                    path: julia_bindir.to_string_lossy().to_string(),
+                })?;
+            }
+
+            if !image_path.exists() {
+                Err(IOError::NotFound {
+                    path: image_path.to_string_lossy().to_string(),
+                })?;
+            }
+        }
+
+        if let Some(level) = self.optimization_level {
+            if level > 3 {
+                Err(RuntimeError::InvalidOption {
+                    option: "optimization level".into(),
+                    reason: format!("{} is not between 0 and 3", level),
+                })?;
+            }
+        }
+
+        if let Some(ref project) = self.project {
+            let is_special = project.to_string_lossy().starts_with('@');
+            if !is_special && !project.exists() {
+                Err(IOError::NotFound {
+                    path: project.to_string_lossy().to_string(),
+                })?;
+            }
+        }
+
+        if !self.depot_path.is_empty() {
+            std::env::join_paths(self.depot_path.iter()).map_err(|e| {
+                RuntimeError::InvalidOption {
+                    option: "depot path".into(),
+                    reason: e.to_string(),
+                }
+            })?;
+        }
+
+        Ok(())
+    }
+
+    // Set the startup options, must be called before Julia is initialized.
+    #[cfg(any(feature = "sync-rt", feature = "async-rt"))]
+    pub(crate) unsafe fn set_options(&self) {
+        use jl_sys::jl_options;
+
+        if let Some(level) = self.optimization_level {
+            jl_options.opt_level = level as _;
+        }
+
+        if let Some(mode) = self.check_bounds {
+            jl_options.check_bounds = match mode {
+                CheckBounds::Default => 0,
+                CheckBounds::On => 1,
+                CheckBounds::Off => 2,
+            };
+        }
+
+        if let Some(ref project) = self.project {
+            // The string must outlive the runtime.
+            let project = std::ffi::CString::new(project.to_string_lossy().as_bytes()).unwrap();
+            jl_options.project = project.into_raw();
+        }
+
+        if !self.depot_path.is_empty() {
+            let depot_path = std::env::join_paths(self.depot_path.iter()).unwrap();
+            std::env::set_var("JULIA_DEPOT_PATH", depot_path);
+        }
+
+        if let Some(enable) = self.compiled_modules {
+            jl_options.use_compiled_modules = enable as _;
+        }
+
+        #[cfg(any(feature = "julia-1-11", feature = "julia-1-10", feature = "julia-1-9"))]
+        if let Some(size) = self.heap_size_hint {
+            jl_options.heap_size_hint = size;
+        }
+
+        if let Some(enable) = self.startup_file {
+            jl_options.startupfile = if enable { 1 } else { 2 };
+        }
+
+        if let Some(enable) = self.handle_signals {
+            jl_options.handle_signals = enable as _;
+        }
+
+        if let Some(enable) = self.pkg_offline {
+            std::env::set_var("JULIA_PKG_OFFLINE", if enable { "true" } else { "false" });
+        }
+    }
+
+    // Evaluate the startup file if this has been enabled and the precompile statements. Must be
+    // called after Julia has been initialized. Like the Julia executable, an exception thrown by
+    // the startup file doesn't prevent the runtime from starting. Failed precompile statements
+    // are skipped. Both are recorded in the returned report.
+    #[cfg(any(feature = "sync-rt", feature = "async-rt"))]
+    pub(crate) unsafe fn run_startup(&self, precompile_statements: &[String]) -> StartupReport {
+        StartupReport {
+            startup_file_error: self.load_startup_file(),
+            failed_precompile_statements: run_precompile_statements(precompile_statements),
+        }
+    }
+
+    // Evaluate the startup file if this has been enabled, returns the error message if it throws
+    // an exception.
+    #[cfg(any(feature = "sync-rt", feature = "async-rt"))]
+    unsafe fn load_startup_file(&self) -> Option<String> {
+        if self.startup_file != Some(true) {
+            return None;
+        }
+
+        let unrooted = Unrooted::new();
+        match Value::eval_string(unrooted, "Base.load_julia_startup()") {
+            Ok(_) => None,
+            Err(e) => Some(e.as_value().error_string_or(CANNOT_DISPLAY_VALUE)),
+        }
+    }
+
+    // Read the precompile statements from the provided files and append the statements that were
+    // provided directly. Must be called before Julia is initialized so a missing file doesn't
+    // leave the runtime partially initialized.
+    #[cfg(any(feature = "sync-rt", feature = "async-rt"))]
+    pub(crate) fn read_precompile_statements(&self) -> JlrsResult<Vec<String>> {
+        let mut statements = Vec::new();
+
+        for path in self.precompile_files.iter() {
+            if !path.exists() {
+                Err(IOError::NotFound {
+                    path: path.to_string_lossy().to_string(),
+                })?;
+            }
+
+            let contents = std::fs::read_to_string(path).map_err(JlrsError::other)?;
+            statements.extend(
+                contents
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(String::from),
+            );
+        }
+
+        statements.extend(self.precompile_statements.iter().cloned());
+        Ok(statements)
+    }
+}
+
+// Evaluate the precompile statements, returns the statements that failed.
+//
+// Safety: must be called from a thread known to Julia after Julia has been initialized.
+#[cfg(any(feature = "sync-rt", feature = "async-rt"))]
+unsafe fn run_precompile_statements(statements: &[String]) -> Vec<String> {
+    let unrooted = Unrooted::new();
+
+    // Statements that refer to code that hasn't been loaded throw an exception, they're skipped.
+    statements
+        .iter()
+        .filter(|statement| Value::eval_string(unrooted, statement.as_str()).is_err())
+        .cloned()
+        .collect()
+}
+
+/// Failures that occurred while the runtime was started, after Julia had been initialized.
+///
+/// These failures don't prevent the runtime from starting. Like the Julia executable, the runtime
+/// starts if the startup file throws an exception. Precompile statements that fail, e.g. because
+/// they refer to code that hasn't been loaded, are skipped.
+#[cfg(any(feature = "sync-rt", feature = "async-rt"))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StartupReport {
+    startup_file_error: Option<String>,
+    failed_precompile_statements: Vec<String>,
+}
+
+#[cfg(any(feature = "sync-rt", feature = "async-rt"))]
+impl StartupReport {
+    /// Returns the error message of the exception thrown by the startup file, or `None` if it
+    /// wasn't loaded or didn't throw.
+    #[inline]
+    pub fn startup_file_error(&self) -> Option<&str> {
+        self.startup_file_error.as_deref()
+    }
+
+    /// Returns the precompile statements that failed and have been skipped.
+    #[inline]
+    pub fn failed_precompile_statements(&self) -> &[String] {
+        &self.failed_precompile_statements
+    }
+
+    /// Returns `true` if nothing failed.
+    #[inline]
+    pub fn is_clean(&self) -> bool {
+        self.startup_file_error.is_none() && self.failed_precompile_statements.is_empty()
+    }
 }
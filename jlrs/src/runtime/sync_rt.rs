@@ -18,7 +18,10 @@ use crate::{
         stack_frame::{PinnedFrame, StackFrame},
         target::frame::GcFrame,
     },
-    runtime::{builder::RuntimeBuilder, INIT},
+    runtime::{
+        builder::{RuntimeBuilder, StartupReport},
+        INIT,
+    },
     INSTALL_METHOD,
 };
 
@@ -26,31 +29,25 @@ use crate::{
 ///
 /// This pending instance can be activated by calling [`PendingJulia::instance`].
 pub struct PendingJulia {
+    startup_report: StartupReport,
     _not_send_sync: PhantomData<*mut c_void>,
 }
 
 impl PendingJulia {
     pub(crate) unsafe fn init(builder: RuntimeBuilder) -> JlrsResult<Self> {
+        builder.validate()?;
+        let precompile_statements = builder.read_precompile_statements()?;
+
         if jl_is_initialized() != 0 || INIT.swap(true, Ordering::Relaxed) {
             Err(RuntimeError::AlreadyInitialized)?;
         }
 
-        if let Some((julia_bindir, image_path)) = builder.image {
+        builder.set_options();
+
+        if let Some((ref julia_bindir, ref image_path)) = builder.image {
             let julia_bindir_str = julia_bindir.to_string_lossy().to_string();
             let image_path_str = image_path.to_string_lossy().to_string();
 
-            if !julia_bindir.exists() {
-                return Err(IOError::NotFound {
-                    path: julia_bindir_str,
-                })?;
-            }
-
-            if !image_path.exists() {
-                return Err(IOError::NotFound {
-                    path: image_path_str,
-                })?;
-            }
-
             let bindir = std::ffi::CString::new(julia_bindir_str).unwrap();
             let im_rel_path = std::ffi::CString::new(image_path_str).unwrap();
 
@@ -60,15 +57,24 @@ impl PendingJulia {
         }
 
         assert!(jl_is_initialized() != 0);
+        let startup_report = builder.run_startup(&precompile_statements);
 
         let install_method = builder.install_jlrs_core.clone();
         INSTALL_METHOD.get_or_init(|| install_method);
 
         Ok(PendingJulia {
+            startup_report,
             _not_send_sync: PhantomData,
         })
     }
 
+    /// Returns the failures that occurred while Julia was started, i.e. the exception thrown by
+    /// the startup file and the precompile statements that failed.
+    #[inline]
+    pub fn startup_report(&self) -> &StartupReport {
+        &self.startup_report
+    }
+
     /// Activate the pending instance.
     ///
     /// The provided `StackFrame` should be allocated on the stack.
@@ -90,6 +96,21 @@ impl PendingJulia {
             wrapped
         }
     }
+
+    /// Shut down Julia.
+    ///
+    /// Julia's `atexit` hooks are called with `exit_code`, finalizers are run, and the remaining
+    /// Julia tasks are stopped. Dropping a `PendingJulia` shuts Julia down with exit code 0.
+    ///
+    /// Julia can't be initialized again after it has been shut down.
+    pub fn shutdown(self, exit_code: i32) {
+        unsafe {
+            jl_atexit_hook(exit_code);
+        }
+
+        // The atexit hooks must not be called again when self is dropped.
+        std::mem::forget(self);
+    }
 }
 
 impl Drop for PendingJulia {
@@ -108,10 +129,10 @@ pub struct Julia<'context> {
 
 impl Julia<'_> {
     /// Enable or disable colored error messages originating from Julia. If this is enabled the
-    /// error message in [`JlrsError::Exception`] can contain ANSI color codes. This feature is
-    /// disabled by default.
+    /// error message in [`JlrsError::JuliaException`] can contain ANSI color codes. This feature
+    /// is disabled by default.
     ///
-    /// [`JlrsError::Exception`]: crate::error::JlrsError::Exception
+    /// [`JlrsError::JuliaException`]: crate::error::JlrsError::JuliaException
     pub fn error_color(&mut self, enable: bool) -> JlrsResult<()> {
         self.scope(|frame| unsafe {
             let enable = if enable {
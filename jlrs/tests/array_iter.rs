@@ -0,0 +1,135 @@
+mod util;
+
+#[cfg(test)]
+#[cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::{data::managed::array::dimensions::Dims, prelude::*};
+
+    use super::util::JULIA;
+
+    fn indexed_iter() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let data: Vec<i32> = (1..=6).collect();
+                    let array = Array::from_vec(&mut frame, data, (2, 3))?.into_jlrs_result()?;
+                    let accessor = array.bits_data::<i32>()?;
+
+                    let mut n = 0;
+                    for (index, elem) in accessor.indexed_iter() {
+                        assert_eq!(index.rank(), 2);
+                        assert_eq!(index.n_elements(0), n % 2);
+                        assert_eq!(index.n_elements(1), n / 2);
+                        assert_eq!(*elem, n as i32 + 1);
+                        assert_eq!(accessor[index], *elem);
+                        n += 1;
+                    }
+
+                    assert_eq!(n, 6);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn axis_iter() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let data: Vec<u8> = (1..=24).collect();
+                    let array = Array::from_vec(&mut frame, data, (2, 3, 4))?.into_jlrs_result()?;
+                    let accessor = array.bits_data::<u8>()?;
+
+                    let slices = accessor.axis_iter(2)?.collect::<Vec<_>>();
+                    assert_eq!(slices.len(), 4);
+                    assert_eq!(slices[1].dimensions().as_slice(), &[2, 3]);
+                    assert_eq!(slices[1].as_slice().unwrap(), &[7, 8, 9, 10, 11, 12]);
+
+                    let first = accessor.axis_iter(0)?.next().unwrap();
+                    assert_eq!(first.dimensions().as_slice(), &[3, 4]);
+                    assert!(first.as_slice().is_none());
+                    assert_eq!(first[(1, 1)], 9);
+                    assert_eq!(
+                        first.iter().step_by(3).copied().collect::<Vec<_>>(),
+                        &[1, 7, 13, 19]
+                    );
+
+                    assert!(accessor.axis_iter(3).is_err());
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn columns_and_rows() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let data: Vec<f64> = (1..=6).map(|x| x as f64).collect();
+                    let array = Array::from_vec(&mut frame, data, (2, 3))?.into_jlrs_result()?;
+                    let accessor = array.bits_data::<f64>()?;
+
+                    let column = accessor.column(1)?;
+                    assert_eq!(column.as_slice().unwrap(), &[3.0, 4.0]);
+
+                    let row = accessor.row(1)?;
+                    assert_eq!(row.to_vec(), vec![2.0, 4.0, 6.0]);
+                    assert_eq!(row.strides(), &[2]);
+
+                    assert!(accessor.column(3).is_err());
+                    assert!(accessor.row(2).is_err());
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn get_range() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let data: Vec<u16> = (1..=12).collect();
+                    let array = Array::from_vec(&mut frame, data, (3, 4))?.into_jlrs_result()?;
+                    let accessor = array.bits_data::<u16>()?;
+
+                    let block = accessor.get_range(&[1..3, 2..4])?;
+                    assert_eq!(block.dimensions().as_slice(), &[2, 2]);
+                    assert_eq!(block.to_vec(), vec![8, 9, 11, 12]);
+                    assert_eq!(block[(0, 1)], 11);
+
+                    let empty = accessor.get_range(&[1..1, 0..4])?;
+                    assert!(empty.is_empty());
+                    assert_eq!(empty.iter().count(), 0);
+
+                    assert!(accessor.get_range(&[0..4, 0..1]).is_err());
+                    assert!(accessor.get_range(&[0..1]).is_err());
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn array_iter_tests() {
+        indexed_iter();
+        axis_iter();
+        columns_and_rows();
+        get_range();
+    }
+}
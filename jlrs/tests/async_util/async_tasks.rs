@@ -889,3 +889,129 @@ impl AsyncTask for BorrowArrayData {
         Ok(v)
     }
 }
+
+pub struct CallLatestTask;
+
+#[async_trait(?Send)]
+impl AsyncTask for CallLatestTask {
+    type Output = f64;
+    type Affinity = DispatchAny;
+
+    async fn run<'base>(&mut self, mut frame: AsyncGcFrame<'base>) -> JlrsResult<Self::Output> {
+        let v = unsafe {
+            let func = Value::eval_string(&mut frame, "async_latest_fn(x::Int) = x + 1")
+                .into_jlrs_result()?;
+
+            Value::eval_string(&mut frame, "async_latest_fn(x::Float64) = x + 2.0")
+                .into_jlrs_result()?;
+
+            let arg = Value::new(&mut frame, 1.0f64);
+            func.call_async_latest(&mut frame, [arg])
+                .await
+                .into_jlrs_result()?
+                .unbox::<f64>()?
+        };
+
+        Ok(v)
+    }
+}
+
+// Defines `interruptible_sleep(seconds, flag)` and the global `flag`, which is set to `true` if
+// the sleep is interrupted with an `InterruptException`.
+unsafe fn interruptible_sleep<'target>(
+    frame: &mut AsyncGcFrame<'target>,
+    flag: &str,
+) -> JlrsResult<(Value<'target, 'static>, Value<'target, 'static>)> {
+    let func = Value::eval_string(
+        &mut *frame,
+        "function interruptible_sleep(seconds, flag)
+            try
+                sleep(seconds)
+            catch e
+                e isa InterruptException && (flag[] = true)
+                rethrow()
+            end
+        end",
+    )
+    .into_jlrs_result()?;
+
+    let cmd = format!("global {} = Threads.Atomic{{Bool}}(false)", flag);
+    let flag = Value::eval_string(&mut *frame, cmd).into_jlrs_result()?;
+    Ok((func, flag))
+}
+
+// Sleeps in Julia for `seconds`, the global `flag` is set to `true` if the sleep is interrupted.
+pub struct InterruptibleSleepTask {
+    pub seconds: f64,
+    pub flag: &'static str,
+}
+
+#[async_trait(?Send)]
+impl AsyncTask for InterruptibleSleepTask {
+    type Output = ();
+    type Affinity = DispatchAny;
+
+    async fn run<'base>(&mut self, mut frame: AsyncGcFrame<'base>) -> JlrsResult<Self::Output> {
+        unsafe {
+            let (func, flag) = interruptible_sleep(&mut frame, self.flag)?;
+            let seconds = Value::new(&mut frame, self.seconds);
+            func.call_async_local(&mut frame, [seconds, flag])
+                .await
+                .into_jlrs_result()?;
+        }
+
+        Ok(())
+    }
+}
+
+// Returns the value of the global `flag` set by `InterruptibleSleepTask`.
+pub struct InterruptedTask {
+    pub flag: &'static str,
+}
+
+#[async_trait(?Send)]
+impl AsyncTask for InterruptedTask {
+    type Output = bool;
+    type Affinity = DispatchAny;
+
+    async fn run<'base>(&mut self, mut frame: AsyncGcFrame<'base>) -> JlrsResult<Self::Output> {
+        unsafe {
+            let cmd = format!("isdefined(Main, :{0}) && {0}[]", self.flag);
+            let interrupted = Value::eval_string(&mut frame, cmd).into_jlrs_result()?;
+            Ok(interrupted.unbox::<bool>()?.as_bool())
+        }
+    }
+}
+
+// Sleeps in Julia for 60 seconds with a timeout of 100 ms, the global `flag` is set to `true` if
+// the sleep is interrupted.
+pub struct TimeoutTask {
+    pub interrupt: bool,
+    pub flag: &'static str,
+}
+
+#[async_trait(?Send)]
+impl AsyncTask for TimeoutTask {
+    type Output = bool;
+    type Affinity = DispatchAny;
+
+    async fn run<'base>(&mut self, mut frame: AsyncGcFrame<'base>) -> JlrsResult<Self::Output> {
+        let res = unsafe {
+            let (func, flag) = interruptible_sleep(&mut frame, self.flag)?;
+            let seconds = Value::new(&mut frame, 60.0f64);
+            func.call_async_local_timeout(
+                &mut frame,
+                [seconds, flag],
+                std::time::Duration::from_millis(100),
+                self.interrupt,
+            )
+            .await
+        };
+
+        match res {
+            Err(e) if matches!(*e, jlrs::error::JlrsError::Timeout(_)) => Ok(true),
+            Err(e) => Err(e),
+            Ok(_) => Ok(false),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+mod util;
+
+#[cfg(test)]
+#[cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::{data::managed::bit_array::BitArray, prelude::*};
+
+    use super::util::JULIA;
+
+    fn create_bit_array() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let bits = BitArray::new(&mut frame, (2, 3))?;
+                    assert_eq!(bits.rank(), 2);
+                    assert_eq!(bits.len(), 6);
+                    assert_eq!(bits.dimensions().as_slice(), &[2, 3]);
+                    assert!(bits.to_vec().iter().all(|b| !b));
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn bit_array_from_slice() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let data: Vec<bool> = (0..100).map(|i| i % 3 == 0).collect();
+                    let bits = BitArray::from_slice(&mut frame, &data, (10, 10))?;
+                    assert_eq!(bits.to_vec(), data);
+                    assert_eq!(bits.get((3, 0)), Some(true));
+                    assert_eq!(bits.get((4, 0)), Some(false));
+                    assert_eq!(bits.get((9, 6)), Some(true));
+                    assert!(bits.get((10, 0)).is_none());
+
+                    assert!(BitArray::from_slice(&mut frame, &data, 99).is_err());
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn bit_array_from_iter() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let bits = BitArray::from_iter(&mut frame, [true, false, true], 3)?;
+                    let count = Module::base(&frame).function(&frame, "count")?.as_managed();
+                    let n = count
+                        .call1(&mut frame, bits.as_value())
+                        .into_jlrs_result()?
+                        .unbox::<isize>()?;
+                    assert_eq!(n, 2);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn bit_array_from_julia() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let bits = Value::eval_string(&mut frame, "[1, -1, 2] .> 0")
+                        .into_jlrs_result()?
+                        .cast::<BitArray>()?;
+                    assert_eq!(bits.dimensions().as_slice(), &[3]);
+                    assert_eq!(bits.to_vec(), vec![true, false, true]);
+
+                    let v = Value::eval_string(&mut frame, "[true, false]").into_jlrs_result()?;
+                    assert!(v.cast::<BitArray>().is_err());
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn set_bit_array() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let bits = BitArray::new(&mut frame, 70)?;
+                    bits.set(65, true)?;
+                    bits.set(1, true)?;
+                    bits.set(1, false)?;
+                    assert!(bits.set(70, true).is_err());
+
+                    assert_eq!(bits.get(65), Some(true));
+                    assert_eq!(bits.get(1), Some(false));
+                    assert_eq!(bits.to_vec().iter().filter(|b| **b).count(), 1);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn bit_array_tests() {
+        create_bit_array();
+        bit_array_from_slice();
+        bit_array_from_iter();
+        bit_array_from_julia();
+        set_bit_array();
+    }
+}
@@ -0,0 +1,106 @@
+mod util;
+#[cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::{info::Info, prelude::*};
+
+    use super::util::JULIA;
+
+    fn call_latest_sees_new_methods() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(&mut frame, "latest_test_fn(x::Int) = x + 1")
+                        .into_jlrs_result()?;
+
+                    Value::eval_string(&mut frame, "latest_test_fn(x::Float64) = x + 2.0")
+                        .into_jlrs_result()?;
+
+                    let arg = Value::new(&mut frame, 1.0f64);
+                    let v = func
+                        .call_latest(&mut frame, [arg])
+                        .into_jlrs_result()?
+                        .unbox::<f64>()?;
+
+                    assert_eq!(v, 3.0);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn call_latest_with_kw() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let a_value = Value::new(&mut frame, 1isize);
+                    let b_value = Value::new(&mut frame, 10isize);
+                    let func = Module::main(&frame)
+                        .submodule(&frame, "JlrsTests")?
+                        .as_managed()
+                        .function(&frame, "funcwithkw")?
+                        .as_managed();
+
+                    let kw = named_tuple!(&mut frame, "b" => b_value);
+                    let v = func
+                        .provide_keywords(kw)?
+                        .call_latest(&mut frame, [a_value])
+                        .into_jlrs_result()?
+                        .unbox::<isize>()?;
+
+                    assert_eq!(v, 11);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn call_latest_exception() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Module::base(&frame).function(&frame, "+")?.as_managed();
+                    let arg = Value::new(&mut frame, 1usize);
+                    let string = JuliaString::new(&mut frame, "a").as_value();
+
+                    assert!(func.call_latest(&mut frame, [arg, string]).is_err());
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn world_counter_increases() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let before = Info::world_counter();
+                    Value::eval_string(&mut frame, "world_counter_test_fn() = 1")
+                        .into_jlrs_result()?;
+
+                    assert!(Info::world_counter() > before);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn call_latest_tests() {
+        call_latest_sees_new_methods();
+        call_latest_with_kw();
+        call_latest_exception();
+        world_counter_increases();
+    }
+}
@@ -0,0 +1,113 @@
+mod util;
+
+#[cfg(test)]
+#[cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::prelude::*;
+
+    use super::util::JULIA;
+
+    fn call_with_owned_args() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Module::base(&frame).function(&frame, "+")?.as_managed();
+                    let res = func
+                        .call_with(&mut frame, (1.0f64, 2i32, 3u8))?
+                        .into_jlrs_result()?;
+                    assert_eq!(res.unbox::<f64>()?, 6.0);
+
+                    let func = Module::base(&frame)
+                        .function(&frame, "length")?
+                        .as_managed();
+                    let res = func
+                        .call_with(&mut frame, (vec![String::from("a"), String::from("b")],))?
+                        .into_jlrs_result()?;
+                    assert_eq!(res.unbox::<isize>()?, 2);
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn call_with_mixed_args() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let arr = TypedArray::<f64>::from_vec(&mut frame, vec![1.0, 2.0], 2)?
+                        .into_jlrs_result()?;
+                    let func = Value::eval_string(&mut frame, "f(a, b, c) = a * b + sum(c)")
+                        .into_jlrs_result()?;
+
+                    let x = Value::new(&mut frame, 3.0f64);
+                    let res = func.call_typed::<f64, _>(&mut frame, (x, 2i32, arr))?;
+                    assert_eq!(res, 9.0);
+
+                    let res =
+                        func.call_typed::<f64, _>(&mut frame, (1.0f64, 1.0f64, Some(1.0f64)))?;
+                    assert_eq!(res, 2.0);
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn call_with_no_args() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(&mut frame, "() -> 42").into_jlrs_result()?;
+                    let res = func.call_typed::<i64, _>(&mut frame, ())?;
+                    assert_eq!(res, 42);
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn call_typed_errors() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(&mut frame, "x -> x > 0 ? x : error(\"neg\")")
+                        .into_jlrs_result()?;
+
+                    assert!(func.call_typed::<i64, _>(&mut frame, (-1i64,)).is_err());
+                    assert!(func.call_typed::<f32, _>(&mut frame, (1i64,)).is_err());
+
+                    let res = func.call_with(&mut frame, (-1i64,))?;
+                    assert!(res.is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn call_with_tests() {
+        call_with_owned_args();
+        call_with_mixed_args();
+        call_with_no_args();
+        call_typed_errors();
+    }
+}
@@ -0,0 +1,159 @@
+mod util;
+
+#[cfg(test)]
+#[cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::{
+        catch::{
+            catch_exceptions, catch_exceptions_of, catch_exceptions_or_rethrow, rethrow, throw,
+        },
+        data::types::exception_types::{BoundsError, DivideError, DomainError},
+        prelude::*,
+    };
+
+    use super::util::JULIA;
+
+    fn catch_bounds_error() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let data = vec![1.0f64, 2.0];
+                    let arr = Array::from_vec(&mut frame, data, 2)?.into_jlrs_result()?;
+                    let getindex = Module::base(&frame)
+                        .function(&frame, "getindex")?
+                        .as_managed();
+                    let idx = Value::new(&mut frame, 3isize);
+
+                    let res = catch_exceptions_of::<BoundsError, _, _, _, _>(
+                        || {
+                            getindex.call_unchecked(&frame, [arr.as_value(), idx]);
+                        },
+                        |e| e.is::<BoundsError>(),
+                    );
+
+                    assert_eq!(res, Err(true));
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn other_exceptions_are_rethrown() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let div = Module::base(&frame).function(&frame, "div")?.as_managed();
+                    let one = Value::new(&mut frame, 1isize);
+                    let zero = Value::new(&mut frame, 0isize);
+
+                    let res = catch_exceptions(
+                        || {
+                            let _ = catch_exceptions_of::<DomainError, _, _, _, _>(
+                                || {
+                                    div.call_unchecked(&frame, [one, zero]);
+                                },
+                                |_| unreachable!(),
+                            );
+                        },
+                        |e| e.is::<DivideError>(),
+                    );
+
+                    assert_eq!(res, Err(true));
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn throw_exception() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let div = Module::base(&frame).function(&frame, "div")?.as_managed();
+                    let one = Value::new(&mut frame, 1isize);
+                    let zero = Value::new(&mut frame, 0isize);
+
+                    let res = catch_exceptions(
+                        || {
+                            let _ = catch_exceptions(
+                                || {
+                                    div.call_unchecked(&frame, [one, zero]);
+                                },
+                                |e| throw(e),
+                            );
+                        },
+                        |e| e.is::<DivideError>(),
+                    );
+
+                    assert_eq!(res, Err(true));
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn handler_can_rethrow() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let div = Module::base(&frame).function(&frame, "div")?.as_managed();
+                    let one = Value::new(&mut frame, 1isize);
+                    let zero = Value::new(&mut frame, 0isize);
+
+                    let res = catch_exceptions_or_rethrow(
+                        || {
+                            div.call_unchecked(&frame, [one, zero]);
+                        },
+                        |e| {
+                            if e.is::<DivideError>() {
+                                Ok(true)
+                            } else {
+                                rethrow()
+                            }
+                        },
+                    );
+                    assert_eq!(res, Err(true));
+
+                    let res = catch_exceptions(
+                        || {
+                            let _ = catch_exceptions_or_rethrow::<_, _, _, ()>(
+                                || {
+                                    div.call_unchecked(&frame, [one, zero]);
+                                },
+                                |_| rethrow(),
+                            );
+                        },
+                        |e| e.is::<DivideError>(),
+                    );
+                    assert_eq!(res, Err(true));
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn catch_exceptions_tests() {
+        catch_bounds_error();
+        other_exceptions_are_rethrown();
+        throw_exception();
+        handler_can_rethrow();
+    }
+}
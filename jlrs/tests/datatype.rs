@@ -624,7 +624,7 @@ mod tests {
         })
     }
 
-    #[cfg(not(feature = "julia-1-10"))]
+    #[cfg(not(any(feature = "julia-1-10", feature = "julia-1-11")))]
     fn datatype_cached_by_hash() {
         JULIA.with(|j| {
             let mut frame = StackFrame::new();
@@ -753,7 +753,7 @@ mod tests {
         datatype_is_const_field();
         cannot_instantiate_with_incorrect_params();
         compare_with_value();
-        #[cfg(not(feature = "julia-1-10"))]
+        #[cfg(not(any(feature = "julia-1-10", feature = "julia-1-11")))]
         datatype_cached_by_hash();
         extend_lifetime();
         check_names();
@@ -0,0 +1,163 @@
+mod util;
+
+#[cfg(test)]
+#[cfg(feature = "sync-rt")]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use jlrs::{data::managed::dict::Dict, prelude::*};
+
+    use super::util::JULIA;
+
+    fn create_empty_dict() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let dict = Dict::<i64, f64>::new(&mut frame)?;
+                    assert_eq!(dict.len()?, 0);
+                    assert!(dict.is_empty()?);
+                    assert!(dict.get(&mut frame, 1)?.is_none());
+                    assert!(dict.as_value().datatype_name()? == "Dict");
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn insert_and_get() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let dict = Dict::<u32, bool>::new(&mut frame)?;
+                    unsafe {
+                        dict.insert(1, true)?;
+                        dict.insert(2, false)?;
+                        dict.insert(1, false)?;
+                    }
+
+                    assert_eq!(dict.len()?, 2);
+                    let value = dict.get(&mut frame, 1)?.unwrap();
+                    assert!(!value.unbox::<bool>()?.as_bool());
+                    assert!(dict.get(&mut frame, 3)?.is_none());
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn roundtrip_hash_map() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let mut map = HashMap::new();
+                    map.insert(1i64, 1.5f64);
+                    map.insert(2i64, 2.5f64);
+
+                    let dict = Dict::<i64, f64>::from_map(&mut frame, &map)?;
+                    assert_eq!(dict.len()?, 2);
+                    assert_eq!(dict.to_hash_map()?, map);
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn roundtrip_btree_map() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let mut map = BTreeMap::new();
+                    map.insert(3u8, 30i16);
+                    map.insert(1u8, 10i16);
+
+                    let dict = Dict::<u8, i16>::from_map(&mut frame, &map)?;
+                    assert_eq!(dict.to_btree_map()?, map);
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn iterate_entries() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let mut map = HashMap::new();
+                    map.insert(1i32, 2i32);
+                    map.insert(3i32, 4i32);
+
+                    let dict = Dict::<i32, i32>::from_map(&mut frame, &map)?;
+                    let entries = dict.iter(&mut frame)?.collect::<Vec<_>>();
+                    assert_eq!(entries.len(), 2);
+
+                    let mut sum = 0;
+                    for (key, value) in entries {
+                        assert_eq!(map[&key.unbox::<i32>()?], value.unbox::<i32>()?);
+                        sum += key.unbox::<i32>()?;
+                    }
+                    assert_eq!(sum, 4);
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn cast_existing_dict() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let value = Value::eval_string(&mut frame, "Dict{Int64,Float64}(1 => 2.0)")
+                        .into_jlrs_result()?;
+
+                    assert!(value.is::<Dict<i64, f64>>());
+                    assert!(!value.is::<Dict<i64, f32>>());
+                    assert!(Dict::<i32, f64>::from_value(value).is_err());
+
+                    let dict = Dict::<i64, f64>::from_value(value)?;
+                    assert_eq!(dict.to_btree_map()?[&1], 2.0);
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn dict_tests() {
+        create_empty_dict();
+        insert_and_get();
+        roundtrip_hash_map();
+        roundtrip_btree_map();
+        iterate_entries();
+        cast_existing_dict();
+    }
+}
@@ -30,7 +30,7 @@ mod tests {
         });
     }
 
-    #[cfg(not(feature = "julia-1-10"))]
+    #[cfg(not(any(feature = "julia-1-10", feature = "julia-1-11")))]
     fn syntax_error() {
         eval_string("asdf fdsa asdf fdsa", |result| {
             assert_eq!(
@@ -40,7 +40,7 @@ mod tests {
         });
     }
 
-    #[cfg(feature = "julia-1-10")]
+    #[cfg(any(feature = "julia-1-10", feature = "julia-1-11"))]
     fn syntax_error() {
         eval_string("asdf fdsa asdf fdsa", |result| {
             assert_eq!(
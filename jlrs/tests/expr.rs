@@ -0,0 +1,134 @@
+mod util;
+#[cfg(all(feature = "sync-rt", feature = "internal-types"))]
+mod tests {
+    use jlrs::{data::managed::internal::expr::Expr, prelude::*};
+
+    use super::util::JULIA;
+
+    fn build_and_eval_call() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let plus = Symbol::new(&frame, "+").as_value();
+                    let a = Value::new(&mut frame, 1isize);
+                    let b = Value::new(&mut frame, 2isize);
+                    let expr = Expr::new(&mut frame, "call", &[plus, a, b]);
+
+                    assert_eq!(expr.head().unwrap().as_str()?, "call");
+
+                    let v = Module::main(&frame)
+                        .eval_expr(&mut frame, expr.as_value())
+                        .into_jlrs_result()?
+                        .unbox::<isize>()?;
+
+                    assert_eq!(v, 3);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn interpolate_literal_symbol() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let name = Symbol::new(&frame, "not_a_variable").as_value();
+                    let quoted = Expr::quote_node(&mut frame, name);
+
+                    let v = Module::main(&frame)
+                        .eval_expr(&mut frame, quoted)
+                        .into_jlrs_result()?;
+
+                    assert!(v.is::<Symbol>());
+                    assert_eq!(v.cast::<Symbol>()?.as_str()?, "not_a_variable");
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn interpolate_string_is_not_code() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let length = Symbol::new(&frame, "length").as_value();
+                    let s = JuliaString::new(&mut frame, "\"); error(\"injected").as_value();
+                    let s = Expr::quote_node(&mut frame, s);
+                    let expr = Expr::new(&mut frame, "call", &[length, s]);
+
+                    let v = Module::main(&frame)
+                        .eval_expr(&mut frame, expr.as_value())
+                        .into_jlrs_result()?
+                        .unbox::<isize>()?;
+
+                    assert_eq!(v, 19);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn global_ref_and_line_number() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let base = Module::base(&frame);
+                    let func = Expr::global_ref(&mut frame, base, "sqrt");
+                    let arg = Value::new(&mut frame, 4.0f64);
+                    let call = Expr::new(&mut frame, "call", &[func, arg]);
+                    let line = Expr::line_number_node(&mut frame, 10, "generated.jl");
+                    let block = Expr::new(&mut frame, "block", &[line, call.as_value()]);
+
+                    let module = Module::main(&frame)
+                        .submodule(&frame, "JlrsTests")?
+                        .as_managed();
+                    let v = module
+                        .eval_expr(&mut frame, block.as_value())
+                        .into_jlrs_result()?
+                        .unbox::<f64>()?;
+
+                    assert_eq!(v, 2.0);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn eval_expr_exception() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let name = Symbol::new(&frame, "undefined_expr_variable").as_value();
+                    let res = Module::main(&frame).eval_expr(&mut frame, name);
+
+                    assert!(res.is_err());
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn expr_tests() {
+        build_and_eval_call();
+        interpolate_literal_symbol();
+        interpolate_string_is_not_code();
+        global_ref_and_line_number();
+        eval_expr_exception();
+    }
+}
@@ -2,7 +2,7 @@ mod util;
 
 #[cfg(feature = "sync-rt")]
 mod tests {
-    use jlrs::prelude::*;
+    use jlrs::{data::managed::function::Function, prelude::*};
 
     use crate::util::JULIA;
 
@@ -49,9 +49,155 @@ mod tests {
         })
     }
 
+    fn precompile_method() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(&mut frame, "precompile_test_fn(x::Int) = x + 1")
+                        .into_jlrs_result()?
+                        .cast::<Function>()?;
+
+                    let int_ty = DataType::int64_type(&frame).as_value();
+                    assert!(func.precompile(&[int_ty])?);
+
+                    let f64_ty = DataType::float64_type(&frame).as_value();
+                    assert!(!func.precompile(&[f64_ty])?);
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[cfg(feature = "internal-types")]
+    fn list_methods() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(
+                        &mut frame,
+                        "methods_test_fn(x::Int) = x; methods_test_fn(x::Float64, y) = x",
+                    )
+                    .into_jlrs_result()?
+                    .cast::<Function>()?;
+
+                    let methods = func.methods()?;
+                    assert_eq!(methods.len(), 2);
+
+                    for method in methods {
+                        assert_eq!(method.name().unwrap().as_str()?, "methods_test_fn");
+                        assert!(method.line() > 0);
+                        assert!(method.file().is_some());
+                        let module = method.module(&frame).unwrap().as_managed();
+                        assert_eq!(module.name().as_str()?, "Main");
+                        assert!(method.signature(&frame).is_some());
+                    }
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[cfg(feature = "internal-types")]
+    fn which_method() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(
+                        &mut frame,
+                        "which_test_fn(x::Int) = 1; which_test_fn(x::AbstractFloat) = 2",
+                    )
+                    .into_jlrs_result()?
+                    .cast::<Function>()?;
+
+                    let f64_ty = DataType::float64_type(&frame).as_value();
+                    let method = func.which(&[f64_ty])?;
+                    let sig = method.signature(&mut frame).unwrap();
+                    let expected = Value::eval_string(
+                        &mut frame,
+                        "Tuple{typeof(which_test_fn), AbstractFloat}",
+                    )
+                    .into_jlrs_result()?;
+                    assert!(sig.egal(expected));
+
+                    let bool_ty = DataType::bool_type(&frame).as_value();
+                    assert!(func.which(&[bool_ty]).is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    #[cfg(all(
+        feature = "internal-types",
+        not(any(feature = "julia-1-6", feature = "julia-1-7"))
+    ))]
+    fn resolve_method() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(&mut frame, "resolve_test_fn(x::Int) = x + 1")
+                        .into_jlrs_result()?
+                        .cast::<Function>()?;
+
+                    let int_ty = DataType::int64_type(&frame).as_value();
+                    let resolved = func.resolve(&[int_ty])?;
+
+                    let arg = Value::new(&mut frame, 1i64);
+                    let res = resolved
+                        .call(&mut frame, [arg])
+                        .into_jlrs_result()?
+                        .unbox::<i64>()?;
+                    assert_eq!(res, 2);
+
+                    // Arguments that don't match the signature are dispatched dynamically.
+                    let arg = Value::new(&mut frame, 1.0f64);
+                    assert!(resolved.call(&mut frame, [arg]).is_err());
+
+                    // Redefining the method changes the world age, the method is resolved again.
+                    let world = resolved.world();
+                    Value::eval_string(&mut frame, "resolve_test_fn(x::Int) = x + 2")
+                        .into_jlrs_result()?;
+                    let arg = Value::new(&mut frame, 1i64);
+                    let res = resolved
+                        .call(&mut frame, [arg])
+                        .into_jlrs_result()?
+                        .unbox::<i64>()?;
+                    assert_eq!(res, 3);
+                    assert!(resolved.world() > world);
+
+                    let any_ty = DataType::any_type(&frame).as_value();
+                    assert!(func.resolve(&[any_ty]).is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
     #[test]
     fn function_tests() {
         extend_lifetime();
         has_datatype();
+        precompile_method();
+        #[cfg(feature = "internal-types")]
+        list_methods();
+        #[cfg(feature = "internal-types")]
+        which_method();
+        #[cfg(all(
+            feature = "internal-types",
+            not(any(feature = "julia-1-6", feature = "julia-1-7"))
+        ))]
+        resolve_method();
     }
 }
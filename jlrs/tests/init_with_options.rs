@@ -0,0 +1,55 @@
+#![cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::{
+        prelude::*,
+        runtime::builder::{CheckBounds, RuntimeBuilder},
+    };
+
+    #[test]
+    fn init_with_options() {
+        let depot = std::env::temp_dir().join("jlrs_init_with_options_depot");
+
+        let mut julia = unsafe {
+            RuntimeBuilder::new()
+                .optimization_level(1)
+                .check_bounds(CheckBounds::On)
+                .project("@.")
+                .depot_path([&depot])
+                .compiled_modules(true)
+                .startup_file(false)
+                .handle_signals(true)
+                .start()
+                .unwrap()
+        };
+
+        let mut frame = StackFrame::new();
+        julia
+            .instance(&mut frame)
+            .scope(|mut frame| unsafe {
+                let opt_level = Value::eval_string(&mut frame, "Base.JLOptions().opt_level")
+                    .into_jlrs_result()?
+                    .unbox::<i8>()?;
+                assert_eq!(opt_level, 1);
+
+                let check_bounds = Value::eval_string(&mut frame, "Base.JLOptions().check_bounds")
+                    .into_jlrs_result()?
+                    .unbox::<i8>()?;
+                assert_eq!(check_bounds, 1);
+
+                let startup_file = Value::eval_string(&mut frame, "Base.JLOptions().startupfile")
+                    .into_jlrs_result()?
+                    .unbox::<i8>()?;
+                assert_eq!(startup_file, 2);
+
+                let depot_path = Value::eval_string(&mut frame, "first(DEPOT_PATH)")
+                    .into_jlrs_result()?
+                    .cast::<JuliaString>()?
+                    .as_str()?
+                    .to_string();
+                assert_eq!(depot_path, depot.to_string_lossy());
+
+                Ok(())
+            })
+            .unwrap();
+    }
+}
@@ -0,0 +1,26 @@
+#![cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::{error::JlrsError, runtime::builder::RuntimeBuilder};
+
+    #[test]
+    fn init_with_invalid_options() {
+        unsafe {
+            let err = RuntimeBuilder::new()
+                .optimization_level(4)
+                .start()
+                .err()
+                .unwrap();
+            assert!(matches!(*err, JlrsError::RuntimeError(_)));
+
+            let err = RuntimeBuilder::new()
+                .project("/jlrs/nonexistent/project")
+                .start()
+                .err()
+                .unwrap();
+            assert!(matches!(*err, JlrsError::IOError(_)));
+
+            // The options are checked before Julia is initialized.
+            assert!(RuntimeBuilder::new().start().is_ok());
+        }
+    }
+}
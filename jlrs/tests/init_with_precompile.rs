@@ -0,0 +1,44 @@
+#![cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::{prelude::*, runtime::builder::RuntimeBuilder};
+
+    #[test]
+    fn init_with_precompile() {
+        let path = std::env::temp_dir().join("jlrs_init_with_precompile.jl");
+        std::fs::write(
+            &path,
+            "precompile(Tuple{typeof(Base.sum), Vector{Float64}})\n\nprecompile(Tuple{typeof(Base.undefined_function)})\n",
+        )
+        .unwrap();
+
+        let mut julia = unsafe {
+            RuntimeBuilder::new()
+                .precompile_file(&path)
+                .precompile_statements(["precompile(Tuple{typeof(Base.prod), Vector{Int}})"])
+                .start()
+                .unwrap()
+        };
+
+        // The statement that refers to an undefined function fails and is skipped.
+        let report = julia.startup_report();
+        assert!(report.startup_file_error().is_none());
+        assert_eq!(
+            report.failed_precompile_statements(),
+            ["precompile(Tuple{typeof(Base.undefined_function)})"]
+        );
+
+        let mut frame = StackFrame::new();
+        julia
+            .instance(&mut frame)
+            .scope(|mut frame| unsafe {
+                let v = Value::eval_string(&mut frame, "sum([1.0, 2.0])")
+                    .into_jlrs_result()?
+                    .unbox::<f64>()?;
+                assert_eq!(v, 3.0);
+                Ok(())
+            })
+            .unwrap();
+
+        std::fs::remove_file(path).ok();
+    }
+}
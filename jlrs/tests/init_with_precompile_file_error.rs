@@ -0,0 +1,15 @@
+#![cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::runtime::builder::RuntimeBuilder;
+
+    #[test]
+    fn init_with_precompile_file_error() {
+        let path = std::env::temp_dir().join("jlrs_precompile_file_does_not_exist.jl");
+
+        unsafe {
+            assert!(RuntimeBuilder::new().precompile_file(path).start().is_err());
+            // The failed attempt must not prevent Julia from being initialized.
+            assert!(RuntimeBuilder::new().start().is_ok());
+        }
+    }
+}
@@ -1,7 +1,11 @@
 mod util;
 #[cfg(feature = "sync-rt")]
 mod tests {
-    use jlrs::prelude::*;
+    use jlrs::{
+        catch::catch_exceptions_or_rethrow,
+        error::{JlrsError, JuliaException},
+        prelude::*,
+    };
 
     use super::util::JULIA;
 
@@ -35,9 +39,128 @@ mod tests {
         });
     }
 
+    fn exc_is_structured() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let div = Module::base(&frame).function(&frame, "div")?.as_managed();
+                    let a = Value::new(&mut frame, 1isize);
+                    let b = Value::new(&mut frame, 0isize);
+                    let err = div.call2(&mut frame, a, b).into_jlrs_result().unwrap_err();
+
+                    match *err {
+                        JlrsError::JuliaException(ref exc) => {
+                            assert_eq!(exc.type_name(), "DivideError");
+                            assert!(exc.get_message().contains("DivideError"));
+                            assert!(exc.backtrace(&frame)?.is_empty());
+                        }
+                        _ => panic!("expected a JuliaException"),
+                    }
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn captured_exc_has_backtrace() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let div = Module::base(&frame).function(&frame, "div")?.as_managed();
+                    let a = Value::new(&mut frame, 1isize);
+                    let b = Value::new(&mut frame, 0isize);
+
+                    let exc = catch_exceptions_or_rethrow(
+                        || {
+                            div.call_unchecked(&frame, [a, b]);
+                        },
+                        |e| Ok(JuliaException::capture(e)),
+                    )
+                    .unwrap_err();
+
+                    assert_eq!(exc.type_name(), "DivideError");
+                    assert!(exc.backtrace(&frame)?.iter().any(|fr| fr.func() == "div"));
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn traced_call_has_backtrace() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let div = Module::base(&frame).function(&frame, "div")?.as_managed();
+                    let a = Value::new(&mut frame, 1isize);
+                    let b = Value::new(&mut frame, 0isize);
+
+                    let exc = div.call_traced(&mut frame, [a, b]).unwrap_err();
+                    assert_eq!(exc.exception().datatype_name().unwrap(), "DivideError");
+                    assert_eq!(exc.info().type_name(), "DivideError");
+                    assert!(exc
+                        .info()
+                        .backtrace(&frame)?
+                        .iter()
+                        .any(|fr| fr.func() == "div"));
+
+                    let err = div.call_typed::<isize, _>(&mut frame, (1isize, 0isize));
+                    match *err.unwrap_err() {
+                        JlrsError::JuliaException(ref exc) => {
+                            assert_eq!(exc.type_name(), "DivideError");
+                            assert!(!exc.backtrace(&frame)?.is_empty());
+                        }
+                        _ => panic!("expected a JuliaException"),
+                    }
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn task_exc_is_unwrapped() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let err = Value::eval_string(&mut frame, "fetch(@async error(\"from task\"))")
+                        .into_jlrs_result()
+                        .unwrap_err();
+
+                    match *err {
+                        JlrsError::JuliaException(ref exc) => {
+                            assert_eq!(exc.type_name(), "ErrorException");
+                            assert!(exc.get_message().contains("from task"));
+                        }
+                        _ => panic!("expected a JuliaException"),
+                    }
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
     #[test]
     fn test_into_jlrs_result() {
         ok_to_jlrs_result();
         exc_to_jlrs_result();
+        exc_is_structured();
+        captured_exc_has_backtrace();
+        traced_call_has_backtrace();
+        task_exc_is_unwrapped();
     }
 }
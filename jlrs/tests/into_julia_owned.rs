@@ -0,0 +1,138 @@
+mod util;
+
+#[cfg(test)]
+#[cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::{
+        convert::into_julia_owned::IntoJuliaOwned, data::types::construct_type::ConstructType,
+        prelude::*,
+    };
+
+    use super::util::JULIA;
+
+    fn convert_primitive() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let v = 3i32.into_julia_owned(&mut frame)?;
+                    assert_eq!(v.unbox::<i32>()?, 3);
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn convert_string() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let v = String::from("foo").into_julia_owned(&mut frame)?;
+                    assert_eq!(v.cast::<JuliaString>()?.as_str()?, "foo");
+
+                    let v = "bar".into_julia_owned(&mut frame)?;
+                    assert_eq!(v.cast::<JuliaString>()?.as_str()?, "bar");
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn convert_vec() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let v = vec![1.0f64, 2.0, 3.0].into_julia_owned(&mut frame)?;
+                    let arr = v.cast::<TypedArray<f64>>()?;
+                    assert_eq!(arr.bits_data()?.as_slice(), &[1.0, 2.0, 3.0]);
+
+                    let boxed: Box<[u8]> = vec![4u8, 5].into_boxed_slice();
+                    let v = boxed.into_julia_owned(&mut frame)?;
+                    let arr = v.cast::<TypedArray<u8>>()?;
+                    assert_eq!(arr.bits_data()?.as_slice(), &[4, 5]);
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn convert_option() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let v = Some(1u16).into_julia_owned(&mut frame)?;
+                    assert_eq!(v.unbox::<u16>()?, 1);
+
+                    let v = None::<u16>.into_julia_owned(&mut frame)?;
+                    assert!(v.is::<Nothing>());
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn convert_nested() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let data = vec![Some(String::from("a")), None];
+                    let v = data.into_julia_owned(&mut frame)?;
+
+                    let ty = <Vec<Option<String>> as IntoJuliaOwned>::JuliaType::construct_type(
+                        &mut frame,
+                    );
+                    assert!(v.isa(ty));
+
+                    let display = Value::eval_string(&mut frame, "repr")
+                        .into_jlrs_result()?
+                        .call1(&mut frame, v)
+                        .into_jlrs_result()?;
+                    assert_eq!(
+                        display.cast::<JuliaString>()?.as_str()?,
+                        "Union{Nothing, String}[\"a\", nothing]"
+                    );
+
+                    let data = vec![vec![1i64], vec![2, 3]];
+                    let v = data.into_julia_owned(&mut frame)?;
+                    let ty =
+                        <Vec<Vec<i64>> as IntoJuliaOwned>::JuliaType::construct_type(&mut frame);
+                    assert!(v.isa(ty));
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn into_julia_owned_tests() {
+        convert_primitive();
+        convert_string();
+        convert_vec();
+        convert_option();
+        convert_nested();
+    }
+}
@@ -295,6 +295,56 @@ mod tests {
         });
     }
 
+    fn call_with_kwargs_macro() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let a_value = Value::new(&mut frame, 1isize);
+                    let func = Module::main(&frame)
+                        .submodule(&frame, "JlrsTests")?
+                        .as_managed()
+                        .function(&frame, "funcwithkw")?
+                        .as_managed();
+
+                    let v = kwargs!(&mut frame, func, b = 10isize)?
+                        .call1(&mut frame, a_value)
+                        .unwrap()
+                        .unbox::<isize>()?;
+
+                    assert_eq!(v, 11);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn call_with_kwargs_macro_mixed() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Value::eval_string(&mut frame, "(; a, b, c) -> string(a, b, c)")
+                        .into_jlrs_result()?;
+                    let c_value = Symbol::new(&frame, "z");
+
+                    let s = kwargs!(&mut frame, func, a = 1.5f64, b = "x", c = c_value,)?
+                        .call0(&mut frame)
+                        .into_jlrs_result()?
+                        .unbox::<String>()?
+                        .unwrap();
+
+                    assert_eq!(s, "1.5xz");
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
     #[test]
     fn kw_func_test() {
         call_no_kw();
@@ -307,5 +357,7 @@ mod tests {
         call_with_kw_and_4_vararg();
         call_with_abstract_kw_f32();
         call_with_abstract_kw_f64();
+        call_with_kwargs_macro();
+        call_with_kwargs_macro_mixed();
     }
 }
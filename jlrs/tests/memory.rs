@@ -0,0 +1,133 @@
+mod util;
+
+#[cfg(test)]
+#[cfg(all(feature = "sync-rt", feature = "julia-1-11"))]
+mod tests {
+    use jlrs::{
+        data::managed::memory::{Memory, TypedMemory},
+        prelude::*,
+    };
+
+    use super::util::JULIA;
+
+    fn create_typed_memory() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let mut memory = TypedMemory::<f64>::new(&mut frame, 4).into_jlrs_result()?;
+                    assert_eq!(memory.len(), 4);
+                    assert!(!memory.as_memory().is_union());
+
+                    for (i, elem) in memory.as_mut_slice().iter_mut().enumerate() {
+                        *elem = i as f64;
+                    }
+
+                    assert_eq!(memory.to_vec(), vec![0.0, 1.0, 2.0, 3.0]);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn get_and_set() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let ty = DataType::any_type(&frame).as_value();
+                    let memory = Memory::new_for(&mut frame, 2, ty).into_jlrs_result()?;
+                    assert!(!memory.is_inline());
+                    assert!(memory.get(&mut frame, 0)?.is_none());
+
+                    let value = Value::new(&mut frame, 3usize);
+                    memory.set(1, value)?;
+                    let elem = memory.get(&mut frame, 1)?.unwrap();
+                    assert_eq!(elem.unbox::<usize>()?, 3);
+
+                    assert!(memory.get(&mut frame, 2).is_err());
+                    assert!(memory.memory_ref(1)?.index() == 1);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn array_memory() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let data = vec![1u8, 2, 3];
+                    let array = Array::from_vec(&mut frame, data, 3)?.into_jlrs_result()?;
+                    let memory = array.memory();
+                    assert_eq!(memory.len(), 3);
+                    assert!(memory.contains::<u8>());
+                    assert!(memory.as_typed::<u16>().is_err());
+
+                    let typed = memory.as_typed::<u8>()?;
+                    assert_eq!(typed.to_vec(), vec![1, 2, 3]);
+                    assert_eq!(array.memory_ref().index(), 0);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn zero_sized_memory_ref() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let ty = DataType::nothing_type(&frame).as_value();
+                    let memory = Memory::new_for(&mut frame, 3, ty).into_jlrs_result()?;
+                    assert_eq!(memory.element_size(), 0);
+                    assert_eq!(memory.memory_ref(2)?.index(), 2);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn atomic_memory_is_not_memory() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let memory = Value::eval_string(&mut frame, "AtomicMemory{Int}(undef, 2)")
+                        .into_jlrs_result()?;
+                    assert!(!memory.is::<Memory>());
+
+                    let memory = Value::eval_string(&mut frame, "Memory{Int}(undef, 2)")
+                        .into_jlrs_result()?;
+                    assert!(memory.is::<Memory>());
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn memory_tests() {
+        create_typed_memory();
+        get_and_set();
+        array_memory();
+        zero_sized_memory_ref();
+        atomic_memory_is_not_memory();
+    }
+}
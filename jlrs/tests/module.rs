@@ -510,6 +510,80 @@ mod tests {
         })
     }
 
+    fn eval_string_in_module() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let module = Module::main(&frame)
+                        .submodule(&frame, "JlrsTests")?
+                        .as_managed();
+
+                    let v = module
+                        .eval_string(&mut frame, "module_eval_x = 3\nmodule_eval_x + 1")?
+                        .into_jlrs_result()?
+                        .unbox::<isize>()?;
+                    assert_eq!(v, 4);
+
+                    assert!(module.global(&frame, "module_eval_x").is_ok());
+                    assert!(Module::main(&frame)
+                        .global(&frame, "module_eval_x")
+                        .is_err());
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn include_string_error_has_position() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let module = Module::main(&frame);
+                    let err = module
+                        .include_string(&mut frame, "x = 1\nerror(\"boom\")", "script.jl")?
+                        .into_jlrs_result()
+                        .unwrap_err();
+
+                    let msg = err.to_string();
+                    assert!(msg.contains("boom"));
+                    assert!(msg.contains("script.jl:2"));
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
+    fn eval_expr_in_module() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let module = Module::main(&frame)
+                        .submodule(&frame, "JlrsTests")?
+                        .as_managed();
+                    module
+                        .eval_string(&mut frame, "module_eval_expr_x = 5")?
+                        .into_jlrs_result()?;
+
+                    let name = Symbol::new(&frame, "module_eval_expr_x").as_value();
+                    let v = module
+                        .eval_expr(&mut frame, name)
+                        .into_jlrs_result()?
+                        .unbox::<isize>()?;
+                    assert_eq!(v, 5);
+
+                    assert!(Module::main(&frame).eval_expr(&mut frame, name).is_err());
+                    Ok(())
+                })
+                .unwrap();
+        })
+    }
+
     #[test]
     fn module_tests() {
         core_module();
@@ -540,5 +614,8 @@ mod tests {
         set_global_unchecked();
         set_const_unchecked();
         function_must_be_function();
+        eval_string_in_module();
+        include_string_error_has_position();
+        eval_expr_in_module();
     }
 }
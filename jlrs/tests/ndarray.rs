@@ -4,10 +4,19 @@ mod util;
 #[cfg(all(feature = "sync-rt", feature = "jlrs-ndarray"))]
 mod tests {
     use jlrs::{
-        convert::ndarray::{NdArrayView, NdArrayViewMut},
-        data::managed::array::{Array, TypedArray},
+        call::Call,
+        convert::{
+            into_jlrs_result::IntoJlrsResult,
+            ndarray::{NdArrayView, NdArrayViewMut},
+        },
+        data::managed::{
+            array::{strided::SubArray, Array, TypedArray},
+            value::Value,
+            Managed,
+        },
         memory::stack_frame::StackFrame,
     };
+    use ndarray::{s, IxDyn, ShapeBuilder};
 
     use super::util::JULIA;
 
@@ -144,6 +153,112 @@ mod tests {
         });
     }
 
+    fn move_fortran_ndarray() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let data = vec![1.0f64, 2.0, 3.0, 4.0, 5.0, 6.0];
+                    let ndarray = ndarray::Array::from_shape_vec(IxDyn(&[3, 2]).f(), data).unwrap();
+                    let x = ndarray[[2, 1]];
+
+                    let array =
+                        TypedArray::from_ndarray(&mut frame, ndarray)?.into_jlrs_result()?;
+                    assert_eq!(array.dimensions().as_slice(), &[3, 2]);
+
+                    let data = array.bits_data()?;
+                    assert_eq!(data[(2, 1)], x);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn move_standard_ndarray() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let data = vec![1u32, 2, 3, 4, 5, 6];
+                    let ndarray = ndarray::Array::from_shape_vec((3, 2), data).unwrap();
+                    let x = ndarray[[2, 1]];
+
+                    let array = Array::from_ndarray(&mut frame, ndarray)?.into_jlrs_result()?;
+                    assert_eq!(array.dimensions().as_slice(), &[2, 3]);
+
+                    let data = array.bits_data::<u32>()?;
+                    assert_eq!(data[(1, 2)], x);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn move_non_contiguous_ndarray() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let data = vec![1u32, 2, 3, 4, 5, 6];
+                    let mut ndarray = ndarray::Array::from_shape_vec((3, 2), data).unwrap();
+                    ndarray.slice_collapse(s![.., 1..]);
+
+                    assert!(Array::from_ndarray(&mut frame, ndarray).is_err());
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn reversed_sub_array_view() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let parent = Value::eval_string(&mut frame, "reshape(collect(1:6), 3, 2)")
+                        .into_jlrs_result()?
+                        .cast::<Array>()?;
+                    let mut view = Value::eval_string(&mut frame, "A -> view(A, 3:-1:1, :)")
+                        .into_jlrs_result()?
+                        .call1(&mut frame, parent.as_value())
+                        .into_jlrs_result()?
+                        .cast::<SubArray>()?;
+
+                    {
+                        let data = view.bits_data::<i64>()?;
+                        let array = data.array_view();
+                        assert_eq!(array.shape(), &[3, 2]);
+                        assert_eq!(array[[0, 0]], 3);
+                        assert_eq!(array[[2, 0]], 1);
+                        assert_eq!(array[[0, 1]], 6);
+                    }
+
+                    {
+                        let mut data = view.bits_data_mut::<i64>()?;
+                        let mut array = data.array_view_mut();
+                        array[[0, 1]] = 0;
+                    }
+
+                    let parent_data = parent.bits_data::<i64>()?;
+                    assert_eq!(parent_data[(2, 1)], 0);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
     #[test]
     fn ndarray_tests() {
         bits_array_view();
@@ -151,5 +266,9 @@ mod tests {
         inline_array_view();
         copied_array_view();
         copied_array_view_mut();
+        move_fortran_ndarray();
+        move_standard_ndarray();
+        move_non_contiguous_ndarray();
+        reversed_sub_array_view();
     }
 }
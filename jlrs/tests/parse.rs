@@ -0,0 +1,104 @@
+mod util;
+#[cfg(all(feature = "sync-rt", feature = "internal-types"))]
+mod tests {
+    use jlrs::{
+        data::managed::{array::dimensions::Dims, internal::expr::Expr},
+        error::JlrsError,
+        prelude::*,
+    };
+
+    use super::util::JULIA;
+
+    fn parse_statements() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    let expr = Expr::parse(&mut frame, "x = 1\nf(y) = y + x\n\n")?;
+                    assert_eq!(expr.head().unwrap().as_str()?, "toplevel");
+
+                    let args = expr.args(&frame).unwrap();
+                    let n_args = unsafe { args.as_managed().dimensions().size() };
+                    assert_eq!(n_args, 2);
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn parse_does_not_evaluate() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    Expr::parse(&mut frame, "parse_not_evaluated = 1")?;
+                    assert!(Module::main(&frame)
+                        .global(&frame, "parse_not_evaluated")
+                        .is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn parse_error_has_position() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    let err = Expr::parse(&mut frame, "x = 1\n  y = )").unwrap_err();
+
+                    match *err {
+                        JlrsError::ParseError(ref err) => {
+                            assert_eq!(err.expr_line(), 2);
+                            assert_eq!(err.expr_column(), 3);
+                            assert!(!err.is_incomplete());
+                        }
+                        _ => panic!("expected a ParseError"),
+                    }
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn parse_incomplete() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|mut frame| {
+                    let err = Expr::parse(&mut frame, "function f()\n  1").unwrap_err();
+
+                    match *err {
+                        JlrsError::ParseError(ref err) => {
+                            assert_eq!(err.expr_line(), 1);
+                            assert!(err.is_incomplete());
+                        }
+                        _ => panic!("expected a ParseError"),
+                    }
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn parse_tests() {
+        parse_statements();
+        parse_does_not_evaluate();
+        parse_error_has_position();
+        parse_incomplete();
+    }
+}
@@ -0,0 +1,122 @@
+mod util;
+#[cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::{
+        error::{JlrsError, PkgError},
+        pkg::{PackageSpec, Pkg},
+        prelude::*,
+    };
+
+    use super::util::JULIA;
+
+    fn jlrs_core_is_installed() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|frame| unsafe {
+                    let installed = Pkg::installed(&frame)?;
+                    assert!(installed.iter().any(|info| info.name() == "JlrsCore"));
+
+                    let version = Pkg::installed_version(&frame, "JlrsCore")?;
+                    assert!(version.is_some());
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn unknown_package_is_not_installed() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|frame| unsafe {
+                    let err = Pkg::installed_version(&frame, "JlrsNonexistentPackage").unwrap_err();
+                    assert!(matches!(
+                        *err,
+                        JlrsError::PkgError(PkgError::NotInstalled { .. })
+                    ));
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn add_fails_in_offline_mode() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|frame| unsafe {
+                    Pkg::offline(&frame, true)?;
+                    let spec = PackageSpec::new("JlrsNonexistentPackage").version("1.0.0");
+                    let res = Pkg::add(&frame, &spec);
+                    Pkg::offline(&frame, false)?;
+
+                    assert!(matches!(
+                        *res.unwrap_err(),
+                        JlrsError::PkgError(PkgError::Failed { .. })
+                    ));
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn activate_missing_project() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|frame| unsafe {
+                    let path = std::env::temp_dir().join("jlrs-nonexistent-project");
+                    let err = Pkg::activate(&frame, path).unwrap_err();
+                    assert!(matches!(
+                        *err,
+                        JlrsError::PkgError(PkgError::ProjectNotFound { .. })
+                    ));
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn activate_and_instantiate_empty_project() {
+        JULIA.with(|j| {
+            let mut frame = StackFrame::new();
+            let mut jlrs = j.borrow_mut();
+
+            jlrs.instance(&mut frame)
+                .scope(|frame| unsafe {
+                    let path =
+                        std::env::temp_dir().join(format!("jlrs-pkg-{}", std::process::id()));
+                    std::fs::create_dir_all(&path).unwrap();
+                    std::fs::write(path.join("Project.toml"), "").unwrap();
+
+                    Pkg::activate(&frame, &path)?;
+                    Pkg::instantiate(&frame)?;
+                    let installed = Pkg::installed(&frame)?;
+                    assert!(installed.iter().all(|info| !info.is_direct_dep()));
+
+                    std::fs::remove_dir_all(&path).ok();
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn pkg_tests() {
+        jlrs_core_is_installed();
+        unknown_package_is_not_installed();
+        add_fails_in_offline_mode();
+        activate_missing_project();
+        // Must be last, changes the active project.
+        activate_and_instantiate_empty_project();
+    }
+}
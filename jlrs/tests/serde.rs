@@ -0,0 +1,250 @@
+mod util;
+
+#[cfg(test)]
+#[cfg(all(feature = "sync-rt", feature = "serde"))]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use jlrs::{
+        convert::serde::{from_value, to_value, to_value_with, StructRepr},
+        data::types::typecheck::NamedTuple,
+        prelude::*,
+    };
+    use serde::{Deserialize, Serialize};
+
+    use super::util::JULIA;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Point {
+        x: f64,
+        y: f64,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Labeled {
+        label: String,
+        points: Vec<Point>,
+        weight: Option<u32>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Line(Point, Point),
+        Rect { w: f64, h: f64 },
+    }
+
+    fn serialize_primitives() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let v = to_value(&mut frame, &3i64)?;
+                    assert_eq!(v.unbox::<i64>()?, 3);
+
+                    let v = to_value(&mut frame, &true)?;
+                    assert!(v.unbox::<bool>()?.as_bool());
+
+                    let v = to_value(&mut frame, "foo")?;
+                    assert_eq!(v.cast::<JuliaString>()?.as_str()?, "foo");
+
+                    let v = to_value(&mut frame, &Option::<u8>::None)?;
+                    assert!(v.is::<Nothing>());
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn serialize_struct() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let point = Point { x: 1.0, y: 2.0 };
+                    let v = to_value(&mut frame, &point)?;
+                    assert!(v.is::<NamedTuple>());
+                    assert_eq!(v.get_field(&mut frame, "y")?.unbox::<f64>()?, 2.0);
+
+                    let v = to_value_with(&mut frame, &point, StructRepr::Dict)?;
+                    assert!(v.datatype_name()? == "Dict");
+
+                    let point2: Point = from_value(v)?;
+                    assert_eq!(point, point2);
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn roundtrip_nested() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let labeled = Labeled {
+                        label: "line".into(),
+                        points: vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }],
+                        weight: Some(3),
+                    };
+
+                    let v = to_value(&mut frame, &labeled)?;
+                    let labeled2: Labeled = from_value(v)?;
+                    assert_eq!(labeled, labeled2);
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn roundtrip_vec() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let data = vec![1.0f64, 2.0, 3.0];
+                    let v = to_value(&mut frame, &data)?;
+                    assert!(v.is::<TypedArray<f64>>());
+
+                    let data2: Vec<f64> = from_value(v)?;
+                    assert_eq!(data, data2);
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn roundtrip_maps() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let mut map = HashMap::new();
+                    map.insert("a".to_string(), 1i64);
+                    map.insert("b".to_string(), 2i64);
+
+                    let v = to_value(&mut frame, &map)?;
+                    assert!(v.datatype_name()? == "Dict");
+
+                    let map2: HashMap<String, i64> = from_value(v)?;
+                    assert_eq!(map, map2);
+
+                    let mut map = BTreeMap::new();
+                    map.insert(1u32, vec![true, false]);
+                    let v = to_value(&mut frame, &map)?;
+                    let map2: BTreeMap<u32, Vec<bool>> = from_value(v)?;
+                    assert_eq!(map, map2);
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn roundtrip_enums() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let shapes = vec![
+                        Shape::Empty,
+                        Shape::Circle(1.0),
+                        Shape::Line(Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 2.0 }),
+                        Shape::Rect { w: 2.0, h: 3.0 },
+                    ];
+
+                    let v = to_value(&mut frame, &shapes[0])?;
+                    assert!(v.is::<Symbol>());
+
+                    let v = to_value(&mut frame, &shapes)?;
+                    let shapes2: Vec<Shape> = from_value(v)?;
+                    assert_eq!(shapes, shapes2);
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn deserialize_julia_data() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let v = Value::eval_string(&mut frame, "(1, \"two\", :three, [4 5; 6 7])")
+                        .into_jlrs_result()?;
+                    let data: (i64, String, String, Vec<i64>) = from_value(v)?;
+                    assert_eq!(data, (1, "two".into(), "three".into(), vec![4, 6, 5, 7]));
+
+                    let v = Value::eval_string(&mut frame, "Dict(:x => 1.0, :y => 2.0)")
+                        .into_jlrs_result()?;
+                    let point: Point = from_value(v)?;
+                    assert_eq!(point, Point { x: 1.0, y: 2.0 });
+
+                    let v = Value::eval_string(&mut frame, "1 + 2im").into_jlrs_result()?;
+                    let complex: BTreeMap<String, i64> = from_value(v)?;
+                    assert_eq!(complex["re"], 1);
+                    assert_eq!(complex["im"], 2);
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn deserialize_error() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| {
+                    let v = to_value(&mut frame, "foo")?;
+                    assert!(from_value::<Point>(v).is_err());
+                    assert!(from_value::<i64>(v).is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn serde_tests() {
+        serialize_primitives();
+        serialize_struct();
+        roundtrip_nested();
+        roundtrip_vec();
+        roundtrip_maps();
+        roundtrip_enums();
+        deserialize_julia_data();
+        deserialize_error();
+    }
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::prelude::*;
+
+    #[test]
+    fn shutdown_runs_atexit_hooks() {
+        let path = std::env::temp_dir().join(format!("jlrs-shutdown-{}", std::process::id()));
+        std::fs::remove_file(&path).ok();
+
+        let mut julia = unsafe { RuntimeBuilder::new().start().unwrap() };
+
+        let mut frame = StackFrame::new();
+        julia
+            .instance(&mut frame)
+            .scope(|mut frame| unsafe {
+                let path = JuliaString::new(&mut frame, path.to_string_lossy()).as_value();
+                let func = Value::eval_string(&mut frame, "p -> atexit(() -> write(p, \"exit\"))")
+                    .into_jlrs_result()?;
+                func.call1(&mut frame, path).into_jlrs_result()?;
+                Ok(())
+            })
+            .unwrap();
+
+        julia.shutdown(3);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, "exit");
+        std::fs::remove_file(&path).ok();
+    }
+}
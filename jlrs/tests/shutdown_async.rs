@@ -0,0 +1,57 @@
+#[cfg(all(feature = "tokio-rt",))]
+#[cfg(test)]
+mod tests {
+    use jlrs::{async_util::channel::TrySendError, prelude::*};
+
+    #[test]
+    fn shutdown_async_runtime() {
+        let (julia, handle) = unsafe {
+            RuntimeBuilder::new()
+                .async_runtime::<Tokio>()
+                .start::<2>()
+                .expect("Could not init Julia")
+        };
+
+        let (sender, receiver) = crossbeam_channel::bounded(1);
+        julia
+            .blocking_task(
+                |mut frame| unsafe {
+                    Value::eval_string(&mut frame, "1 + 1").into_jlrs_result()?;
+                    Ok(())
+                },
+                sender,
+            )
+            .try_dispatch_any()
+            .expect("Could not send blocking task");
+
+        let (report_sender, report_receiver) = crossbeam_channel::bounded(1);
+        julia
+            .startup_report(report_sender)
+            .try_dispatch_main()
+            .expect("Could not request the startup report");
+        assert!(report_receiver.recv().unwrap().unwrap().is_clean());
+
+        let (shutdown_sender, shutdown_receiver) = crossbeam_channel::bounded(1);
+        julia
+            .shutdown(0, shutdown_sender)
+            .try_dispatch_main()
+            .expect("Could not send shutdown task");
+
+        // Queued tasks are completed before the runtime shuts down.
+        receiver.recv().unwrap().unwrap();
+
+        // Both tasks are sent to the main queue, the blocking task has been handled before the
+        // shutdown task is received.
+        let report = shutdown_receiver.recv().unwrap().unwrap();
+        assert_eq!(report.pending_tasks(), 0);
+
+        // New tasks are rejected after the runtime has shut down.
+        let (sender, receiver) = crossbeam_channel::bounded::<JlrsResult<()>>(1);
+        let dispatch = julia.blocking_task(|_| Ok(()), sender).try_dispatch_any();
+        assert!(matches!(dispatch, Err(TrySendError::Closed(_))));
+        std::mem::drop(dispatch);
+        assert!(receiver.recv().is_err());
+
+        handle.join().unwrap().unwrap();
+    }
+}
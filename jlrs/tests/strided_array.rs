@@ -0,0 +1,157 @@
+mod util;
+
+#[cfg(test)]
+#[cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::{
+        data::managed::array::strided::{PermutedDimsArray, ReshapedArray, SubArray},
+        prelude::*,
+    };
+
+    use super::util::JULIA;
+
+    fn sub_array_access() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let view = Value::eval_string(
+                        &mut frame,
+                        "view(reshape(collect(1:12), 3, 4), 2:3, 2:4)",
+                    )
+                    .into_jlrs_result()?
+                    .cast::<SubArray>()?;
+
+                    assert!(view.parent().is::<Array>());
+                    assert_eq!(view.dimensions()?.as_slice(), &[2, 3]);
+                    assert_eq!(view.strides()?, vec![1, 3]);
+
+                    let data = view.bits_data::<i64>()?;
+                    assert_eq!(data[(0, 0)], 5);
+                    assert_eq!(data[(1, 2)], 12);
+                    assert!(data.get((2, 0)).is_none());
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn sub_array_mutate() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let parent = Value::eval_string(&mut frame, "collect(1.0:6.0)")
+                        .into_jlrs_result()?
+                        .cast::<Array>()?;
+                    let view_fn = Module::base(&frame).function(&frame, "view")?.as_managed();
+                    let range = Value::eval_string(&mut frame, "1:2:5").into_jlrs_result()?;
+
+                    let mut view = view_fn
+                        .call2(&mut frame, parent.as_value(), range)
+                        .into_jlrs_result()?
+                        .cast::<SubArray>()?;
+
+                    assert_eq!(view.offset1(), -1);
+                    assert_eq!(view.stride1(), 2);
+                    {
+                        let mut data = view.bits_data_mut::<f64>()?;
+                        data[1] = 0.0;
+                    }
+
+                    let parent_data = parent.bits_data::<f64>()?;
+                    assert_eq!(parent_data[2], 0.0);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn permuted_dims_array_access() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let view = Value::eval_string(
+                        &mut frame,
+                        "PermutedDimsArray(reshape(collect(1:6), 2, 3), (2, 1))",
+                    )
+                    .into_jlrs_result()?
+                    .cast::<PermutedDimsArray>()?;
+
+                    assert_eq!(view.permutation(), vec![2, 1]);
+                    assert_eq!(view.dimensions()?.as_slice(), &[3, 2]);
+
+                    let data = view.bits_data::<i64>()?;
+                    assert_eq!(data[(2, 1)], 6);
+                    assert_eq!(data[(1, 0)], 3);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn reshaped_array_access() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let view =
+                        Value::eval_string(&mut frame, "reshape(view(collect(1:8), 1:4), 2, 2)")
+                            .into_jlrs_result()?
+                            .cast::<ReshapedArray>()?;
+
+                    assert_eq!(view.dimensions()?.as_slice(), &[2, 2]);
+
+                    let data = view.bits_data::<i64>()?;
+                    assert_eq!(data[(1, 1)], 4);
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn invalid_layout() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let view = Value::eval_string(&mut frame, "view(Any[1, 2, 3], 1:2)")
+                        .into_jlrs_result()?
+                        .cast::<SubArray>()?;
+                    assert!(view.bits_data::<i64>().is_err());
+
+                    let view = Value::eval_string(&mut frame, "view([1, 2, 3], 1:2)")
+                        .into_jlrs_result()?
+                        .cast::<SubArray>()?;
+                    assert!(view.bits_data::<f32>().is_err());
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn strided_array_tests() {
+        sub_array_access();
+        sub_array_mutate();
+        permuted_dims_array_access();
+        reshaped_array_access();
+        invalid_layout();
+    }
+}
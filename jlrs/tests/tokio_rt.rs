@@ -7,7 +7,10 @@ mod async_util;
 mod tests {
     use std::{num::NonZeroUsize, sync::Arc};
 
-    use jlrs::prelude::*;
+    use jlrs::{
+        error::{JlrsError, RuntimeError},
+        prelude::*,
+    };
     use once_cell::sync::OnceCell;
 
     use super::async_util::{async_tasks::*, ASYNC_TESTS_JL};
@@ -46,6 +49,26 @@ mod tests {
 
     pub static JULIA: OnceCell<Arc<AsyncJulia<Tokio>>> = OnceCell::new();
 
+    // Polls the global `flag` set by `InterruptibleSleepTask` until it's `true` or 10 seconds
+    // have passed.
+    fn interrupted(julia: &AsyncJulia<Tokio>, flag: &'static str) -> bool {
+        for _ in 0..100 {
+            let (sender, receiver) = crossbeam_channel::bounded(1);
+            julia
+                .task(InterruptedTask { flag }, sender)
+                .try_dispatch_any()
+                .unwrap();
+
+            if receiver.recv().unwrap().unwrap() {
+                return true;
+            }
+
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+
+        false
+    }
+
     #[test]
     fn test_task() {
         let julia = JULIA.get_or_init(init);
@@ -475,6 +498,66 @@ mod tests {
         assert_eq!(receiver.recv().unwrap().unwrap(), 20_009.0);
     }
 
+    #[test]
+    fn test_call_latest_task() {
+        let julia = JULIA.get_or_init(init);
+
+        let (sender, receiver) = crossbeam_channel::bounded(1);
+
+        julia
+            .task(CallLatestTask, sender)
+            .try_dispatch_any()
+            .unwrap();
+
+        assert_eq!(receiver.recv().unwrap().unwrap(), 3.0);
+    }
+
+    #[test]
+    fn test_call_async_timeout() {
+        let julia = JULIA.get_or_init(init);
+
+        for (interrupt, flag) in [(false, "timeout_continued"), (true, "timeout_interrupted")] {
+            let (sender, receiver) = crossbeam_channel::bounded(1);
+
+            julia
+                .task(TimeoutTask { interrupt, flag }, sender)
+                .try_dispatch_any()
+                .unwrap();
+
+            assert!(receiver.recv().unwrap().unwrap());
+            // The Julia task is waiting in `sleep`, so it's only interrupted if requested.
+            assert_eq!(interrupted(julia, flag), interrupt);
+        }
+    }
+
+    #[test]
+    fn test_cancel_task() {
+        let julia = JULIA.get_or_init(init);
+
+        let (sender, receiver) = crossbeam_channel::bounded(1);
+
+        let task = InterruptibleSleepTask {
+            seconds: 60.0,
+            flag: "cancel_interrupted",
+        };
+        let (dispatch, handle) = julia.cancellable_task(task, sender);
+        dispatch.try_dispatch_any().unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        handle.cancel();
+
+        let res = receiver
+            .recv_timeout(std::time::Duration::from_secs(10))
+            .unwrap();
+        assert!(matches!(
+            *res.unwrap_err(),
+            JlrsError::RuntimeError(RuntimeError::Cancelled)
+        ));
+
+        // The Julia task was waiting in `sleep`, so it has been interrupted.
+        assert!(interrupted(julia, "cancel_interrupted"));
+    }
+
     #[test]
     fn test_borrow_array_data() {
         let julia = JULIA.get_or_init(init);
@@ -0,0 +1,100 @@
+mod util;
+
+#[cfg(test)]
+#[cfg(feature = "sync-rt")]
+mod tests {
+    use jlrs::{data::managed::function::TypedFunction, prelude::*};
+
+    use super::util::JULIA;
+
+    fn create_and_call() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Module::base(&frame).function(&frame, "+")?.as_managed();
+                    let add = TypedFunction::<(f64, f64), f64>::new(func)?;
+                    assert_eq!(add.call(&mut frame, (1.0, 2.0))?, 3.0);
+                    assert_eq!(add.function().as_value(), func.as_value());
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn create_with_managed_args() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let arr = TypedArray::<f64>::from_vec(&mut frame, vec![1.0, 2.0, 3.0], 3)?
+                        .into_jlrs_result()?;
+                    let func = Module::base(&frame).function(&frame, "sum")?.as_managed();
+                    let sum = TypedFunction::<(TypedArray<f64>,), f64>::new(func)?;
+                    assert_eq!(sum.call(&mut frame, (arr,))?, 6.0);
+
+                    let func = Module::base(&frame)
+                        .function(&frame, "length")?
+                        .as_managed();
+                    let length = TypedFunction::<(Vec<String>,), isize>::new(func)?;
+                    let data = vec![String::from("a"), String::from("b")];
+                    assert_eq!(length.call(&mut frame, (data,))?, 2);
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn no_matching_method() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|frame| unsafe {
+                    let func = Module::base(&frame).function(&frame, "sqrt")?.as_managed();
+                    assert!(TypedFunction::<(f64, f64), f64>::new(func).is_err());
+                    assert!(TypedFunction::<(String,), f64>::new(func).is_err());
+                    assert!(TypedFunction::<(), f64>::new(func).is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    fn wrong_return_type() {
+        JULIA.with(|j| {
+            let mut julia = j.borrow_mut();
+            let mut frame = StackFrame::new();
+
+            julia
+                .instance(&mut frame)
+                .scope(|mut frame| unsafe {
+                    let func = Module::base(&frame).function(&frame, "+")?.as_managed();
+                    let add = TypedFunction::<(i64, i64), f64>::new(func)?;
+                    assert!(add.call(&mut frame, (1, 2)).is_err());
+
+                    Ok(())
+                })
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn typed_function_tests() {
+        create_and_call();
+        create_with_managed_args();
+        no_matching_method();
+        wrong_return_type();
+    }
+}
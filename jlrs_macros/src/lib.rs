@@ -47,6 +47,15 @@ use self::version::emit_if_compatible;
 ///     #[gc_safe]
 ///     fn foo(arr: Array) -> usize as bar;
 ///
+///     // If an exported function or method panics, the panic is caught and rethrown as a
+///     // `JlrsCore.RustPanic` exception that contains the panic message and backtrace. The
+///     // backtrace is only captured if `RUST_BACKTRACE` is set. You can opt out of catching
+///     // panics with the `#[no_catch_panic]` attribute, a panic that isn't caught aborts the
+///     // process.
+///     #[gc_safe]
+///     #[no_catch_panic]
+///     fn foo(arr: Array) -> usize as bar_unchecked;
+///
 ///     // Exports the function `foo` as `bar!` in the `Base` module.
 ///     //
 ///     // This syntax can be used to extend existing functions.
@@ -116,6 +125,11 @@ use self::version::emit_if_compatible;
 /// end
 /// ```
 ///
+/// The first time a module generated with this macro is initialized, the process-global panic
+/// hook is replaced with a hook that captures the backtrace of the panic for
+/// `JlrsCore.RustPanic` and calls the previous hook. A panic hook that is set later must call
+/// the hook it replaces, otherwise caught panics have no backtrace.
+///
 /// It can be rather tricky to figure out how data is passed from Julia to Rust when `ccall`ing
 /// a function written in Rust. Primitive and `isbits` types are passed by value, managed types
 /// provided directly by jlrs are guaranteed to be boxed, all other types might be passed by
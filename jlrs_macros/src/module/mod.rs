@@ -14,7 +14,8 @@ use syn::{
     spanned::Spanned,
     token::{Brace, Bracket, Comma},
     AttrStyle, Attribute, Error, Expr, ExprLit, FnArg, GenericArgument, Ident, ItemFn, Lit, Meta,
-    Path, PathArguments, Result, ReturnType, Signature, Token, Type, TypeImplTrait, TypeParamBound,
+    Path, PathArguments, Result, ReturnType, Signature, Stmt, Token, Type, TypeImplTrait,
+    TypeParamBound,
 };
 
 use self::parameters::{Apply, ResolvedParameterList};
@@ -234,6 +235,7 @@ impl ExportedFunction {
         env: Option<&ParameterEnvironment>,
         offset: &mut usize,
         gc_safe: bool,
+        catch_panic: bool,
     ) -> Result<Expr> {
         let n_args = self.func.inputs.len();
         let name_ident = &self.func.ident;
@@ -279,11 +281,12 @@ impl ExportedFunction {
             });
             let names = Punctuated::<_, Comma>::from_iter(names);
 
-            let call_expr: Expr = if gc_safe {
-                parse_quote! {  ::jlrs::memory::gc::gc_safe(|| #name_ident(#names)) }
-            } else {
-                parse_quote! { #name_ident(#names) }
-            };
+            let call_expr = call_expr_fragment(
+                parse_quote! { #name_ident(#names) },
+                gc_safe,
+                catch_panic,
+                false,
+            );
 
             let span = self.func.span();
             let invoke_fn: ItemFn = parse_quote_spanned! {
@@ -425,6 +428,7 @@ impl ExportedMethod {
         offset: &mut usize,
         untracked_self: bool,
         gc_safe: bool,
+        catch_panic: bool,
     ) -> Result<Expr> {
         let n_args = self.func.inputs.len();
         let name_ident = &self.func.ident;
@@ -459,7 +463,7 @@ impl ExportedMethod {
             let ccall_arg_idx = 0..n_args;
             let julia_arg_idx = 0..n_args;
 
-            let (ccall_arg_types, julia_arg_types, invoke_fn) = method_arg_type_fragments_in_env(self, &resolver, untracked_self, gc_safe);
+            let (ccall_arg_types, julia_arg_types, invoke_fn) = method_arg_type_fragments_in_env(self, &resolver, untracked_self, gc_safe, catch_panic);
 
             let ex = parse_quote! {
                 {
@@ -1042,10 +1046,12 @@ impl<'a> GenericEnvironment<'a> {
             .map(|it| it.get_exported_fn())
             .map(|it| {
                 let mut gc_safe = false;
+                let mut catch_panic = true;
                 if let Some(attrs) = it.1 {
                     gc_safe = has_outer_path_attr(attrs, "gc_safe");
+                    catch_panic = !has_outer_path_attr(attrs, "no_catch_panic");
                 }
-                it.0.init_with_env(self, env, offset, gc_safe)
+                it.0.init_with_env(self, env, offset, gc_safe, catch_panic)
             })
             .collect::<Result<Vec<_>>>()?;
 
@@ -1081,11 +1087,13 @@ impl<'a> GenericEnvironment<'a> {
             .map(|it| {
                 let mut untracked_self = false;
                 let mut gc_safe = false;
+                let mut catch_panic = true;
                 if let Some(attrs) = it.1 {
                     untracked_self = has_outer_path_attr(attrs, "untracked_self");
                     gc_safe = has_outer_path_attr(attrs, "gc_safe");
+                    catch_panic = !has_outer_path_attr(attrs, "no_catch_panic");
                 }
-                it.0.init_with_env(self, env, offset, untracked_self, gc_safe)
+                it.0.init_with_env(self, env, offset, untracked_self, gc_safe, catch_panic)
             }) // TODO: attrs
             .collect::<Result<Vec<_>>>()?;
 
@@ -1556,6 +1564,9 @@ impl JuliaModule {
 
                 #doc_init_fn
 
+                // Caught panics are rethrown as an instance of this type.
+                static __JLRS_RUST_PANIC: ::jlrs::ccall::RustPanic = ::jlrs::ccall::RustPanic::new();
+
                 static IS_INIT: ::std::sync::atomic::AtomicBool = ::std::sync::atomic::AtomicBool::new(false);
                 if IS_INIT.compare_exchange(false, true, ::std::sync::atomic::Ordering::Relaxed, ::std::sync::atomic::Ordering::Relaxed).is_err() {
                     let unrooted = <::jlrs::data::managed::module::Module as ::jlrs::data::managed::Managed>::unrooted_target(module);
@@ -1567,7 +1578,9 @@ impl JuliaModule {
 
                 ccall.init_jlrs(&::jlrs::InstallJlrsCore::Default, Some(module));
 
-                ccall.scope(|mut frame| {
+                let res = ccall.scope(|mut frame| {
+                    __JLRS_RUST_PANIC.init()?;
+
                     let wrap_mod = ::jlrs::data::managed::module::Module::main(&frame)
                         .submodule(&frame, "JlrsCore")
                         .unwrap()
@@ -1613,7 +1626,11 @@ impl JuliaModule {
                         #doc_init_fn_ident(&mut frame, &mut doc_items, module, doc_item_ty);
                     }
                     Ok(module_info_ty.instantiate_unchecked(&frame, [arr.as_value(), doc_items.as_value()]).leak())
-                }).unwrap()
+                });
+
+                // Errors are thrown as a `JlrsCore.JlrsError`, `ccall` must be dropped first.
+                ::std::mem::drop(ccall);
+                <::jlrs::error::JlrsResult<::jlrs::data::managed::value::ValueRet> as ::jlrs::convert::ccall_types::CCallReturn>::return_or_throw(res)
             }
         };
 
@@ -2447,15 +2464,18 @@ fn function_info_fragment(
     let names = Punctuated::<_, Comma>::from_iter(names);
 
     let mut gc_safe = false;
+    let mut catch_panic = true;
     if let Some(attrs) = attrs {
         gc_safe = has_outer_path_attr(attrs, "gc_safe");
+        catch_panic = !has_outer_path_attr(attrs, "no_catch_panic");
     }
 
-    let call_expr: Expr = if gc_safe {
-        parse_quote! {  ::jlrs::memory::gc::gc_safe(|| #name_ident(#names)) }
-    } else {
-        parse_quote! { #name_ident(#names) }
-    };
+    let call_expr = call_expr_fragment(
+        parse_quote! { #name_ident(#names) },
+        gc_safe,
+        catch_panic,
+        false,
+    );
 
     let span = info.func.span();
     let invoke_fn: ItemFn = parse_quote_spanned! {
@@ -2754,10 +2774,12 @@ fn method_info_fragment<'a>(
 
     let mut untracked_self = false;
     let mut gc_safe = false;
+    let mut catch_panic = true;
 
     if let Some(attrs) = attrs {
         untracked_self = has_outer_path_attr(attrs, "untracked_self");
         gc_safe = has_outer_path_attr(attrs, "gc_safe");
+        catch_panic = !has_outer_path_attr(attrs, "no_catch_panic");
     }
 
     let override_module_fragment = override_module_fragment(&info.name_override);
@@ -2780,7 +2802,7 @@ fn method_info_fragment<'a>(
     let julia_arg_idx = 0..n_args;
 
     let (ccall_arg_types, julia_arg_types, invoke_fn) =
-        method_arg_type_fragments(info, untracked_self, gc_safe);
+        method_arg_type_fragments(info, untracked_self, gc_safe, catch_panic);
 
     parse_quote! {
         {
@@ -3012,6 +3034,7 @@ fn method_arg_type_fragments<'a>(
     info: &'a ExportedMethod,
     untracked_self: bool,
     gc_safe: bool,
+    catch_panic: bool,
 ) -> (
     impl 'a + Iterator<Item = Expr>,
     impl 'a + Iterator<Item = Expr>,
@@ -3025,10 +3048,16 @@ fn method_arg_type_fragments<'a>(
     };
 
     let invoke_fn = match takes_self {
-        None => invoke_fn_no_self_method_fragment(info, gc_safe),
-        Some((true, true)) => invoke_fn_mut_self_method_fragment(info, untracked_self, gc_safe),
-        Some((false, true)) => invoke_fn_ref_self_method_fragment(info, untracked_self, gc_safe),
-        Some((_, false)) => invoke_fn_move_self_method_fragment(info, untracked_self, gc_safe),
+        None => invoke_fn_no_self_method_fragment(info, gc_safe, catch_panic),
+        Some((true, true)) => {
+            invoke_fn_mut_self_method_fragment(info, untracked_self, gc_safe, catch_panic)
+        }
+        Some((false, true)) => {
+            invoke_fn_ref_self_method_fragment(info, untracked_self, gc_safe, catch_panic)
+        }
+        Some((_, false)) => {
+            invoke_fn_move_self_method_fragment(info, untracked_self, gc_safe, catch_panic)
+        }
     };
 
     let parent = &info.parent;
@@ -3080,6 +3109,7 @@ fn method_arg_type_fragments_in_env<'a>(
     resolver: &'a ResolvedParameterList,
     untracked_self: bool,
     gc_safe: bool,
+    catch_panic: bool,
 ) -> (
     impl 'a + Iterator<Item = Expr>,
     impl 'a + Iterator<Item = Expr>,
@@ -3093,16 +3123,28 @@ fn method_arg_type_fragments_in_env<'a>(
     };
 
     let invoke_fn = match takes_self {
-        None => invoke_fn_no_self_method_fragment_in_env(info, resolver, gc_safe),
-        Some((true, true)) => {
-            invoke_fn_mut_self_method_fragment_in_env(info, resolver, untracked_self, gc_safe)
-        }
-        Some((false, true)) => {
-            invoke_fn_ref_self_method_fragment_in_env(info, resolver, untracked_self, gc_safe)
-        }
-        Some((_, false)) => {
-            invoke_fn_move_self_method_fragment_in_env(info, resolver, untracked_self, gc_safe)
-        }
+        None => invoke_fn_no_self_method_fragment_in_env(info, resolver, gc_safe, catch_panic),
+        Some((true, true)) => invoke_fn_mut_self_method_fragment_in_env(
+            info,
+            resolver,
+            untracked_self,
+            gc_safe,
+            catch_panic,
+        ),
+        Some((false, true)) => invoke_fn_ref_self_method_fragment_in_env(
+            info,
+            resolver,
+            untracked_self,
+            gc_safe,
+            catch_panic,
+        ),
+        Some((_, false)) => invoke_fn_move_self_method_fragment_in_env(
+            info,
+            resolver,
+            untracked_self,
+            gc_safe,
+            catch_panic,
+        ),
     };
 
     let parent = resolver.apply(&info.parent);
@@ -3387,7 +3429,11 @@ fn invoke_async_callback_in_env<'a>(
     }
 }
 
-fn invoke_fn_no_self_method_fragment(info: &ExportedMethod, gc_safe: bool) -> ItemFn {
+fn invoke_fn_no_self_method_fragment(
+    info: &ExportedMethod,
+    gc_safe: bool,
+    catch_panic: bool,
+) -> ItemFn {
     let name = &info.func.ident;
     let span = info.func.ident.span();
     let ty = &info.parent;
@@ -3403,15 +3449,12 @@ fn invoke_fn_no_self_method_fragment(info: &ExportedMethod, gc_safe: bool) -> It
 
     let names = Punctuated::<_, Comma>::from_iter(names);
 
-    let call_expr: Expr = if gc_safe {
-        parse_quote! {
-            ::jlrs::memory::gc::gc_safe(|| {
-                <#ty>::#name(#names)
-            })
-        }
-    } else {
-        parse_quote! { <#ty>::#name(#names) }
-    };
+    let call_expr = call_expr_fragment(
+        parse_quote! { <#ty>::#name(#names) },
+        gc_safe,
+        catch_panic,
+        false,
+    );
 
     parse_quote_spanned! {
         span=> unsafe extern "C" fn invoke(#args) #new_ret_ty {
@@ -3425,6 +3468,7 @@ fn invoke_fn_no_self_method_fragment_in_env(
     info: &ExportedMethod,
     resolver: &ResolvedParameterList,
     gc_safe: bool,
+    catch_panic: bool,
 ) -> ItemFn {
     let name = &info.func.ident;
     let span = info.func.ident.span();
@@ -3442,15 +3486,12 @@ fn invoke_fn_no_self_method_fragment_in_env(
         })
         .collect();
 
-    let call_expr: Expr = if gc_safe {
-        parse_quote! {
-            ::jlrs::memory::gc::gc_safe(|| {
-                <#ty>::#name(#names)
-            })
-        }
-    } else {
-        parse_quote! { <#ty>::#name(#names) }
-    };
+    let call_expr = call_expr_fragment(
+        parse_quote! { <#ty>::#name(#names) },
+        gc_safe,
+        catch_panic,
+        false,
+    );
 
     parse_quote_spanned! {
         span=> unsafe extern "C" fn invoke(#args) #new_ret_ty {
@@ -3464,6 +3505,7 @@ fn invoke_fn_ref_self_method_fragment(
     info: &ExportedMethod,
     untracked_self: bool,
     gc_safe: bool,
+    catch_panic: bool,
 ) -> ItemFn {
     let name = &info.func.ident;
     let span = info.func.ident.span();
@@ -3495,15 +3537,12 @@ fn invoke_fn_ref_self_method_fragment(
         parse_quote! { (&this).track_shared() }
     };
 
-    let call_expr: Expr = if gc_safe {
-        parse_quote! {
-            ::jlrs::memory::gc::gc_safe(|| {
-                this.#name(#names)
-            })
-        }
-    } else {
-        parse_quote! { this.#name(#names) }
-    };
+    let call_expr = call_expr_fragment(
+        parse_quote! { this.#name(#names) },
+        gc_safe,
+        catch_panic,
+        true,
+    );
 
     parse_quote_spanned! {
         span=> unsafe extern "C" fn invoke(#args_self_renamed) #new_ret_ty {
@@ -3523,6 +3562,7 @@ fn invoke_fn_ref_self_method_fragment_in_env(
     resolver: &ResolvedParameterList,
     untracked_self: bool,
     gc_safe: bool,
+    catch_panic: bool,
 ) -> ItemFn {
     let name = &info.func.ident;
     let span = info.func.ident.span();
@@ -3554,15 +3594,12 @@ fn invoke_fn_ref_self_method_fragment_in_env(
         parse_quote! { (&this).track_shared() }
     };
 
-    let call_expr: Expr = if gc_safe {
-        parse_quote! {
-            ::jlrs::memory::gc::gc_safe(|| {
-                this.#name(#names)
-            })
-        }
-    } else {
-        parse_quote! { this.#name(#names) }
-    };
+    let call_expr = call_expr_fragment(
+        parse_quote! { this.#name(#names) },
+        gc_safe,
+        catch_panic,
+        true,
+    );
 
     parse_quote_spanned! {
         span=> unsafe extern "C" fn invoke(#args_self_renamed) #new_ret_ty {
@@ -3581,6 +3618,7 @@ fn invoke_fn_move_self_method_fragment(
     info: &ExportedMethod,
     untracked_self: bool,
     gc_safe: bool,
+    catch_panic: bool,
 ) -> ItemFn {
     let name = &info.func.ident;
     let span = info.func.ident.span();
@@ -3612,15 +3650,12 @@ fn invoke_fn_move_self_method_fragment(
         parse_quote! { (&this).track_shared() }
     };
 
-    let call_expr: Expr = if gc_safe {
-        parse_quote! {
-            ::jlrs::memory::gc::gc_safe(|| {
-                this.clone().#name(#names)
-            })
-        }
-    } else {
-        parse_quote! { this.clone().#name(#names) }
-    };
+    let call_expr = call_expr_fragment(
+        parse_quote! { this.clone().#name(#names) },
+        gc_safe,
+        catch_panic,
+        true,
+    );
 
     parse_quote_spanned! {
         span=> unsafe extern "C" fn invoke(#args_self_renamed) #new_ret_ty {
@@ -3640,6 +3675,7 @@ fn invoke_fn_move_self_method_fragment_in_env(
     resolver: &ResolvedParameterList,
     untracked_self: bool,
     gc_safe: bool,
+    catch_panic: bool,
 ) -> ItemFn {
     let name = &info.func.ident;
     let span = info.func.ident.span();
@@ -3671,15 +3707,12 @@ fn invoke_fn_move_self_method_fragment_in_env(
         parse_quote! { (&this).track_shared() }
     };
 
-    let call_expr: Expr = if gc_safe {
-        parse_quote! {
-            ::jlrs::memory::gc::gc_safe(|| {
-                this.clone().#name(#names)
-            })
-        }
-    } else {
-        parse_quote! { this.clone().#name(#names) }
-    };
+    let call_expr = call_expr_fragment(
+        parse_quote! { this.clone().#name(#names) },
+        gc_safe,
+        catch_panic,
+        true,
+    );
 
     parse_quote_spanned! {
         span=> unsafe extern "C" fn invoke(#args_self_renamed) #new_ret_ty {
@@ -3698,6 +3731,7 @@ fn invoke_fn_mut_self_method_fragment(
     info: &ExportedMethod,
     untracked_self: bool,
     gc_safe: bool,
+    catch_panic: bool,
 ) -> ItemFn {
     let name = &info.func.ident;
     let span = info.func.ident.span();
@@ -3728,15 +3762,12 @@ fn invoke_fn_mut_self_method_fragment(
         parse_quote! { (&mut this).track_exclusive() }
     };
 
-    let call_expr: Expr = if gc_safe {
-        parse_quote! {
-            ::jlrs::memory::gc::gc_safe(|| {
-                this.#name(#names)
-            })
-        }
-    } else {
-        parse_quote! { this.#name(#names) }
-    };
+    let call_expr = call_expr_fragment(
+        parse_quote! { this.#name(#names) },
+        gc_safe,
+        catch_panic,
+        true,
+    );
 
     parse_quote_spanned! {
         span=> unsafe extern "C" fn invoke(#args_self_renamed) #new_ret_ty {
@@ -3757,6 +3788,7 @@ fn invoke_fn_mut_self_method_fragment_in_env(
     resolver: &ResolvedParameterList,
     untracked_self: bool,
     gc_safe: bool,
+    catch_panic: bool,
 ) -> ItemFn {
     let name = &info.func.ident;
     let span = info.func.ident.span();
@@ -3787,15 +3819,12 @@ fn invoke_fn_mut_self_method_fragment_in_env(
         parse_quote! { (&mut this).track_exclusive() }
     };
 
-    let call_expr: Expr = if gc_safe {
-        parse_quote! {
-            ::jlrs::memory::gc::gc_safe(|| {
-                this.#name(#names)
-            })
-        }
-    } else {
-        parse_quote! { this.#name(#names) }
-    };
+    let call_expr = call_expr_fragment(
+        parse_quote! { this.#name(#names) },
+        gc_safe,
+        catch_panic,
+        true,
+    );
 
     parse_quote_spanned! {
         span=> unsafe extern "C" fn invoke(#args_self_renamed) #new_ret_ty {
@@ -3811,6 +3840,46 @@ fn invoke_fn_mut_self_method_fragment_in_env(
     }
 }
 
+// Wraps the call to an exported function or method. If `gc_safe` is true the function is called
+// in a GC-safe state. If `catch_panic` is true panics are caught and rethrown as a
+// `JlrsCore.RustPanic` exception, `this` is dropped before the exception is thrown if
+// `takes_self` is true.
+fn call_expr_fragment(call: Expr, gc_safe: bool, catch_panic: bool, takes_self: bool) -> Expr {
+    let call: Expr = if catch_panic {
+        parse_quote! {
+            ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| #call))
+        }
+    } else {
+        call
+    };
+
+    let call: Expr = if gc_safe {
+        parse_quote! { ::jlrs::memory::gc::gc_safe(|| #call) }
+    } else {
+        call
+    };
+
+    if !catch_panic {
+        return call;
+    }
+
+    let drop_this: Option<Stmt> = if takes_self {
+        Some(parse_quote! { ::std::mem::drop(this); })
+    } else {
+        None
+    };
+
+    parse_quote! {
+        match #call {
+            Ok(res) => res,
+            Err(payload) => {
+                #drop_this
+                __JLRS_RUST_PANIC.throw(payload)
+            }
+        }
+    }
+}
+
 fn has_outer_path_attr(attrs: &[Attribute], name: &str) -> bool {
     for attr in attrs {
         match attr.style {
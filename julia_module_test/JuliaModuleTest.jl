@@ -34,6 +34,9 @@ using Test
     @test JuliaModuleTest.freestanding_func_ret_rust_result(false) == 3
     @inferred JuliaModuleTest.freestanding_func_ret_rust_result(false)
     @test_throws JlrsCore.JlrsError JuliaModuleTest.freestanding_func_ret_rust_result(true)
+
+    @test_throws JlrsCore.RustPanic JuliaModuleTest.freestanding_func_panics()
+    @test JuliaModuleTest.freestanding_func_noargs_no_catch() == 0
 end
 
 @testset "OpaqueInt" begin
@@ -97,6 +97,11 @@ fn freestanding_func_ret_rust_result(throw_err: Bool) -> JlrsResult<i32> {
     }
 }
 
+#[inline]
+fn freestanding_func_panics() -> usize {
+    panic!("freestanding_func_panics")
+}
+
 #[derive(Clone, Debug)]
 struct OpaqueInt {
     a: i32,
@@ -330,6 +335,10 @@ julia_module! {
     fn freestanding_func_typevaluearg(a: TypedValue<usize>) -> usize;
     fn freestanding_func_ret_array(dt: DataType) -> ArrayRet;
     fn freestanding_func_ret_rust_result(throw_err: Bool) -> JlrsResult<i32>;
+    fn freestanding_func_panics() -> usize;
+
+    #[no_catch_panic]
+    fn freestanding_func_noargs() -> usize as freestanding_func_noargs_no_catch;
 
     struct OpaqueInt;
     in OpaqueInt fn new(value: i32) -> TypedValueRet<OpaqueInt> as OpaqueInt;